@@ -9,12 +9,17 @@ pub struct Args {
     pub command: Option<Command>,
 
     /// Optional path to the configuration file
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     pub config: Option<String>,
 
     /// Use local protobuf files
     #[arg(short, long)]
     pub local_protos: bool,
+
+    /// A precompiled `FileDescriptorSet` file, as produced by
+    /// `protoc --descriptor_set_out` or `buf build -o`. Can be repeated.
+    #[arg(long = "protoset", global = true)]
+    pub protosets: Vec<String>,
 }
 
 #[derive(Debug, Subcommand)]
@@ -24,6 +29,35 @@ pub enum Command {
     /// Setup wireman and create a default configuration file.
     #[command(aliases = ["setup", "install"])]
     Init,
+    /// Sends a single request and prints the response as JSON.
+    Call(CallArgs),
+}
+
+#[derive(Debug, clap::Args)]
+pub struct CallArgs {
+    /// The method to call, e.g. `package.Service/Method`.
+    pub method: String,
+
+    /// The server address. Defaults to the configured address.
+    #[arg(short, long)]
+    pub address: Option<String>,
+
+    /// The request message as JSON. Use `@file.json` to read from a file
+    /// or `@-` to read from stdin. Repeat it for client streaming methods.
+    #[arg(short, long)]
+    pub data: Vec<String>,
+
+    /// A request header as `key:value`. Can be repeated.
+    #[arg(short = 'H', long = "header")]
+    pub headers: Vec<String>,
+
+    /// The deadline of the request, e.g. "10s". Defaults to the configured timeout.
+    #[arg(short, long)]
+    pub timeout: Option<String>,
+
+    /// The environment whose address, auth, TLS and variables are used.
+    #[arg(short, long)]
+    pub env: Option<String>,
 }
 
 #[must_use]
@@ -37,7 +71,7 @@ pub fn parse(version: &'static str) -> Args {
         Some(Command::Init) => {
             install();
         }
-        None => {}
+        Some(Command::Call(_)) | None => {}
     }
     args
 }
@@ -5,21 +5,37 @@ use crate::install::expand_file;
 use crate::install::expand_path;
 use logger::LogLevel;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs::read_to_string;
 use std::path::Path;
 use std::str::FromStr;
+use std::time::Duration;
 use theme::Config as ThemeConfig;
 
 /// The top level config.
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 pub struct Config {
     /// The include directories in which to search for the protos
+    #[serde(default)]
     pub includes: Vec<String>,
     /// A list of proto files such as [internal.proto, api.proto]
+    #[serde(default)]
     pub files: Vec<String>,
+    /// A list of precompiled `FileDescriptorSet` files such as [api.protoset]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub protosets: Vec<String>,
     /// The history config
     #[serde(default)]
     pub history: HistoryConfig,
+    /// The collections config
+    #[serde(default, skip_serializing_if = "CollectionsConfig::is_empty")]
+    pub collections: CollectionsConfig,
+    /// The reflection cache config
+    #[serde(default, skip_serializing_if = "ReflectionConfig::is_empty")]
+    pub reflection: ReflectionConfig,
+    /// The JSON serialization profile of the messages
+    #[serde(default, skip_serializing_if = "JsonConfig::is_default")]
+    pub json: JsonConfig,
     /// The server config
     #[serde(default)]
     pub server: ServerConfig,
@@ -32,6 +48,9 @@ pub struct Config {
     /// Optional TLS settings
     #[serde(default)]
     pub tls: TlsConfig,
+    /// Optional named environments such as local, staging or prod
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub environments: BTreeMap<String, EnvironmentConfig>,
 }
 
 impl Config {
@@ -94,6 +113,13 @@ impl Config {
     pub fn files(&self) -> Vec<String> {
         self.files.iter().map(|e| expand_file(e)).collect()
     }
+
+    /// Gets the protoset files. Tries to shell expand the path if it
+    /// contains environment variables such as $HOME or ~.
+    #[must_use]
+    pub fn protosets(&self) -> Vec<String> {
+        self.protosets.iter().map(|e| expand_file(e)).collect()
+    }
 }
 
 impl FromStr for Config {
@@ -116,6 +142,16 @@ pub struct ServerConfig {
     pub default_address: Option<String>,
     /// The default auth header
     pub default_auth_header: Option<String>,
+    /// The default deadline of a request, e.g. "10s" or "500ms"
+    pub default_timeout: Option<String>,
+    /// The default compression of the sent messages, e.g. "gzip"
+    pub default_send_encoding: Option<String>,
+    /// The default encodings that the server may use to compress responses
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub default_accept_encodings: Vec<String>,
+    /// The transport per address. Unlisted addresses use native grpc.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub transports: BTreeMap<String, Transport>,
 }
 
 impl ServerConfig {
@@ -135,10 +171,112 @@ impl ServerConfig {
         Self {
             default_address,
             default_auth_header,
+            default_timeout: None,
+            default_send_encoding: None,
+            default_accept_encodings: Vec::new(),
+            transports: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the parsed default deadline. Returns `None` if no
+    /// deadline is configured.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the deadline cannot be parsed.
+    pub fn default_timeout(&self) -> Result<Option<Duration>> {
+        let Some(timeout) = &self.default_timeout else {
+            return Ok(None);
+        };
+        parse_duration(timeout)
+            .map(Some)
+            .ok_or_else(|| Error::InvalidTimeoutError(timeout.clone()))
+    }
+
+    /// Returns the transport of an address. A trailing slash is ignored.
+    #[must_use]
+    pub fn transport(&self, address: &str) -> Transport {
+        let address = address.trim_end_matches('/');
+        self.transports
+            .iter()
+            .find(|(addr, _)| addr.trim_end_matches('/') == address)
+            .map(|(_, transport)| *transport)
+            .unwrap_or_default()
+    }
+}
+
+/// The wire protocol used to talk to a server.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, PartialOrd)]
+#[serde(rename_all = "kebab-case")]
+pub enum Transport {
+    /// Native grpc over HTTP/2
+    #[default]
+    Grpc,
+    /// grpc-web with binary messages, e.g. through an envoy proxy
+    GrpcWeb,
+    /// grpc-web with base64 encoded messages
+    GrpcWebText,
+}
+
+/// A named environment. Overrides the server and TLS config and
+/// defines variables that are substituted as `{{var}}`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct EnvironmentConfig {
+    /// The address of the environment
+    pub address: Option<String>,
+    /// The auth header of the environment
+    pub auth_header: Option<String>,
+    /// The TLS settings of the environment
+    pub tls: Option<TlsConfig>,
+    /// The variables of the environment
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+}
+
+impl EnvironmentConfig {
+    /// Replaces all `{{var}}` placeholders with the value of the variable.
+    /// Unknown variables are kept as they are.
+    #[must_use]
+    pub fn substitute(&self, text: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find("{{") {
+            let Some(end) = rest[start..].find("}}") else {
+                break;
+            };
+            let name = rest[start + 2..start + end].trim();
+            result.push_str(&rest[..start]);
+            match self.variables.get(name) {
+                Some(value) => result.push_str(value),
+                None => result.push_str(&rest[start..start + end + 2]),
+            }
+            rest = &rest[start + end + 2..];
         }
+        result.push_str(rest);
+        result
     }
 }
 
+/// Parses a duration such as "500ms", "10s", "1.5m" or "1h".
+/// A number without unit is interpreted as seconds.
+#[must_use]
+pub fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let secs = match unit.trim() {
+        "ms" => number / 1000.0,
+        "" | "s" => number,
+        "m" => number * 60.0,
+        "h" => number * 3600.0,
+        _ => return None,
+    };
+    Duration::try_from_secs_f64(secs).ok()
+}
+
 /// The history config of the grpc client.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd)]
 pub struct HistoryConfig {
@@ -189,6 +327,113 @@ impl HistoryConfig {
     }
 }
 
+/// The config of the request collections.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq, PartialOrd)]
+pub struct CollectionsConfig {
+    /// The directory where the collections are saved
+    #[serde(default)]
+    pub directory: String,
+}
+
+impl CollectionsConfig {
+    /// Instantiate a new collections config
+    #[must_use]
+    pub fn new(directory: &str) -> Self {
+        Self {
+            directory: directory.to_string(),
+        }
+    }
+
+    /// Whether no collections directory is configured.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.directory.is_empty()
+    }
+
+    /// Returns the path to the collections. Tries to shell expand the path if it
+    /// contains environment variables such as $HOME or ~.
+    #[must_use]
+    pub fn directory_expanded(&self) -> String {
+        if self.directory.is_empty() {
+            return String::new();
+        }
+        expand_path(&self.directory)
+    }
+}
+
+/// The config of the server reflection cache.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct ReflectionConfig {
+    /// The directory where the reflected descriptors are cached
+    #[serde(default)]
+    pub directory: String,
+}
+
+impl ReflectionConfig {
+    /// Instantiate a new reflection config
+    #[must_use]
+    pub fn new(directory: &str) -> Self {
+        Self {
+            directory: directory.to_string(),
+        }
+    }
+
+    /// Whether no cache directory is configured.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.directory.is_empty()
+    }
+
+    /// Returns the path to the reflection cache. Tries to shell expand the path
+    /// if it contains environment variables such as $HOME or ~.
+    #[must_use]
+    pub fn directory_expanded(&self) -> String {
+        if self.directory.is_empty() {
+            return String::new();
+        }
+        expand_path(&self.directory)
+    }
+}
+
+/// The JSON serialization profile of the messages.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct JsonConfig {
+    /// Whether fields with default values are omitted
+    pub skip_default_fields: bool,
+    /// Whether enums are serialized as numbers instead of names
+    pub enums_as_numbers: bool,
+    /// Whether the proto field names are used instead of lowerCamelCase
+    pub proto_field_names: bool,
+    /// Whether 64-bit integers are serialized as strings
+    pub stringify_64_bit_integers: bool,
+    /// Whether unknown fields are rejected instead of ignored
+    pub deny_unknown_fields: bool,
+    /// Whether exported grpcurl commands pass `-emit-defaults`
+    pub emit_defaults: bool,
+}
+
+impl Default for JsonConfig {
+    fn default() -> Self {
+        Self {
+            skip_default_fields: false,
+            enums_as_numbers: false,
+            proto_field_names: false,
+            stringify_64_bit_integers: false,
+            deny_unknown_fields: true,
+            emit_defaults: false,
+        }
+    }
+}
+
+impl JsonConfig {
+    /// Whether the default profile is configured.
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 /// The logger config for wireman
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq, PartialOrd)]
 pub struct LoggingConfig {
@@ -238,6 +483,14 @@ impl LoggingConfig {
 pub struct TlsConfig {
     pub use_native: Option<bool>,
     pub custom_cert: Option<String>,
+    /// The PEM encoded client certificate for mutual TLS
+    pub client_cert: Option<String>,
+    /// The PEM encoded private key of the client certificate
+    pub client_key: Option<String>,
+    /// Overrides the domain name used for SNI and certificate verification
+    pub domain_name: Option<String>,
+    /// Skips the hostname verification, e.g. for local dev certs
+    pub skip_hostname_verification: Option<bool>,
 }
 
 impl TlsConfig {
@@ -245,15 +498,15 @@ impl TlsConfig {
     pub fn new(use_native: bool) -> Self {
         Self {
             use_native: Some(use_native),
-            custom_cert: None,
+            ..Self::default()
         }
     }
 
     #[must_use]
     pub fn custom(custom: &str) -> Self {
         Self {
-            use_native: None,
             custom_cert: Some(custom.to_string()),
+            ..Self::default()
         }
     }
 }
@@ -272,28 +525,59 @@ mod test {
             "api.proto",
             "internal.proto"
         ]
+        protosets = ["api.protoset"]
         [server]
         default_address = "http://localhost:50051"
+        default_timeout = "10s"
+        default_send_encoding = "gzip"
+        default_accept_encodings = ["gzip", "zstd"]
         [history]
         directory = "/Users/test"
         autosave = false
+        [collections]
+        directory = "/Users/test/collections"
+        [reflection]
+        directory = "/Users/test/reflection"
+        [json]
+        enums_as_numbers = true
+        deny_unknown_fields = false
         [logging]
         directory = "/Users"
         level = "Debug"
         [tls]
         custom_cert = "cert.pem"
+        client_cert = "client.pem"
+        client_key = "client.key"
         [ui]
         skin = "skin.toml"
         "#;
-        let cfg = Config::deserialize_toml(&data).unwrap();
+        let cfg = Config::deserialize_toml(data).unwrap();
         let expected = Config {
             includes: vec!["/Users/myworkspace".to_string()],
             files: vec!["api.proto".to_string(), "internal.proto".to_string()],
-            tls: TlsConfig::custom("cert.pem"),
-            server: ServerConfig::new("http://localhost:50051", ""),
+            protosets: vec!["api.protoset".to_string()],
+            tls: TlsConfig {
+                client_cert: Some("client.pem".to_string()),
+                client_key: Some("client.key".to_string()),
+                ..TlsConfig::custom("cert.pem")
+            },
+            server: ServerConfig {
+                default_timeout: Some("10s".to_string()),
+                default_send_encoding: Some("gzip".to_string()),
+                default_accept_encodings: vec!["gzip".to_string(), "zstd".to_string()],
+                ..ServerConfig::new("http://localhost:50051", "")
+            },
             logging: LoggingConfig::new(LogLevel::Debug, "/Users"),
             history: HistoryConfig::new("/Users/test", false, false),
+            collections: CollectionsConfig::new("/Users/test/collections"),
+            reflection: ReflectionConfig::new("/Users/test/reflection"),
+            json: JsonConfig {
+                enums_as_numbers: true,
+                deny_unknown_fields: false,
+                ..JsonConfig::default()
+            },
             ui: theme::Config::new(Some(String::from("skin.toml"))),
+            environments: BTreeMap::new(),
         };
         assert_eq!(cfg, expected);
     }
@@ -303,11 +587,16 @@ mod test {
         let cfg = Config {
             includes: vec!["/Users/myworkspace".to_string()],
             files: vec!["api.proto".to_string(), "internal.proto".to_string()],
+            protosets: vec![],
             tls: TlsConfig::default(),
             server: ServerConfig::new("http://localhost:50051", ""),
             logging: LoggingConfig::new(LogLevel::Debug, "/Users"),
             history: HistoryConfig::new("/Users/test", false, false),
+            collections: CollectionsConfig::default(),
+            reflection: ReflectionConfig::default(),
+            json: JsonConfig::default(),
             ui: theme::Config::new(Some(String::from("skin.toml"))),
+            environments: BTreeMap::new(),
         };
         let expected = r#"includes = ["/Users/myworkspace"]
 files = ["api.proto", "internal.proto"]
@@ -332,16 +621,119 @@ skin = "skin.toml"
         assert_eq!(cfg.serialize_toml().unwrap(), expected);
     }
 
+    #[test]
+    fn test_deserialize_environments() {
+        let data = r#"
+        includes = []
+        files = []
+        [environments.staging]
+        address = "https://staging.example.com"
+        auth_header = "Bearer {{token}}"
+        [environments.staging.variables]
+        token = "secret"
+        [environments.staging.tls]
+        custom_cert = "staging.pem"
+        "#;
+        let cfg = Config::deserialize_toml(data).unwrap();
+        let expected = EnvironmentConfig {
+            address: Some("https://staging.example.com".to_string()),
+            auth_header: Some("Bearer {{token}}".to_string()),
+            tls: Some(TlsConfig::custom("staging.pem")),
+            variables: BTreeMap::from([("token".to_string(), "secret".to_string())]),
+        };
+        assert_eq!(cfg.environments.get("staging"), Some(&expected));
+    }
+
+    #[test]
+    fn test_substitute() {
+        let env = EnvironmentConfig {
+            variables: BTreeMap::from([
+                ("id".to_string(), "42".to_string()),
+                ("host".to_string(), "localhost".to_string()),
+            ]),
+            ..EnvironmentConfig::default()
+        };
+        assert_eq!(
+            env.substitute(r#"{"id": {{id}}, "host": "{{ host }}"}"#),
+            r#"{"id": 42, "host": "localhost"}"#
+        );
+        assert_eq!(env.substitute("{{unknown}} {{id"), "{{unknown}} {{id");
+    }
+
+    #[test]
+    fn test_transport() {
+        let data = r#"
+        includes = []
+        files = []
+        [server.transports]
+        "https://edge.example.com" = "grpc-web"
+        "http://localhost:8080/" = "grpc-web-text"
+        "#;
+        let cfg = Config::deserialize_toml(data).unwrap();
+        let server = &cfg.server;
+        assert_eq!(
+            server.transport("https://edge.example.com/"),
+            Transport::GrpcWeb
+        );
+        assert_eq!(
+            server.transport("http://localhost:8080"),
+            Transport::GrpcWebText
+        );
+        assert_eq!(server.transport("http://localhost:50051"), Transport::Grpc);
+
+        let data = r#"
+        includes = []
+        files = []
+        [server.transports]
+        "https://edge.example.com" = "websocket"
+        "#;
+        assert!(Config::deserialize_toml(data).is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("500ms"), Some(Duration::from_millis(500)));
+        assert_eq!(parse_duration("10s"), Some(Duration::from_secs(10)));
+        assert_eq!(parse_duration("10"), Some(Duration::from_secs(10)));
+        assert_eq!(parse_duration("1.5m"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("1h"), Some(Duration::from_secs(3600)));
+        assert_eq!(parse_duration("10x"), None);
+        assert_eq!(parse_duration(""), None);
+    }
+
+    #[test]
+    fn test_default_timeout() {
+        let mut server = ServerConfig::default();
+        assert_eq!(server.default_timeout().unwrap(), None);
+
+        server.default_timeout = Some("10s".to_string());
+        assert_eq!(
+            server.default_timeout().unwrap(),
+            Some(Duration::from_secs(10))
+        );
+
+        server.default_timeout = Some("10 seconds".to_string());
+        assert!(matches!(
+            server.default_timeout(),
+            Err(Error::InvalidTimeoutError(timeout)) if timeout == "10 seconds"
+        ));
+    }
+
     #[test]
     fn test_shell_expand() {
         let cfg = Config {
             includes: vec!["$HOME/workspace".to_string()],
             files: vec![],
+            protosets: vec![],
             tls: TlsConfig::default(),
             server: ServerConfig::default(),
             logging: LoggingConfig::default(),
             history: HistoryConfig::default(),
+            collections: CollectionsConfig::default(),
+            reflection: ReflectionConfig::default(),
+            json: JsonConfig::default(),
             ui: ThemeConfig::default(),
+            environments: BTreeMap::new(),
         };
         let got = cfg.includes();
         let home = std::env::var("HOME").unwrap();
@@ -30,4 +30,8 @@ pub enum Error {
     /// Error deserializing toml-formatted config
     #[error("error deserializing config")]
     DeserializeConfigError(#[source] toml::de::Error),
+
+    /// Error parsing the default timeout of the server config
+    #[error("invalid default timeout `{0}`, expected e.g. \"10s\"")]
+    InvalidTimeoutError(String),
 }
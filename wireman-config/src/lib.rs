@@ -7,17 +7,38 @@
 //!
 //! - `includes`: A list of include directories for `gRPC`.
 //! - `files`: A list of .proto files to include.
+//! - `protosets`: A list of precompiled `FileDescriptorSet` files.
 //! - `server`
 //!   - `default_address`: The default address of the `gRPC` server.
 //!   - `default_auth_header`: The default authentication header.
+//!   - `default_timeout`: The default deadline of a request, e.g. "10s".
+//!   - `default_send_encoding`, `default_accept_encodings`: The default compression.
+//!   - `transports`: The transport per address: `grpc`, `grpc-web` or `grpc-web-text`.
+//! - `tls`
+//!   - `use_native`: Whether to use the native root certificates.
+//!   - `custom_cert`: The path to a custom root certificate.
+//!   - `client_cert`, `client_key`: The client certificate and key for mTLS.
+//!   - `domain_name`: Overrides the domain name used for SNI.
+//!   - `skip_hostname_verification`: Skips the hostname verification.
+//! - `environments.<name>`
+//!   - `address`, `auth_header`, `tls`: Override the server and TLS config.
+//!   - `variables`: Variables that are substituted as `{{var}}`.
 //! - `history`
 //!   - `directory`: The folder path where the history should be kept
+//! - `collections`
+//!   - `directory`: The folder path where the named requests are kept
+//! - `reflection`
+//!   - `directory`: The folder path where reflected descriptors are cached
+//! - `json`
+//!   - `skip_default_fields`, `enums_as_numbers`, `proto_field_names`,
+//!     `stringify_64_bit_integers`, `deny_unknown_fields`: The JSON profile.
+//!   - `emit_defaults`: Whether exported grpcurl commands pass `-emit-defaults`
 pub mod cli;
 pub mod config;
 pub mod error;
 mod install;
 mod setup;
-pub use config::Config;
+pub use config::{parse_duration, Config};
 pub use setup::init_from_env;
 
 /// This env is used to read the path for the `WireMan` config.
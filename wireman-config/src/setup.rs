@@ -50,7 +50,7 @@ pub fn setup(dry_run: bool, args: &Args) -> Result<(Config, Option<String>)> {
     let mut config = match load_config(&config_file, dry_run) {
         Ok(config) => config,
         Err(err) => {
-            if args.local_protos {
+            if args.local_protos || !args.protosets.is_empty() {
                 Config::default()
             } else {
                 return Err(err);
@@ -60,9 +60,14 @@ pub fn setup(dry_run: bool, args: &Args) -> Result<(Config, Option<String>)> {
     if args.local_protos {
         update_config_with_local_protos(&mut config)?;
     };
+    config.protosets.extend(args.protosets.iter().cloned());
 
     init_history(&mut config, config_dir, dry_run)?;
 
+    init_collections(&mut config, config_dir, dry_run);
+
+    init_reflection(&mut config, config_dir, dry_run);
+
     init_logger(&mut config, config_dir, dry_run)?;
 
     if !dry_run {
@@ -113,7 +118,11 @@ fn get_parent_dir(config_file: &str) -> String {
 }
 
 fn load_config(config_file: &str, dry_run: bool) -> Result<Config> {
-    match Config::load(config_file) {
+    let config = Config::load(config_file).and_then(|config| {
+        config.server.default_timeout()?;
+        Ok(config)
+    });
+    match config {
         Ok(config) => Ok(config),
         Err(err) => {
             if dry_run {
@@ -172,6 +181,32 @@ fn init_history(config: &mut Config, config_dir: &Path, dry_run: bool) -> Result
     Ok(())
 }
 
+/// Sets the default collections directory. The directory itself is
+/// created when the first request is saved.
+fn init_collections(config: &mut Config, config_dir: &Path, dry_run: bool) {
+    let mut collections_dir = config.collections.directory_expanded();
+    if collections_dir.is_empty() {
+        collections_dir = config_dir.join("collections").to_string_lossy().to_string();
+    }
+    if dry_run {
+        println!("{:<20} {}", "Collections:", collections_dir);
+    }
+    config.collections.directory = collections_dir;
+}
+
+/// Sets the default reflection cache directory. The directory itself is
+/// created when the first reflected descriptor is cached.
+fn init_reflection(config: &mut Config, config_dir: &Path, dry_run: bool) {
+    let mut reflection_dir = config.reflection.directory_expanded();
+    if reflection_dir.is_empty() {
+        reflection_dir = config_dir.join("reflection").to_string_lossy().to_string();
+    }
+    if dry_run {
+        println!("{:<20} {}", "Reflection cache:", reflection_dir);
+    }
+    config.reflection.directory = reflection_dir;
+}
+
 fn init_logger(config: &mut Config, config_dir: &Path, dry_run: bool) -> Result<()> {
     let logger_file = match logger_dir_checked(config_dir, &config.logging) {
         Err(err) => {
@@ -18,7 +18,7 @@ async fn main() -> Result<()> {
     println!("Service: {:}", service.full_name());
     println!("Method:  {:}", method.full_name());
 
-    let mut req = desc.get_request(&method);
+    let mut req = desc.get_request(method);
     req.set_address("http://localhost:50051");
 
     let resp = do_request(&req).await?;
@@ -0,0 +1,161 @@
+#![allow(clippy::module_name_repetitions)]
+use crate::error::{Error, Result};
+use http::Uri;
+use std::{fmt, path::PathBuf, str::FromStr};
+
+/// The uri of requests over a unix domain socket.
+const UNIX_URI: &str = "http://localhost";
+
+/// A parsed server address.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Address {
+    /// A tcp address with an `http` or `https` scheme.
+    Tcp(Uri),
+    /// The path of a unix domain socket.
+    Unix(PathBuf),
+}
+
+impl Address {
+    /// Parses and normalizes an address. Supported forms are:
+    ///
+    /// - `http://host:port` and `https://host:port`
+    /// - `host:port`, which is treated as `http://host:port`
+    /// - `dns:///host:port`, which uses `https` for port 443
+    /// - `unix:///path/to/app.sock` and `unix:path/to/app.sock`
+    ///
+    /// # Errors
+    ///
+    /// Errors if the address cannot be parsed.
+    pub fn parse(address: &str) -> Result<Self> {
+        let address = address.trim();
+        if address.is_empty() {
+            return Err(invalid("the address is empty"));
+        }
+
+        if let Some(path) = address.strip_prefix("unix:") {
+            let path = path.strip_prefix("//").unwrap_or(path);
+            if path.is_empty() {
+                return Err(invalid("the socket path is missing"));
+            }
+            return Ok(Self::Unix(PathBuf::from(path)));
+        }
+
+        if let Some(target) = address.strip_prefix("dns:") {
+            // The authority of the dns server is ignored.
+            let target = match target.strip_prefix("//") {
+                Some(target) => target.split_once('/').map_or("", |(_, target)| target),
+                None => target,
+            };
+            let uri = parse_tcp(&format!("http://{target}"))?;
+            let scheme = if uri.port_u16() == Some(443) {
+                "https"
+            } else {
+                "http"
+            };
+            return parse_tcp(&format!("{scheme}://{target}")).map(Self::Tcp);
+        }
+
+        match address.split_once("://") {
+            Some(("http" | "https", _)) => parse_tcp(address).map(Self::Tcp),
+            Some((scheme, _)) => Err(invalid(&format!(
+                "unsupported scheme \"{scheme}\", expected http, https, dns or unix"
+            ))),
+            None => parse_tcp(&format!("http://{address}")).map(Self::Tcp),
+        }
+    }
+
+    /// Returns the uri of the requests. Requests over a unix domain
+    /// socket are sent to `http://localhost`.
+    #[must_use]
+    pub fn uri(&self) -> Uri {
+        match self {
+            Self::Tcp(uri) => uri.clone(),
+            Self::Unix(_) => Uri::from_static(UNIX_URI),
+        }
+    }
+}
+
+impl FromStr for Address {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl From<Uri> for Address {
+    fn from(uri: Uri) -> Self {
+        Self::Tcp(uri)
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(uri) => write!(f, "{uri}"),
+            Self::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+/// Parses a tcp address that has an `http` or `https` scheme.
+fn parse_tcp(address: &str) -> Result<Uri> {
+    if address.contains(char::is_whitespace) {
+        return Err(invalid("the address contains whitespace"));
+    }
+    let uri = Uri::try_from(address).map_err(|err| invalid(&err.to_string()))?;
+    if uri.host().unwrap_or_default().is_empty() {
+        return Err(invalid("the host is missing"));
+    }
+    Ok(uri)
+}
+
+fn invalid(reason: &str) -> Error {
+    Error::InvalidAddress(reason.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn tcp(uri: &str) -> Address {
+        Address::Tcp(Uri::from_str(uri).unwrap())
+    }
+
+    #[test]
+    fn test_parse_address() {
+        for (given, expected) in [
+            ("http://localhost:50051", tcp("http://localhost:50051")),
+            (" https://example.com ", tcp("https://example.com")),
+            ("localhost:50051", tcp("http://localhost:50051")),
+            ("[::1]:50051", tcp("http://[::1]:50051")),
+            ("dns:///example.com:443", tcp("https://example.com:443")),
+            ("dns://8.8.8.8/example.com:80", tcp("http://example.com:80")),
+            ("dns:localhost:50051", tcp("http://localhost:50051")),
+            (
+                "unix:///run/app.sock",
+                Address::Unix(PathBuf::from("/run/app.sock")),
+            ),
+            ("unix:app.sock", Address::Unix(PathBuf::from("app.sock"))),
+        ] {
+            assert_eq!(Address::parse(given).unwrap(), expected, "{given}");
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_address() {
+        for given in [
+            "",
+            "unix://",
+            "ftp://localhost:21",
+            "http://",
+            "local host:50051",
+        ] {
+            let result = Address::parse(given);
+            assert!(
+                matches!(result, Err(Error::InvalidAddress(_))),
+                "{given}: {result:?}"
+            );
+        }
+    }
+}
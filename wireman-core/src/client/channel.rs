@@ -0,0 +1,570 @@
+#![allow(clippy::module_name_repetitions)]
+use super::address::Address;
+use super::tls::TlsConfig;
+use super::web::{BoxError, GrpcWebService};
+use crate::error::Error;
+use crate::Result;
+use hyper_util::rt::TokioIo;
+use std::{
+    fmt,
+    future::Future,
+    io,
+    path::PathBuf,
+    pin::Pin,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+#[cfg(unix)]
+use tokio::net::UnixStream;
+use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
+    net::TcpStream,
+};
+use tonic::{
+    body::Body,
+    transport::{Channel, Uri},
+};
+use tower_service::Service;
+
+/// The connection state of a [`GrpcChannel`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// No connection is open. The channel connects on the next call.
+    #[default]
+    Idle,
+    /// The channel is establishing a connection.
+    Connecting,
+    /// The channel is connected.
+    Ready,
+    /// The last connection attempt or call failed. The channel
+    /// reconnects on the next call.
+    TransientFailure,
+}
+
+impl fmt::Display for ConnectionState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let display_str = match self {
+            ConnectionState::Idle => "idle",
+            ConnectionState::Connecting => "connecting",
+            ConnectionState::Ready => "ready",
+            ConnectionState::TransientFailure => "transient failure",
+        };
+        write!(f, "{display_str}")
+    }
+}
+
+/// The wire protocol of a [`GrpcChannel`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Transport {
+    /// Native `gRPC` over HTTP/2.
+    #[default]
+    Grpc,
+    /// `gRPC-Web` with binary messages over HTTP/1.1.
+    GrpcWeb,
+    /// `gRPC-Web` with base64 encoded messages over HTTP/1.1.
+    GrpcWebText,
+}
+
+impl fmt::Display for Transport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let display_str = match self {
+            Transport::Grpc => "grpc",
+            Transport::GrpcWeb => "grpc-web",
+            Transport::GrpcWebText => "grpc-web-text",
+        };
+        write!(f, "{display_str}")
+    }
+}
+
+impl FromStr for Transport {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "grpc" => Ok(Transport::Grpc),
+            "grpc-web" => Ok(Transport::GrpcWeb),
+            "grpc-web-text" => Ok(Transport::GrpcWebText),
+            _ => Err(Error::Internal(format!("unknown transport {s}"))),
+        }
+    }
+}
+
+/// The connection state shared between a channel and its connections.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SharedState(Arc<Mutex<ConnectionState>>);
+
+impl SharedState {
+    pub(crate) fn get(&self) -> ConnectionState {
+        self.0.lock().map(|state| *state).unwrap_or_default()
+    }
+
+    pub(crate) fn set(&self, state: ConnectionState) {
+        if let Ok(mut current) = self.0.lock() {
+            *current = state;
+        }
+    }
+
+    /// Sets the state if it currently equals `from`.
+    fn transition(&self, from: ConnectionState, to: ConnectionState) {
+        if let Ok(mut current) = self.0.lock() {
+            if *current == from {
+                *current = to;
+            }
+        }
+    }
+}
+
+/// A lazily connecting channel that keeps track of its connection
+/// state. Cloning the channel is cheap and shares the connection.
+#[derive(Clone, Debug)]
+pub struct GrpcChannel {
+    service: ChannelService,
+    transport: Transport,
+    state: SharedState,
+}
+
+impl GrpcChannel {
+    /// Returns a new native `gRPC` channel. The connection is established
+    /// on the first call. If no tls is given, the connection is not
+    /// encrypted.
+    ///
+    /// # Errors
+    ///
+    /// Errors if tls config cannot be build.
+    pub fn new<T: Into<Address>>(address: T, tls_config: Option<TlsConfig>) -> Result<Self> {
+        Self::with_transport(address, tls_config, Transport::Grpc)
+    }
+
+    /// Returns a new channel that speaks the given wire protocol.
+    ///
+    /// # Errors
+    ///
+    /// Errors if tls config cannot be build.
+    pub fn with_transport<T: Into<Address>>(
+        address: T,
+        tls_config: Option<TlsConfig>,
+        transport: Transport,
+    ) -> Result<Self> {
+        let address = address.into();
+        let state = SharedState::default();
+        let service = match transport {
+            Transport::Grpc => {
+                ChannelService::Grpc(build_channel(address, tls_config, state.clone())?)
+            }
+            Transport::GrpcWeb | Transport::GrpcWebText => {
+                let text = transport == Transport::GrpcWebText;
+                let service = GrpcWebService::new(address, tls_config, text, state.clone())?;
+                ChannelService::Web(service)
+            }
+        };
+        Ok(Self {
+            service,
+            transport,
+            state,
+        })
+    }
+
+    /// Returns the wire protocol of the channel.
+    #[must_use]
+    pub fn transport(&self) -> Transport {
+        self.transport
+    }
+
+    /// Returns the current connection state.
+    #[must_use]
+    pub fn state(&self) -> ConnectionState {
+        self.state.get()
+    }
+
+    pub(crate) fn shared_state(&self) -> SharedState {
+        self.state.clone()
+    }
+
+    pub(crate) fn service(&self) -> ChannelService {
+        self.service.clone()
+    }
+}
+
+/// The http service of a [`GrpcChannel`].
+#[derive(Clone, Debug)]
+pub(crate) enum ChannelService {
+    Grpc(Channel),
+    Web(GrpcWebService),
+}
+
+impl Service<http::Request<Body>> for ChannelService {
+    type Response = http::Response<Body>;
+    type Error = BoxError;
+    type Future =
+        Pin<Box<dyn Future<Output = std::result::Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<std::result::Result<(), Self::Error>> {
+        match self {
+            ChannelService::Grpc(channel) => channel.poll_ready(cx).map_err(Into::into),
+            ChannelService::Web(service) => service.poll_ready(cx),
+        }
+    }
+
+    fn call(&mut self, request: http::Request<Body>) -> Self::Future {
+        match self {
+            ChannelService::Grpc(channel) => {
+                let response = channel.call(request);
+                Box::pin(async move { response.await.map_err(Into::into) })
+            }
+            ChannelService::Web(service) => service.call(request),
+        }
+    }
+}
+
+/// Builds a lazily connecting channel whose connection state is
+/// written to `state`. If no tls is given or the address is not an
+/// `https` address, the connection is not encrypted.
+///
+/// # Errors
+///
+/// Errors if tls config cannot be build.
+pub(crate) fn build_channel(
+    address: Address,
+    tls_config: Option<TlsConfig>,
+    state: SharedState,
+) -> Result<Channel> {
+    let uri = match address {
+        Address::Tcp(uri) => uri,
+        Address::Unix(path) => return build_unix_channel(path, state),
+    };
+    let builder = Channel::builder(uri);
+
+    let is_https = builder.uri().scheme() == Some(&http::uri::Scheme::HTTPS);
+    let channel = match tls_config {
+        Some(tls_config) if tls_config.skip_hostname_verification() && is_https => {
+            // The TLS handshake is done by the connector, so the channel
+            // itself must not attempt to add another TLS layer.
+            let builder = Channel::builder(into_http_uri(builder.uri())?);
+            let connector = StateConnector::new(tls_config.skip_hostname_connector()?, state);
+            builder.connect_with_connector_lazy(connector)
+        }
+        // Tonic performs the TLS handshake on top of the tcp stream
+        // for https addresses.
+        Some(tls_config) => builder
+            .tls_config(tls_config.client_tls_config())?
+            .connect_with_connector_lazy(StateConnector::new(TcpConnector, state)),
+        None => builder.connect_with_connector_lazy(StateConnector::new(TcpConnector, state)),
+    };
+
+    Ok(channel)
+}
+
+/// Returns a channel that connects to a unix domain socket. Tls is
+/// not used on local sockets.
+#[cfg(unix)]
+#[allow(clippy::unnecessary_wraps)]
+fn build_unix_channel(path: PathBuf, state: SharedState) -> Result<Channel> {
+    let builder = Channel::builder(Address::Unix(path.clone()).uri());
+    Ok(builder.connect_with_connector_lazy(StateConnector::new(UnixConnector(path), state)))
+}
+
+#[cfg(not(unix))]
+fn build_unix_channel(_: PathBuf, _: SharedState) -> Result<Channel> {
+    Err(Error::InvalidAddress(String::from(
+        "unix sockets are not supported on this platform",
+    )))
+}
+
+/// Replaces the `https` scheme of the uri with `http`. The port is made
+/// explicit, so that the https port is kept if the uri has none.
+fn into_http_uri(uri: &Uri) -> Result<Uri> {
+    let error = || Error::Internal(String::from("Failed to parse address"));
+    let mut parts = uri.clone().into_parts();
+    if parts.scheme == Some(http::uri::Scheme::HTTPS) {
+        parts.scheme = Some(http::uri::Scheme::HTTP);
+        let host = uri.host().ok_or_else(error)?;
+        let port = uri.port_u16().unwrap_or(443);
+        let authority = format!("{host}:{port}").parse().map_err(|_| error())?;
+        parts.authority = Some(authority);
+    }
+    Uri::from_parts(parts).map_err(|_| error())
+}
+
+/// Opens a plain tcp connection to the host of the uri.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TcpConnector;
+
+impl Service<Uri> for TcpConnector {
+    type Response = TokioIo<TcpStream>;
+    type Error = io::Error;
+    type Future = Pin<Box<dyn Future<Output = io::Result<Self::Response>> + Send>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let host = uri
+            .host()
+            .unwrap_or_default()
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_string();
+        let default_port = if uri.scheme() == Some(&http::uri::Scheme::HTTPS) {
+            443
+        } else {
+            80
+        };
+        let port = uri.port_u16().unwrap_or(default_port);
+
+        Box::pin(async move {
+            let stream = TcpStream::connect((host.as_str(), port)).await?;
+            stream.set_nodelay(true)?;
+            Ok(TokioIo::new(stream))
+        })
+    }
+}
+
+/// Opens a connection to a unix domain socket. The uri is ignored.
+#[cfg(unix)]
+#[derive(Clone, Debug)]
+pub(crate) struct UnixConnector(pub(crate) PathBuf);
+
+#[cfg(unix)]
+impl Service<Uri> for UnixConnector {
+    type Response = TokioIo<UnixStream>;
+    type Error = io::Error;
+    type Future = Pin<Box<dyn Future<Output = io::Result<Self::Response>> + Send>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _: Uri) -> Self::Future {
+        let path = self.0.clone();
+        Box::pin(async move { Ok(TokioIo::new(UnixStream::connect(path).await?)) })
+    }
+}
+
+/// Wraps a connector and writes the state of its connections.
+#[derive(Clone)]
+pub(crate) struct StateConnector<C> {
+    inner: C,
+    state: SharedState,
+}
+
+impl<C> StateConnector<C> {
+    pub(crate) fn new(inner: C, state: SharedState) -> Self {
+        Self { inner, state }
+    }
+}
+
+impl<C, S> Service<Uri> for StateConnector<C>
+where
+    C: Service<Uri, Response = TokioIo<S>, Error = io::Error>,
+    C::Future: Send + 'static,
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    type Response = TokioIo<StateStream<S>>;
+    type Error = io::Error;
+    type Future = Pin<Box<dyn Future<Output = io::Result<Self::Response>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let state = self.state.clone();
+        state.set(ConnectionState::Connecting);
+        let connect = self.inner.call(uri);
+
+        Box::pin(async move {
+            match connect.await {
+                Ok(io) => {
+                    state.set(ConnectionState::Ready);
+                    Ok(TokioIo::new(StateStream {
+                        inner: io.into_inner(),
+                        state,
+                    }))
+                }
+                Err(err) => {
+                    state.set(ConnectionState::TransientFailure);
+                    Err(err)
+                }
+            }
+        })
+    }
+}
+
+/// A connection that resets the state to idle once it is closed.
+pub(crate) struct StateStream<S> {
+    inner: S,
+    state: SharedState,
+}
+
+impl<S> Drop for StateStream<S> {
+    fn drop(&mut self) {
+        self.state
+            .transition(ConnectionState::Ready, ConnectionState::Idle);
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for StateStream<S> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for StateStream<S> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::temp_path;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_state_connector() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            // given
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let state = SharedState::default();
+            let mut connector = StateConnector::new(TcpConnector, state.clone());
+            assert_eq!(state.get(), ConnectionState::Idle);
+
+            // when
+            let uri: Uri = format!("http://{addr}").parse().unwrap();
+            let io = connector.call(uri).await.unwrap();
+
+            // then
+            assert_eq!(state.get(), ConnectionState::Ready);
+
+            // when
+            drop(io);
+
+            // then
+            assert_eq!(state.get(), ConnectionState::Idle);
+
+            // when
+            drop(listener);
+            let uri: Uri = format!("http://{addr}").parse().unwrap();
+            let result = connector.call(uri).await;
+
+            // then
+            assert!(result.is_err());
+            assert_eq!(state.get(), ConnectionState::TransientFailure);
+        });
+    }
+
+    #[test]
+    fn test_into_http_uri() {
+        // given
+        let default_port: Uri = "https://localhost".parse().unwrap();
+        let custom_port: Uri = "https://localhost:8443/".parse().unwrap();
+
+        // when
+        let default_port = into_http_uri(&default_port).unwrap();
+        let custom_port = into_http_uri(&custom_port).unwrap();
+
+        // then
+        assert_eq!(default_port.to_string(), "http://localhost:443/");
+        assert_eq!(custom_port.to_string(), "http://localhost:8443/");
+    }
+
+    #[test]
+    fn test_skip_hostname_verification_on_http() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            // given
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let address = Address::parse(&format!("http://{addr}")).unwrap();
+            let tls = TlsConfig::native().with_skip_hostname_verification(true);
+            let mut channel = build_channel(address, Some(tls), SharedState::default()).unwrap();
+
+            // when
+            tokio::spawn(async move {
+                std::future::poll_fn(|cx| channel.poll_ready(cx)).await.ok();
+                let request = http::Request::new(Body::empty());
+                let _ = channel.call(request).await;
+            });
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut preface = [0; 3];
+            let mut read = 0;
+            while read < preface.len() {
+                stream.readable().await.unwrap();
+                match stream.try_read(&mut preface[read..]) {
+                    Ok(0) => break,
+                    Ok(n) => read += n,
+                    Err(err) if err.kind() == io::ErrorKind::WouldBlock => {}
+                    Err(err) => panic!("{err}"),
+                }
+            }
+
+            // then
+            // The connection starts with the http2 preface, not with a
+            // tls handshake.
+            assert_eq!(&preface, b"PRI");
+        });
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unix_connector() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            // given
+            let (_guard, path) = temp_path("wireman.sock");
+            let listener = tokio::net::UnixListener::bind(&path).unwrap();
+            let state = SharedState::default();
+            let mut connector = StateConnector::new(UnixConnector(path.clone()), state.clone());
+
+            // when
+            let io = connector.call(Address::Unix(path.clone()).uri()).await;
+
+            // then
+            assert!(io.is_ok());
+            assert!(listener.accept().await.is_ok());
+            assert_eq!(state.get(), ConnectionState::Ready);
+        });
+    }
+}
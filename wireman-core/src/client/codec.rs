@@ -1,6 +1,7 @@
 //! This file is taken from `https://github.com/andrewhickman/grpc-client`
+use super::metrics::MetricsRecorder;
 use crate::descriptor::{DynamicMessage, RequestMessage, ResponseMessage};
-use prost_reflect::prost::Message;
+use prost_reflect::prost::{bytes::Buf, Message};
 use prost_reflect::MethodDescriptor;
 use tonic::{
     codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder},
@@ -10,13 +11,26 @@ use tonic::{
 /// `DynamicCodec` is a customizable `gRPC` codec that can handle requests and responses with
 /// dynamic message descriptors.
 #[derive(Debug, Clone)]
-pub struct DynamicCodec(MethodDescriptor);
+pub struct DynamicCodec {
+    desc: MethodDescriptor,
+    metrics: Option<MetricsRecorder>,
+}
 
 impl DynamicCodec {
     /// Create a new `DynamicCodec` with the provided [`MethodDescriptor`].
     #[must_use]
     pub fn new(desc: MethodDescriptor) -> Self {
-        DynamicCodec(desc)
+        DynamicCodec {
+            desc,
+            metrics: None,
+        }
+    }
+
+    /// Records the sizes of the encoded and decoded messages.
+    #[must_use]
+    pub fn with_metrics(mut self, metrics: MetricsRecorder) -> Self {
+        self.metrics = Some(metrics);
+        self
     }
 }
 
@@ -43,14 +57,18 @@ impl Encoder for DynamicCodec {
     // Encodes a request message and writes it to the destination buffer.
     fn encode(&mut self, request: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
         // Ensure the request message descriptor matches the codec's input descriptor.
-        debug_assert_eq!(request.message_descriptor(), self.0.input());
+        debug_assert_eq!(request.message_descriptor(), self.desc.input());
 
         // Encode the request message into the destination buffer.
-        request
-            .message()
+        let message = request.message();
+        message
             .encode(dst)
             .map_err(|err| Status::internal(err.to_string()))?;
 
+        if let Some(metrics) = &self.metrics {
+            metrics.record_request(message.encoded_len());
+        }
+
         Ok(())
     }
 }
@@ -62,7 +80,8 @@ impl Decoder for DynamicCodec {
     /// Decodes a response message from the source buffer.
     fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
         // Create a dynamic message with the codec's output descriptor.
-        let mut message = DynamicMessage::new(self.0.output());
+        let mut message = DynamicMessage::new(self.desc.output());
+        let len = src.remaining();
 
         // Merge the source buffer into the dynamic message.
         message
@@ -70,9 +89,13 @@ impl Decoder for DynamicCodec {
             .map_err(|err| Status::internal(err.to_string()))?;
 
         // Create a response message and set its message to the decoded dynamic message.
-        let mut response = ResponseMessage::new(self.0.output(), self.0.clone());
+        let mut response = ResponseMessage::new(self.desc.output(), self.desc.clone());
         response.set_message(message);
 
+        if let Some(metrics) = &self.metrics {
+            metrics.record_response(len);
+        }
+
         Ok(Some(response))
     }
 }
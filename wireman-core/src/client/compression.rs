@@ -0,0 +1,217 @@
+#![allow(clippy::module_name_repetitions)]
+use crate::error::{Error, Result};
+use std::{fmt, str::FromStr};
+use tonic::codec::CompressionEncoding;
+
+/// A message compression algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    Gzip,
+    Deflate,
+    Zstd,
+}
+
+impl Encoding {
+    /// All supported encodings.
+    pub const ALL: [Encoding; 3] = [Encoding::Gzip, Encoding::Deflate, Encoding::Zstd];
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let display_str = match self {
+            Encoding::Gzip => "gzip",
+            Encoding::Deflate => "deflate",
+            Encoding::Zstd => "zstd",
+        };
+        write!(f, "{display_str}")
+    }
+}
+
+impl FromStr for Encoding {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim() {
+            "gzip" => Ok(Encoding::Gzip),
+            "deflate" => Ok(Encoding::Deflate),
+            "zstd" => Ok(Encoding::Zstd),
+            _ => Err(Error::InvalidCompression(format!(
+                "unknown encoding \"{s}\", expected gzip, deflate or zstd"
+            ))),
+        }
+    }
+}
+
+impl From<Encoding> for CompressionEncoding {
+    fn from(encoding: Encoding) -> Self {
+        match encoding {
+            Encoding::Gzip => CompressionEncoding::Gzip,
+            Encoding::Deflate => CompressionEncoding::Deflate,
+            Encoding::Zstd => CompressionEncoding::Zstd,
+        }
+    }
+}
+
+/// The compression settings of a request.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Compression {
+    /// The encoding of the sent messages. Messages are sent
+    /// uncompressed if not set.
+    pub send: Option<Encoding>,
+    /// The encodings that are advertised in `grpc-accept-encoding`.
+    pub accept: Vec<Encoding>,
+}
+
+impl Compression {
+    /// Parses the send encoding and a comma separated list of accepted
+    /// encodings. Empty inputs and `identity` mean no compression.
+    ///
+    /// # Errors
+    ///
+    /// Errors if an encoding is unknown.
+    pub fn parse(send: &str, accept: &str) -> Result<Self> {
+        let send = match send.trim() {
+            "" | "identity" => None,
+            send => Some(send.parse()?),
+        };
+        let accept = accept
+            .split(',')
+            .map(str::trim)
+            .filter(|encoding| !encoding.is_empty() && *encoding != "identity")
+            .map(str::parse)
+            .collect::<Result<_>>()?;
+        Ok(Self { send, accept })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::channel::GrpcChannel;
+    use crate::client::GrpcClient;
+    use crate::descriptor::RequestMessage;
+    use crate::ProtoDescriptor;
+    use bytes::Bytes;
+    use http::{Request, Response, Uri};
+    use http_body_util::{BodyExt, StreamBody};
+    use hyper::{body::Frame, server::conn::http2 as server, service::service_fn, HeaderMap};
+    use hyper_util::rt::{TokioExecutor, TokioIo};
+    use std::convert::Infallible;
+    use std::sync::{Arc, Mutex};
+    use tokio::net::TcpListener;
+
+    /// The `grpc-encoding` and `grpc-accept-encoding` headers and the
+    /// compression flag of the first message of a request.
+    type Received = (Option<String>, Option<String>, u8);
+
+    fn load_test_message() -> RequestMessage {
+        let desc = ProtoDescriptor::new(vec!["."], vec!["test_files/test.proto"]).unwrap();
+        let method = desc
+            .get_method_by_name("proto.TestService", "Simple")
+            .unwrap();
+        let mut request = RequestMessage::new(method.input(), method);
+        request.message_mut().from_json("{\"number\": 1}").unwrap();
+        request.set_compression(Compression::parse("gzip", "gzip").unwrap());
+        request
+    }
+
+    /// Serves a single connection like a `gRPC` server that answers each
+    /// call with one empty message and announces a gzip encoding.
+    async fn serve_grpc(listener: TcpListener, received: Arc<Mutex<Vec<Received>>>) {
+        let (stream, _) = listener.accept().await.unwrap();
+        let service = service_fn(move |request: Request<hyper::body::Incoming>| {
+            let received = Arc::clone(&received);
+            async move {
+                let header = |name: &str| {
+                    request
+                        .headers()
+                        .get(name)
+                        .map(|value| value.to_str().unwrap().to_string())
+                };
+                let (encoding, accept) = (header("grpc-encoding"), header("grpc-accept-encoding"));
+                let body = request.into_body().collect().await.unwrap().to_bytes();
+                received.lock().unwrap().push((encoding, accept, body[0]));
+
+                let mut trailers = HeaderMap::new();
+                trailers.insert("grpc-status", "0".parse().unwrap());
+                let frames: Vec<std::result::Result<_, Infallible>> = vec![
+                    Ok(Frame::data(Bytes::from_static(&[0, 0, 0, 0, 0]))),
+                    Ok(Frame::trailers(trailers)),
+                ];
+                let response = Response::builder()
+                    .header("content-type", "application/grpc")
+                    .header("grpc-encoding", "gzip")
+                    .body(StreamBody::new(tokio_stream::iter(frames)))
+                    .unwrap();
+                Ok::<_, Infallible>(response)
+            }
+        });
+        let _ = server::Builder::new(TokioExecutor::new())
+            .serve_connection(TokioIo::new(stream), service)
+            .await;
+    }
+
+    #[test]
+    fn test_compressed_calls() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            // given
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let received = Arc::new(Mutex::new(Vec::new()));
+            tokio::spawn(serve_grpc(listener, Arc::clone(&received)));
+            let uri: Uri = format!("http://{addr}").parse().unwrap();
+            let mut client = GrpcClient::from_channel(&GrpcChannel::new(uri, None).unwrap());
+            let request = load_test_message();
+            let messages = || tokio_stream::iter(vec![request.clone()]);
+
+            // when
+            let unary = client.unary(&request).await.unwrap();
+            let mut server_streaming = client.server_streaming(&request).await.unwrap();
+            while server_streaming.message().await.unwrap().is_some() {}
+            let client_streaming = client.client_streaming(&request, messages()).await.unwrap();
+            let mut bidi_streaming = client
+                .bidirectional_streaming(&request, messages())
+                .await
+                .unwrap();
+            while bidi_streaming.message().await.unwrap().is_some() {}
+
+            // then
+            let expected = (
+                Some("gzip".to_string()),
+                Some("gzip,identity".to_string()),
+                1,
+            );
+            assert_eq!(*received.lock().unwrap(), vec![expected; 4]);
+            let encodings = vec![
+                unary.details.unwrap().encoding,
+                server_streaming.details().await.unwrap().encoding,
+                client_streaming.details.unwrap().encoding,
+                bidi_streaming.details().await.unwrap().encoding,
+            ];
+            assert_eq!(encodings, vec![Some("gzip".to_string()); 4]);
+        });
+    }
+
+    #[test]
+    fn test_parse_compression() {
+        // when
+        let compression = Compression::parse("gzip", "zstd, gzip,").unwrap();
+
+        // then
+        let expected = Compression {
+            send: Some(Encoding::Gzip),
+            accept: vec![Encoding::Zstd, Encoding::Gzip],
+        };
+        assert_eq!(compression, expected);
+        assert_eq!(
+            Compression::parse(" identity ", "").unwrap(),
+            Compression::default()
+        );
+        assert!(Compression::parse("br", "").is_err());
+        assert!(Compression::parse("", "gzip,lz4").is_err());
+    }
+}
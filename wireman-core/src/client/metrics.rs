@@ -0,0 +1,124 @@
+#![allow(clippy::module_name_repetitions)]
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// The timing and payload sizes of a call.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CallMetrics {
+    /// The time between sending the request and receiving the response
+    /// headers.
+    pub time_to_first_byte: Option<Duration>,
+    /// The time between sending the request and the last event of the
+    /// call. Once the call is complete, this is the total duration.
+    pub duration: Duration,
+    /// The number of sent messages.
+    pub request_count: usize,
+    /// The encoded size of the sent messages in bytes.
+    pub request_bytes: usize,
+    /// The encoded size of the received messages in bytes.
+    pub response_bytes: usize,
+    /// The arrival time of each received message, relative to the
+    /// start of the call.
+    pub arrivals: Vec<Duration>,
+}
+
+impl CallMetrics {
+    /// The number of received messages.
+    #[must_use]
+    pub fn response_count(&self) -> usize {
+        self.arrivals.len()
+    }
+}
+
+/// Records the [`CallMetrics`] of a call. Cloning the recorder shares
+/// the metrics, so that the codec can record the sizes of the messages
+/// as they are encoded and decoded.
+#[derive(Debug, Clone)]
+pub struct MetricsRecorder {
+    started: Instant,
+    metrics: Arc<Mutex<CallMetrics>>,
+}
+
+impl MetricsRecorder {
+    /// Starts recording a call.
+    #[must_use]
+    pub fn start() -> Self {
+        Self {
+            started: Instant::now(),
+            metrics: Arc::default(),
+        }
+    }
+
+    /// Returns the metrics recorded so far.
+    #[must_use]
+    pub fn snapshot(&self) -> CallMetrics {
+        let mut metrics = self
+            .metrics
+            .lock()
+            .map(|metrics| metrics.clone())
+            .unwrap_or_default();
+        metrics.duration = self.started.elapsed();
+        metrics
+    }
+
+    /// Records the arrival of the response headers.
+    pub(crate) fn record_first_byte(&self) {
+        let elapsed = self.started.elapsed();
+        self.update(|metrics| {
+            metrics.time_to_first_byte.get_or_insert(elapsed);
+        });
+    }
+
+    /// Records a sent message.
+    pub(crate) fn record_request(&self, bytes: usize) {
+        self.update(|metrics| {
+            metrics.request_count += 1;
+            metrics.request_bytes += bytes;
+        });
+    }
+
+    /// Records a received message.
+    pub(crate) fn record_response(&self, bytes: usize) {
+        let elapsed = self.started.elapsed();
+        self.update(|metrics| {
+            metrics.response_bytes += bytes;
+            metrics.arrivals.push(elapsed);
+        });
+    }
+
+    fn update<F: FnOnce(&mut CallMetrics)>(&self, f: F) {
+        if let Ok(mut metrics) = self.metrics.lock() {
+            f(&mut metrics);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_metrics_recorder() {
+        // given
+        let recorder = MetricsRecorder::start();
+
+        // when
+        recorder.clone().record_request(10);
+        recorder.record_request(5);
+        recorder.record_first_byte();
+        recorder.record_response(20);
+        recorder.record_response(30);
+        let metrics = recorder.snapshot();
+
+        // then
+        assert_eq!(metrics.request_count, 2);
+        assert_eq!(metrics.request_bytes, 15);
+        assert_eq!(metrics.response_bytes, 50);
+        assert_eq!(metrics.response_count(), 2);
+        assert!(metrics.time_to_first_byte.is_some());
+        assert!(metrics.arrivals[0] <= metrics.arrivals[1]);
+        assert!(metrics.arrivals[1] <= metrics.duration);
+    }
+}
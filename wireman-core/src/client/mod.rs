@@ -1,21 +1,31 @@
 #![allow(clippy::module_name_repetitions)]
+pub mod address;
+pub mod channel;
 pub(crate) mod codec;
+pub mod compression;
+pub mod metrics;
 pub mod reflection;
 pub mod tls;
+pub(crate) mod web;
 
-use crate::descriptor::response::StreamingResponse;
+use crate::descriptor::response::{ResponseDetails, StreamingResponse};
 use crate::descriptor::RequestMessage;
 use crate::descriptor::ResponseMessage;
 use crate::error::Error;
 use crate::Result;
+use address::Address;
+use channel::{ChannelService, ConnectionState, GrpcChannel, SharedState, Transport};
+use metrics::MetricsRecorder;
 use tls::TlsConfig;
-use tonic::transport::Uri;
-use tonic::{client::Grpc, transport::Channel};
+use tonic::{client::Grpc, Code, Streaming};
+use web::BoxError;
 
 /// Represents a `gRPC` client for making RPC calls.
 #[derive(Clone, Debug)]
 pub struct GrpcClient {
-    grpc: Grpc<Channel>,
+    grpc: Grpc<ChannelService>,
+    transport: Transport,
+    state: SharedState,
 }
 
 impl GrpcClient {
@@ -25,18 +35,47 @@ impl GrpcClient {
     /// # Errors
     ///
     /// Errors if tls config cannot be build.
-    pub fn new<T: Into<Uri>>(uri: T, tls_config: Option<TlsConfig>) -> Result<Self> {
-        let builder = Channel::builder(uri.into());
-
-        let channel = if let Some(tls_config) = tls_config {
-            builder.tls_config(tls_config.0)?.connect_lazy()
-        } else {
-            builder.connect_lazy()
-        };
-
-        Ok(GrpcClient {
-            grpc: Grpc::new(channel),
-        })
+    pub fn new<T: Into<Address>>(address: T, tls_config: Option<TlsConfig>) -> Result<Self> {
+        let channel = GrpcChannel::new(address, tls_config)?;
+
+        Ok(Self::from_channel(&channel))
+    }
+
+    /// Returns a new Grpc Client that shares the connection of the
+    /// channel.
+    #[must_use]
+    pub fn from_channel(channel: &GrpcChannel) -> Self {
+        GrpcClient {
+            grpc: Grpc::new(channel.service()),
+            transport: channel.transport(),
+            state: channel.shared_state(),
+        }
+    }
+
+    /// Returns the client with the compression settings of the request.
+    fn grpc_for(&self, request: &RequestMessage) -> Grpc<ChannelService> {
+        let compression = request.compression();
+        let mut grpc = self.grpc.clone();
+        if let Some(encoding) = compression.send {
+            grpc = grpc.send_compressed(encoding.into());
+        }
+        for encoding in &compression.accept {
+            grpc = grpc.accept_compressed((*encoding).into());
+        }
+        grpc
+    }
+
+    /// Updates the connection state from the result of a call.
+    fn observe<T>(&self, result: Result<T>) -> Result<T> {
+        match &result {
+            Ok(_) => self.state.set(ConnectionState::Ready),
+            Err(Error::GrpcNotReady(_)) => self.state.set(ConnectionState::TransientFailure),
+            Err(Error::GrpcError(status)) if status.code == Code::Unavailable => {
+                self.state.set(ConnectionState::TransientFailure);
+            }
+            Err(_) => {}
+        }
+        result
     }
 
     /// Make a unary `gRPC` call.
@@ -45,15 +84,25 @@ impl GrpcClient {
     /// - `gRPC` client is not ready
     /// - Server call failed
     pub async fn unary(&mut self, request: &RequestMessage) -> Result<ResponseMessage> {
-        self.grpc.ready().await.map_err(Error::GrpcNotReady)?;
+        let result = self.unary_inner(request).await;
+        self.observe(result)
+    }
+
+    async fn unary_inner(&mut self, request: &RequestMessage) -> Result<ResponseMessage> {
+        let mut grpc = self.grpc_for(request);
+        grpc.ready().await.map_err(not_ready)?;
 
         let path = request.path();
-        let codec = request.codec();
+        let metrics = MetricsRecorder::start();
+        let codec = request.codec().with_metrics(metrics.clone());
 
         let request = request.clone().into();
-        let response = self.grpc.unary(request, path, codec).await?;
+        // Tonic merges the trailers into the headers of unary responses, so
+        // the call is made as a stream to keep them apart.
+        let response = grpc.server_streaming(request, path, codec).await?;
+        metrics.record_first_byte();
 
-        Ok(response.into_inner())
+        into_single_response(response, metrics).await
     }
 
     /// Make a server-streaming `gRPC` call.
@@ -65,15 +114,27 @@ impl GrpcClient {
         &mut self,
         request: &RequestMessage,
     ) -> Result<StreamingResponse> {
-        self.grpc.ready().await.map_err(Error::GrpcNotReady)?;
+        let result = self.server_streaming_inner(request).await;
+        self.observe(result)
+    }
+
+    async fn server_streaming_inner(
+        &mut self,
+        request: &RequestMessage,
+    ) -> Result<StreamingResponse> {
+        let mut grpc = self.grpc_for(request);
+        grpc.ready().await.map_err(not_ready)?;
 
         let path = request.path();
-        let codec = request.codec();
+        let metrics = MetricsRecorder::start();
+        let codec = request.codec().with_metrics(metrics.clone());
 
         let request = request.clone().into();
-        let response = self.grpc.server_streaming(request, path, codec).await?;
+        let response = grpc.server_streaming(request, path, codec).await?;
+        metrics.record_first_byte();
 
-        Ok(StreamingResponse::new(response.into_inner()))
+        let (headers, inner, _) = response.into_parts();
+        Ok(StreamingResponse::new(inner, headers, metrics))
     }
 
     /// Make a client-streaming `gRPC` call.
@@ -89,15 +150,35 @@ impl GrpcClient {
     where
         S: tokio_stream::Stream<Item = RequestMessage> + Send + 'static,
     {
-        self.grpc.ready().await.map_err(Error::GrpcNotReady)?;
+        let result = self.client_streaming_inner(head, messages).await;
+        self.observe(result)
+    }
+
+    async fn client_streaming_inner<S>(
+        &mut self,
+        head: &RequestMessage,
+        messages: S,
+    ) -> Result<ResponseMessage>
+    where
+        S: tokio_stream::Stream<Item = RequestMessage> + Send + 'static,
+    {
+        if self.transport != Transport::Grpc {
+            return Err(Error::UnsupportedClientStreaming(
+                self.transport.to_string(),
+            ));
+        }
+        let mut grpc = self.grpc_for(head);
+        grpc.ready().await.map_err(not_ready)?;
 
         let path = head.path();
-        let codec = head.codec();
+        let metrics = MetricsRecorder::start();
+        let codec = head.codec().with_metrics(metrics.clone());
 
         let request = into_streaming_request(head, messages);
-        let response = self.grpc.client_streaming(request, path, codec).await?;
+        let response = grpc.streaming(request, path, codec).await?;
+        metrics.record_first_byte();
 
-        Ok(response.into_inner())
+        into_single_response(response, metrics).await
     }
 
     /// Make a bidirectional-streaming `gRPC` call.
@@ -113,15 +194,45 @@ impl GrpcClient {
     where
         S: tokio_stream::Stream<Item = RequestMessage> + Send + 'static,
     {
-        self.grpc.ready().await.map_err(Error::GrpcNotReady)?;
+        let result = self.bidirectional_streaming_inner(head, messages).await;
+        self.observe(result)
+    }
+
+    async fn bidirectional_streaming_inner<S>(
+        &mut self,
+        head: &RequestMessage,
+        messages: S,
+    ) -> Result<StreamingResponse>
+    where
+        S: tokio_stream::Stream<Item = RequestMessage> + Send + 'static,
+    {
+        if self.transport != Transport::Grpc {
+            return Err(Error::UnsupportedClientStreaming(
+                self.transport.to_string(),
+            ));
+        }
+        let mut grpc = self.grpc_for(head);
+        grpc.ready().await.map_err(not_ready)?;
 
         let path = head.path();
-        let codec = head.codec();
+        let metrics = MetricsRecorder::start();
+        let codec = head.codec().with_metrics(metrics.clone());
 
         let request = into_streaming_request(head, messages);
-        let response = self.grpc.streaming(request, path, codec).await?;
+        let response = grpc.streaming(request, path, codec).await?;
+        metrics.record_first_byte();
+
+        let (headers, inner, _) = response.into_parts();
+        Ok(StreamingResponse::new(inner, headers, metrics))
+    }
+}
 
-        Ok(StreamingResponse::new(response.into_inner()))
+/// Maps the error of a channel that is not ready.
+#[allow(clippy::needless_pass_by_value)]
+fn not_ready(err: BoxError) -> Error {
+    match err.downcast::<tonic::transport::Error>() {
+        Ok(err) => Error::GrpcNotReady(*err),
+        Err(err) => Error::Internal(err.to_string()),
     }
 }
 
@@ -136,9 +247,27 @@ where
     if let Some(metadata) = metadata {
         *req.metadata_mut() = metadata.inner;
     }
+    if let Some(timeout) = head.timeout() {
+        req.set_timeout(timeout);
+    }
     req
 }
 
+/// Reads the single message of a streamed response, followed by the trailers.
+async fn into_single_response(
+    response: tonic::Response<Streaming<ResponseMessage>>,
+    metrics: MetricsRecorder,
+) -> Result<ResponseMessage> {
+    let (headers, mut stream, _) = response.into_parts();
+    let mut message = stream
+        .message()
+        .await?
+        .ok_or_else(|| tonic::Status::internal("Missing response message."))?;
+    let trailers = stream.trailers().await?.unwrap_or_default();
+    message.details = Some(ResponseDetails::new(headers, trailers, metrics.snapshot()));
+    Ok(message)
+}
+
 /// Creates a new `gRPC` client and sends a message to a `gRPC` server.
 /// This method is async.
 ///
@@ -148,9 +277,9 @@ pub async fn call_unary_async(
     request: &RequestMessage,
     tls: Option<TlsConfig>,
 ) -> Result<ResponseMessage> {
-    let uri = request.uri()?;
+    let address = request.target()?;
 
-    let mut client = GrpcClient::new(uri, tls)?;
+    let mut client = GrpcClient::new(address, tls)?;
 
     client.unary(request).await
 }
@@ -164,9 +293,9 @@ pub async fn call_server_streaming(
     req: &RequestMessage,
     tls: Option<TlsConfig>,
 ) -> Result<StreamingResponse> {
-    let uri = req.uri()?;
+    let address = req.target()?;
 
-    let mut client = GrpcClient::new(uri, tls)?;
+    let mut client = GrpcClient::new(address, tls)?;
 
     client.server_streaming(req).await
 }
@@ -184,9 +313,9 @@ pub async fn call_client_streaming<S>(
 where
     S: tokio_stream::Stream<Item = RequestMessage> + Send + 'static,
 {
-    let uri = head.uri()?;
+    let address = head.target()?;
 
-    let mut client = GrpcClient::new(uri, tls)?;
+    let mut client = GrpcClient::new(address, tls)?;
 
     client.client_streaming(head, messages).await
 }
@@ -204,9 +333,9 @@ pub async fn call_bidirectional_streaming<S>(
 where
     S: tokio_stream::Stream<Item = RequestMessage> + Send + 'static,
 {
-    let uri = head.uri()?;
+    let address = head.target()?;
 
-    let mut client = GrpcClient::new(uri, tls)?;
+    let mut client = GrpcClient::new(address, tls)?;
 
     client.bidirectional_streaming(head, messages).await
 }
@@ -1,6 +1,8 @@
 use prost_types::FileDescriptorSet;
 use tonic::Code;
 
+use super::address::Address;
+use super::channel::{build_channel, SharedState};
 use crate::{descriptor::ReflectionRequest, error::Error};
 
 mod v1;
@@ -10,10 +12,14 @@ mod v1alpha;
 pub(crate) async fn build_file_descriptor_set(
     request: ReflectionRequest,
 ) -> Result<FileDescriptorSet, Error> {
-    let v1_result = v1::build_file_descriptor_set(request.clone()).await;
+    let address = Address::parse(&request.host)?;
+    // The channel is shared by all reflection round-trips.
+    let channel = build_channel(address, request.tls.clone(), SharedState::default())?;
+
+    let v1_result = v1::build_file_descriptor_set(&request, channel.clone()).await;
     if let Err(Error::GrpcError(status)) = &v1_result {
         if status.code == Code::Unimplemented {
-            match v1alpha::build_file_descriptor_set(request).await {
+            match v1alpha::build_file_descriptor_set(&request, channel).await {
                 Ok(alpha_result) => return Ok(alpha_result),
                 Err(_) => return v1_result,
             }
@@ -21,3 +27,86 @@ pub(crate) async fn build_file_descriptor_set(
     }
     v1_result
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bytes::Bytes;
+    use http::{Request, Response};
+    use http_body_util::{BodyExt, StreamBody};
+    use hyper::{body::Frame, server::conn::http2 as server, service::service_fn, HeaderMap};
+    use hyper_util::rt::{TokioExecutor, TokioIo};
+    use prost::Message;
+    use std::convert::Infallible;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::net::TcpListener;
+    use tonic_reflection::pb::v1alpha::{
+        server_reflection_response::MessageResponse, ListServiceResponse, ServerReflectionResponse,
+    };
+
+    /// Serves a reflection service that only implements v1alpha and
+    /// lists no services. Counts the accepted connections.
+    async fn serve_reflection(listener: TcpListener, connections: Arc<AtomicUsize>) {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            connections.fetch_add(1, Ordering::SeqCst);
+            let service = service_fn(|request: Request<hyper::body::Incoming>| async move {
+                let v1 = request.uri().path().starts_with("/grpc.reflection.v1.");
+                let _ = request.into_body().collect().await;
+
+                let mut frames: Vec<Result<_, Infallible>> = Vec::new();
+                let mut trailers = HeaderMap::new();
+                if v1 {
+                    trailers.insert("grpc-status", "12".parse().unwrap());
+                } else {
+                    let response = ServerReflectionResponse {
+                        message_response: Some(MessageResponse::ListServicesResponse(
+                            ListServiceResponse::default(),
+                        )),
+                        ..ServerReflectionResponse::default()
+                    };
+                    let payload = response.encode_to_vec();
+                    let mut frame = vec![0];
+                    frame.extend(u32::try_from(payload.len()).unwrap().to_be_bytes());
+                    frame.extend(payload);
+                    frames.push(Ok(Frame::data(Bytes::from(frame))));
+                    trailers.insert("grpc-status", "0".parse().unwrap());
+                }
+                frames.push(Ok(Frame::trailers(trailers)));
+                let response = Response::builder()
+                    .header("content-type", "application/grpc")
+                    .body(StreamBody::new(tokio_stream::iter(frames)))
+                    .unwrap();
+                Ok::<_, Infallible>(response)
+            });
+            tokio::spawn(
+                server::Builder::new(TokioExecutor::new())
+                    .serve_connection(TokioIo::new(stream), service),
+            );
+        }
+    }
+
+    #[test]
+    fn test_reflection_shares_channel() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            // given
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let connections = Arc::new(AtomicUsize::new(0));
+            tokio::spawn(serve_reflection(listener, Arc::clone(&connections)));
+            let request = ReflectionRequest::new(&format!("http://{addr}"));
+
+            // when
+            let file_descriptor_set = build_file_descriptor_set(request).await.unwrap();
+
+            // then
+            assert!(file_descriptor_set.file.is_empty());
+            assert_eq!(connections.load(Ordering::SeqCst), 1);
+        });
+    }
+}
@@ -1,9 +1,8 @@
-use std::{collections::HashMap, str::FromStr};
+use std::collections::HashMap;
 
-use http::Uri;
 use prost::Message;
 use prost_types::{FileDescriptorProto, FileDescriptorSet};
-use tokio_stream::{once, StreamExt};
+use tokio_stream::{once, Once, StreamExt};
 use tonic::{transport::Channel, Request};
 use tonic_reflection::pb::v1alpha::{
     server_reflection_client::ServerReflectionClient, server_reflection_request::MessageRequest,
@@ -12,13 +11,17 @@ use tonic_reflection::pb::v1alpha::{
 
 use crate::{descriptor::ReflectionRequest, error::Error};
 
+type Client = ServerReflectionClient<Channel>;
+
 /// # Errors
 ///
 /// Fails if server reflection fails.
 pub(crate) async fn build_file_descriptor_set(
-    request: ReflectionRequest,
+    request: &ReflectionRequest,
+    channel: Channel,
 ) -> Result<FileDescriptorSet, Error> {
-    let services = make_list_service_reflection_request(&request).await?;
+    let mut client = ServerReflectionClient::new(channel);
+    let services = make_list_service_reflection_request(&mut client, request).await?;
 
     let mut file_descriptors: HashMap<String, FileDescriptorProto> = HashMap::new();
     for service in &services {
@@ -26,8 +29,15 @@ pub(crate) async fn build_file_descriptor_set(
             continue;
         }
 
-        let file_descriptor = make_file_by_symbol_reflection_request(&request, service).await?;
-        handle_reflection_dependencies(&request, &file_descriptor, &mut file_descriptors).await?;
+        let file_descriptor =
+            make_file_by_symbol_reflection_request(&mut client, request, service).await?;
+        handle_reflection_dependencies(
+            &mut client,
+            request,
+            &file_descriptor,
+            &mut file_descriptors,
+        )
+        .await?;
         file_descriptors.insert(file_descriptor.name().to_string(), file_descriptor);
     }
     let file_descriptor_set = FileDescriptorSet {
@@ -41,53 +51,56 @@ pub(crate) async fn build_file_descriptor_set(
 ///
 /// Fails if server reflection fails.
 async fn make_file_by_symbol_reflection_request(
+    client: &mut Client,
     request: &ReflectionRequest,
     containing_symbol: &str,
 ) -> Result<FileDescriptorProto, Error> {
     let message = MessageRequest::FileContainingSymbol(String::from(containing_symbol));
-    make_file_reflection_request(request, message).await
+    make_file_reflection_request(client, request, message).await
 }
 
 /// # Errors
 ///
 /// Fails if server reflection fails.
 async fn make_file_by_filename_reflection_request(
+    client: &mut Client,
     request: &ReflectionRequest,
     filename: &str,
 ) -> Result<FileDescriptorProto, Error> {
     let message = MessageRequest::FileByFilename(String::from(filename));
-    make_file_reflection_request(request, message).await
+    make_file_reflection_request(client, request, message).await
 }
 
-/// # Errors
-///
-/// Fails if server reflection fails.
-pub(crate) async fn make_file_reflection_request(
+/// Builds the streaming reflection request including the metadata.
+fn into_reflection_request(
     request: &ReflectionRequest,
     message: MessageRequest,
-) -> Result<FileDescriptorProto, Error> {
+) -> Request<Once<ServerReflectionRequest>> {
     let reflection_request = ServerReflectionRequest {
         host: request.host.clone(),
         message_request: Some(message),
     };
     let mut reflection_request = Request::new(once(reflection_request));
 
-    // Address
-    let uri = Uri::from_str(&request.host).map_err(|_| {
-        Error::Internal(format!("Could not create uri from string {}", request.host))
-    })?;
-
     // Metadata
     let metadata = request.metadata.clone();
     if let Some(meta) = metadata {
         *reflection_request.metadata_mut() = meta.inner;
     }
 
-    // Build channel
-    let builder = Channel::builder(uri);
-    let channel = builder.connect_lazy();
+    reflection_request
+}
+
+/// # Errors
+///
+/// Fails if server reflection fails.
+async fn make_reflection_request(
+    client: &mut Client,
+    request: &ReflectionRequest,
+    message: MessageRequest,
+) -> Result<MessageResponse, Error> {
+    let reflection_request = into_reflection_request(request, message);
 
-    let mut client = ServerReflectionClient::new(channel);
     let mut inbound = client
         .server_reflection_info(reflection_request)
         .await?
@@ -102,6 +115,19 @@ pub(crate) async fn make_file_reflection_request(
 
     debug_assert!(inbound.next().await.is_none());
 
+    Ok(response)
+}
+
+/// # Errors
+///
+/// Fails if server reflection fails.
+async fn make_file_reflection_request(
+    client: &mut Client,
+    request: &ReflectionRequest,
+    message: MessageRequest,
+) -> Result<FileDescriptorProto, Error> {
+    let response = make_reflection_request(client, request, message).await?;
+
     let MessageResponse::FileDescriptorResponse(descriptor) = response else {
         let internal =
             Error::Internal("File descriptor reflection response is of incorrect type".to_string());
@@ -126,46 +152,11 @@ pub(crate) async fn make_file_reflection_request(
 ///
 /// Fails if server reflection fails.
 async fn make_list_service_reflection_request(
+    client: &mut Client,
     request: &ReflectionRequest,
 ) -> Result<Vec<String>, Error> {
-    let host = request.host.clone();
-
-    let message_request = MessageRequest::ListServices(String::new());
-    let reflection_request = ServerReflectionRequest {
-        host: host.clone(),
-        message_request: Some(message_request),
-    };
-    let mut reflection_request = Request::new(tokio_stream::once(reflection_request));
-
-    // Address
-    let uri = Uri::from_str(&host).map_err(|_| {
-        Error::Internal(format!("Could not create uri from string {}", request.host))
-    })?;
-
-    // Metadata
-    let metadata = request.metadata.clone();
-    if let Some(meta) = metadata {
-        *reflection_request.metadata_mut() = meta.inner;
-    }
-
-    // Build channel
-    let builder = Channel::builder(uri);
-    let channel = builder.connect_lazy();
-
-    let mut client = ServerReflectionClient::new(channel);
-    let mut inbound = client
-        .server_reflection_info(reflection_request)
-        .await?
-        .into_inner();
-
-    let response = inbound
-        .next()
-        .await
-        .ok_or(Error::Internal("No streamed response".to_string()))??
-        .message_response
-        .ok_or(Error::Internal("No message response".to_string()))?;
-
-    debug_assert!(inbound.next().await.is_none());
+    let message = MessageRequest::ListServices(String::new());
+    let response = make_reflection_request(client, request, message).await?;
 
     let MessageResponse::ListServicesResponse(response) = response else {
         let internal =
@@ -181,6 +172,7 @@ async fn make_list_service_reflection_request(
 /// Fails if server reflection fails.
 #[allow(clippy::implicit_hasher)]
 async fn handle_reflection_dependencies(
+    client: &mut Client,
     request: &ReflectionRequest,
     file_descriptor: &FileDescriptorProto,
     file_descriptors: &mut HashMap<String, FileDescriptorProto>,
@@ -190,12 +182,14 @@ async fn handle_reflection_dependencies(
             continue;
         }
 
-        let dep_descriptor = make_file_by_filename_reflection_request(request, &dependency).await?;
+        let dep_descriptor =
+            make_file_by_filename_reflection_request(client, request, &dependency).await?;
 
         file_descriptors.insert(dep_descriptor.name().to_string(), dep_descriptor.clone());
 
         // Recursively handle the dependencies
         Box::pin(handle_reflection_dependencies(
+            client,
             request,
             &dep_descriptor,
             file_descriptors,
@@ -1,16 +1,45 @@
 #![allow(clippy::module_name_repetitions)]
 use crate::error::{Error, Result};
-use tonic::transport::{Certificate, ClientTlsConfig};
+use http::Uri;
+use hyper_util::rt::TokioIo;
+use rustls::{
+    client::{
+        danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+        WebPkiServerVerifier,
+    },
+    pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer, ServerName, UnixTime},
+    CertificateError, ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme,
+};
+use std::{
+    future::Future,
+    io,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tokio::net::TcpStream;
+use tokio_rustls::{client::TlsStream, TlsConnector};
+use tonic::transport::{Certificate, ClientTlsConfig, Identity};
+use tower_service::Service;
 
 /// The TLS config of the grpc client.
-#[derive(Debug, Clone)]
-pub struct TlsConfig(pub(super) ClientTlsConfig);
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct TlsConfig {
+    /// The PEM encoded root certificate. Uses the native roots if `None`.
+    ca: Option<String>,
+    /// The PEM encoded client certificate and private key for mTLS.
+    identity: Option<(String, String)>,
+    /// Overrides the domain name used for SNI and certificate verification.
+    domain_name: Option<String>,
+    /// Whether the hostname of the server certificate is verified.
+    skip_hostname_verification: bool,
+}
 
 impl TlsConfig {
     /// Create a new `TlsConfig` with native certificate.
     #[must_use]
     pub fn native() -> Self {
-        Self(ClientTlsConfig::new().with_enabled_roots())
+        Self::default()
     }
 
     /// Create a new `TlsConfig` with a custom certificate.
@@ -20,9 +49,254 @@ impl TlsConfig {
     /// Errors if root certificates cannot be read from path.
     pub fn custom(cert_path: String) -> Result<Self> {
         let pem = std::fs::read_to_string(cert_path).map_err(Error::LoadTLSCertificateError)?;
-        let ca = Certificate::from_pem(pem);
-        let tls = ClientTlsConfig::new().ca_certificate(ca);
+        Ok(Self {
+            ca: Some(pem),
+            ..Self::default()
+        })
+    }
+
+    /// Sets the client certificate and private key for mutual TLS.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the certificate or key cannot be read from path.
+    pub fn with_client_identity(mut self, cert_path: String, key_path: String) -> Result<Self> {
+        let cert = std::fs::read_to_string(cert_path).map_err(Error::LoadTLSIdentityError)?;
+        let key = std::fs::read_to_string(key_path).map_err(Error::LoadTLSIdentityError)?;
+        self.identity = Some((cert, key));
+        Ok(self)
+    }
+
+    /// Overrides the domain name used for SNI and certificate verification.
+    #[must_use]
+    pub fn with_domain_name<T: Into<String>>(mut self, domain_name: T) -> Self {
+        self.domain_name = Some(domain_name.into());
+        self
+    }
+
+    /// Skips the hostname verification of the server certificate. The
+    /// certificate chain is still verified. Intended for local dev certs.
+    #[must_use]
+    pub fn with_skip_hostname_verification(mut self, skip: bool) -> Self {
+        self.skip_hostname_verification = skip;
+        self
+    }
+
+    /// Whether the hostname verification is skipped.
+    #[must_use]
+    pub fn skip_hostname_verification(&self) -> bool {
+        self.skip_hostname_verification
+    }
+
+    /// Returns the tonic tls config.
+    pub(super) fn client_tls_config(&self) -> ClientTlsConfig {
+        let mut tls = ClientTlsConfig::new();
+        tls = match &self.ca {
+            Some(ca) => tls.ca_certificate(Certificate::from_pem(ca)),
+            None => tls.with_enabled_roots(),
+        };
+        if let Some((cert, key)) = &self.identity {
+            tls = tls.identity(Identity::from_pem(cert, key));
+        }
+        if let Some(domain_name) = &self.domain_name {
+            tls = tls.domain_name(domain_name);
+        }
+        tls
+    }
+
+    /// Returns a connector that establishes the TLS connection without
+    /// verifying the hostname. Tonic does not support custom certificate
+    /// verifiers, so the handshake is done before handing over the stream.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the certificates or the private key cannot be parsed.
+    pub(super) fn skip_hostname_connector(&self) -> Result<TlsStreamConnector> {
+        self.tls_stream_connector(b"h2")
+    }
+
+    /// Returns a connector that establishes the TLS connection and
+    /// negotiates the given ALPN protocol. The hostname is only verified
+    /// if the verification is not skipped.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the certificates or the private key cannot be parsed.
+    pub(super) fn tls_stream_connector(&self, alpn: &[u8]) -> Result<TlsStreamConnector> {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+
+        let mut roots = RootCertStore::empty();
+        if let Some(ca) = &self.ca {
+            for cert in CertificateDer::pem_slice_iter(ca.as_bytes()) {
+                roots
+                    .add(cert.map_err(tls_config_error)?)
+                    .map_err(tls_config_error)?;
+            }
+        } else {
+            roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+        }
+        let verifier =
+            WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider.clone())
+                .build()
+                .map_err(tls_config_error)?;
+
+        let verifier: Arc<dyn ServerCertVerifier> = if self.skip_hostname_verification {
+            Arc::new(SkipHostnameVerifier(verifier))
+        } else {
+            verifier
+        };
+        let builder = ClientConfig::builder_with_provider(Arc::clone(&provider))
+            .with_safe_default_protocol_versions()
+            .map_err(tls_config_error)?
+            .dangerous()
+            .with_custom_certificate_verifier(verifier);
+
+        let mut config = if let Some((cert, key)) = &self.identity {
+            let certs = CertificateDer::pem_slice_iter(cert.as_bytes())
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(tls_config_error)?;
+            let key = PrivateKeyDer::from_pem_slice(key.as_bytes()).map_err(tls_config_error)?;
+            builder
+                .with_client_auth_cert(certs, key)
+                .map_err(tls_config_error)?
+        } else {
+            builder.with_no_client_auth()
+        };
+        config.alpn_protocols = vec![alpn.to_vec()];
+
+        Ok(TlsStreamConnector {
+            connector: TlsConnector::from(Arc::new(config)),
+            domain_name: self.domain_name.clone(),
+        })
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn tls_config_error<E: std::fmt::Display>(err: E) -> Error {
+    Error::TLSConfigError(err.to_string())
+}
+
+/// Verifies the server certificate chain but accepts certificates that
+/// are not valid for the requested hostname.
+#[derive(Debug)]
+struct SkipHostnameVerifier(Arc<WebPkiServerVerifier>);
+
+impl ServerCertVerifier for SkipHostnameVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        match self
+            .0
+            .verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)
+        {
+            Err(rustls::Error::InvalidCertificate(
+                CertificateError::NotValidForName | CertificateError::NotValidForNameContext { .. },
+            )) => Ok(ServerCertVerified::assertion()),
+            result => result,
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        self.0.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        self.0.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.supported_verify_schemes()
+    }
+}
+
+/// Connects to the server and performs the TLS handshake.
+#[derive(Clone)]
+pub(super) struct TlsStreamConnector {
+    connector: TlsConnector,
+    domain_name: Option<String>,
+}
+
+impl Service<Uri> for TlsStreamConnector {
+    type Response = TokioIo<TlsStream<TcpStream>>;
+    type Error = io::Error;
+    type Future = Pin<Box<dyn Future<Output = io::Result<Self::Response>> + Send>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let connector = self.connector.clone();
+        let host = uri
+            .host()
+            .unwrap_or_default()
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_string();
+        let port = uri.port_u16().unwrap_or(443);
+        let domain_name = self.domain_name.clone().unwrap_or_else(|| host.clone());
+
+        Box::pin(async move {
+            let server_name = ServerName::try_from(domain_name)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+            let stream = TcpStream::connect((host.as_str(), port)).await?;
+            stream.set_nodelay(true)?;
+            let stream = connector.connect(server_name, stream).await?;
+            Ok(TokioIo::new(stream))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_missing_client_identity() {
+        let result = TlsConfig::native()
+            .with_client_identity("missing.pem".to_string(), "missing.key".to_string());
+        assert!(matches!(result, Err(Error::LoadTLSIdentityError(_))));
+    }
+
+    #[test]
+    fn test_skip_hostname_connector() {
+        let tls = TlsConfig::native()
+            .with_domain_name("localhost")
+            .with_skip_hostname_verification(true);
+        assert!(tls.skip_hostname_verification());
+        assert!(tls.skip_hostname_connector().is_ok());
+
+        let invalid_ca = TlsConfig {
+            ca: Some(String::from("not a certificate")),
+            ..tls.clone()
+        };
+        assert!(matches!(
+            invalid_ca.skip_hostname_connector(),
+            Err(Error::TLSConfigError(_))
+        ));
 
-        Ok(Self(tls))
+        let invalid_key = TlsConfig {
+            identity: Some((String::new(), String::from("not a key"))),
+            ..tls
+        };
+        assert!(matches!(
+            invalid_key.skip_hostname_connector(),
+            Err(Error::TLSConfigError(_))
+        ));
     }
 }
@@ -0,0 +1,547 @@
+//! A `gRPC-Web` transport. Requests are sent over HTTP/1.1 and the
+//! trailers are parsed from the end of the response body.
+#![allow(clippy::module_name_repetitions)]
+use super::address::Address;
+#[cfg(unix)]
+use super::channel::UnixConnector;
+use super::channel::{SharedState, StateConnector, TcpConnector};
+use super::tls::TlsConfig;
+#[cfg(not(unix))]
+use crate::error::Error;
+use crate::Result;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bytes::{Buf, Bytes, BytesMut};
+use http::{
+    header::{ACCEPT, CONTENT_TYPE, HOST, TE},
+    uri::PathAndQuery,
+    HeaderMap, HeaderName, HeaderValue, Request, Response, Uri, Version,
+};
+use http_body::{Body as HttpBody, Frame};
+use http_body_util::{BodyExt, Full};
+use hyper::client::conn::http1::{self, SendRequest};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{ready, Context, Poll},
+};
+use tokio::sync::Mutex;
+use tonic::body::Body;
+use tower_service::Service;
+
+pub(crate) type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// The flag of a frame that holds the trailers.
+const TRAILERS_FLAG: u8 = 0x80;
+
+/// The length of the flag and length prefix of a frame.
+const FRAME_HEADER_LEN: usize = 5;
+
+/// Translates `gRPC` requests into `gRPC-Web` requests. The connection is
+/// kept open and reused for subsequent calls.
+#[derive(Clone)]
+pub(crate) struct GrpcWebService {
+    uri: Uri,
+    text: bool,
+    connector: WebConnector,
+    sender: Arc<Mutex<Option<SendRequest<Full<Bytes>>>>>,
+}
+
+impl std::fmt::Debug for GrpcWebService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GrpcWebService")
+            .field("uri", &self.uri)
+            .field("text", &self.text)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Clone)]
+enum WebConnector {
+    Tcp(StateConnector<TcpConnector>),
+    Tls(StateConnector<super::tls::TlsStreamConnector>),
+    #[cfg(unix)]
+    Unix(StateConnector<super::channel::UnixConnector>),
+}
+
+impl GrpcWebService {
+    /// Returns a new `gRPC-Web` service. If `text` is set, the messages
+    /// are base64 encoded (`grpc-web-text`).
+    ///
+    /// # Errors
+    ///
+    /// Errors if tls config cannot be build.
+    pub(crate) fn new(
+        address: Address,
+        tls_config: Option<TlsConfig>,
+        text: bool,
+        state: SharedState,
+    ) -> Result<Self> {
+        let uri = address.uri();
+        let connector = match (address, tls_config) {
+            #[cfg(unix)]
+            (Address::Unix(path), _) => {
+                WebConnector::Unix(StateConnector::new(UnixConnector(path), state))
+            }
+            #[cfg(not(unix))]
+            (Address::Unix(_), _) => {
+                return Err(Error::InvalidAddress(String::from(
+                    "unix sockets are not supported on this platform",
+                )))
+            }
+            (Address::Tcp(_), Some(tls_config)) => WebConnector::Tls(StateConnector::new(
+                tls_config.tls_stream_connector(b"http/1.1")?,
+                state,
+            )),
+            (Address::Tcp(_), None) => WebConnector::Tcp(StateConnector::new(TcpConnector, state)),
+        };
+        Ok(Self {
+            uri,
+            text,
+            connector,
+            sender: Arc::default(),
+        })
+    }
+
+    /// Opens a new HTTP/1.1 connection.
+    async fn connect(
+        connector: WebConnector,
+        uri: Uri,
+    ) -> std::result::Result<SendRequest<Full<Bytes>>, BoxError> {
+        let sender = match connector {
+            WebConnector::Tcp(mut connector) => {
+                let (sender, conn) = http1::handshake(connector.call(uri).await?).await?;
+                tokio::spawn(conn);
+                sender
+            }
+            WebConnector::Tls(mut connector) => {
+                let (sender, conn) = http1::handshake(connector.call(uri).await?).await?;
+                tokio::spawn(conn);
+                sender
+            }
+            #[cfg(unix)]
+            WebConnector::Unix(mut connector) => {
+                let (sender, conn) = http1::handshake(connector.call(uri).await?).await?;
+                tokio::spawn(conn);
+                sender
+            }
+        };
+        Ok(sender)
+    }
+
+    /// Converts a `gRPC` request into a `gRPC-Web` request.
+    async fn into_web_request(
+        uri: &Uri,
+        text: bool,
+        request: Request<Body>,
+    ) -> std::result::Result<Request<Full<Bytes>>, BoxError> {
+        let (mut parts, body) = request.into_parts();
+        let mut body = body.collect().await?.to_bytes();
+        if text {
+            body = Bytes::from(STANDARD.encode(&body));
+        }
+
+        let path = parts
+            .uri
+            .path_and_query()
+            .cloned()
+            .unwrap_or_else(|| PathAndQuery::from_static("/"));
+        parts.uri = Uri::from(path);
+        parts.version = Version::HTTP_11;
+
+        let content_type = HeaderValue::from_static(if text {
+            "application/grpc-web-text+proto"
+        } else {
+            "application/grpc-web+proto"
+        });
+        let headers = &mut parts.headers;
+        headers.remove(TE);
+        headers.insert(CONTENT_TYPE, content_type.clone());
+        headers.insert(ACCEPT, content_type);
+        headers.insert("x-grpc-web", HeaderValue::from_static("1"));
+        if let Some(authority) = uri.authority() {
+            headers.insert(HOST, HeaderValue::from_str(authority.as_str())?);
+        }
+
+        Ok(Request::from_parts(parts, Full::new(body)))
+    }
+}
+
+impl Service<Request<Body>> for GrpcWebService {
+    type Response = Response<Body>;
+    type Error = BoxError;
+    type Future =
+        Pin<Box<dyn Future<Output = std::result::Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<std::result::Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let this = self.clone();
+
+        Box::pin(async move {
+            let request = Self::into_web_request(&this.uri, this.text, request).await?;
+
+            // A HTTP/1.1 connection handles one call at a time.
+            let mut cached = this.sender.lock().await;
+            let reusable = match cached.take() {
+                Some(mut sender) => sender.ready().await.is_ok().then_some(sender),
+                None => None,
+            };
+            let mut sender = match reusable {
+                Some(sender) => sender,
+                None => Self::connect(this.connector.clone(), this.uri.clone()).await?,
+            };
+            let response = sender.send_request(request).await;
+            *cached = Some(sender);
+            let response = response?;
+
+            let (mut parts, body) = response.into_parts();
+            parts
+                .headers
+                .insert(CONTENT_TYPE, HeaderValue::from_static("application/grpc"));
+            let body = GrpcWebBody::new(body, this.text);
+            Ok(Response::from_parts(parts, Body::new(body)))
+        })
+    }
+}
+
+/// Converts a `gRPC-Web` response body into a `gRPC` response body. The
+/// data frames are passed on and the trailers frame is returned as
+/// HTTP trailers.
+pub(crate) struct GrpcWebBody<B> {
+    inner: B,
+    text: bool,
+    /// The decoded bytes that do not form a complete frame yet
+    buf: BytesMut,
+    /// The base64 encoded bytes that do not form a complete quantum yet
+    encoded: Vec<u8>,
+    done: bool,
+}
+
+impl<B> GrpcWebBody<B> {
+    pub(crate) fn new(inner: B, text: bool) -> Self {
+        Self {
+            inner,
+            text,
+            buf: BytesMut::new(),
+            encoded: Vec::new(),
+            done: false,
+        }
+    }
+
+    /// Appends a chunk of the response body.
+    fn push(&mut self, data: &[u8]) -> std::result::Result<(), BoxError> {
+        if !self.text {
+            self.buf.extend_from_slice(data);
+            return Ok(());
+        }
+        self.encoded
+            .extend(data.iter().filter(|b| !b.is_ascii_whitespace()));
+        let complete = self.encoded.len() / 4 * 4;
+        // The server may pad every chunk, so each padded segment is
+        // decoded on its own.
+        let mut start = 0;
+        while start < complete {
+            let mut end = start;
+            while end < complete {
+                end += 4;
+                if self.encoded[end - 1] == b'=' {
+                    break;
+                }
+            }
+            let decoded = STANDARD.decode(&self.encoded[start..end])?;
+            self.buf.extend_from_slice(&decoded);
+            start = end;
+        }
+        self.encoded.drain(..complete);
+        Ok(())
+    }
+
+    /// Returns the next complete frame.
+    fn next_frame(&mut self) -> std::result::Result<Option<Frame<Bytes>>, BoxError> {
+        if self.buf.len() < FRAME_HEADER_LEN {
+            return Ok(None);
+        }
+        let flag = self.buf[0];
+        let len = u32::from_be_bytes([self.buf[1], self.buf[2], self.buf[3], self.buf[4]]);
+        let len = usize::try_from(len)?;
+        if self.buf.len() < FRAME_HEADER_LEN + len {
+            return Ok(None);
+        }
+        let mut frame = self.buf.split_to(FRAME_HEADER_LEN + len).freeze();
+        if flag & TRAILERS_FLAG == 0 {
+            return Ok(Some(Frame::data(frame)));
+        }
+        frame.advance(FRAME_HEADER_LEN);
+        Ok(Some(Frame::trailers(parse_trailers(&frame)?)))
+    }
+}
+
+impl<B> HttpBody for GrpcWebBody<B>
+where
+    B: HttpBody<Data = Bytes> + Unpin,
+    B::Error: Into<BoxError>,
+{
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<std::result::Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(frame) = this.next_frame()? {
+                return Poll::Ready(Some(Ok(frame)));
+            }
+            if this.done {
+                if this.buf.is_empty() && this.encoded.is_empty() {
+                    return Poll::Ready(None);
+                }
+                this.buf.clear();
+                this.encoded.clear();
+                return Poll::Ready(Some(Err("incomplete grpc-web frame".into())));
+            }
+            match ready!(Pin::new(&mut this.inner).poll_frame(cx)) {
+                Some(Ok(frame)) => match frame.into_data() {
+                    Ok(data) => this.push(&data)?,
+                    // Trailers sent as HTTP/2 trailers are passed on as is.
+                    Err(frame) => return Poll::Ready(Some(Ok(frame))),
+                },
+                Some(Err(err)) => return Poll::Ready(Some(Err(err.into()))),
+                None => this.done = true,
+            }
+        }
+    }
+}
+
+/// Parses the trailers of a `gRPC-Web` response, which are encoded as
+/// HTTP/1 header lines.
+fn parse_trailers(data: &[u8]) -> std::result::Result<HeaderMap, BoxError> {
+    let mut trailers = HeaderMap::new();
+    for line in data.split(|b| *b == b'\n') {
+        let line = std::str::from_utf8(line)?.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            return Err("invalid grpc-web trailer".into());
+        };
+        let name = HeaderName::from_bytes(name.trim().as_bytes())?;
+        let value = HeaderValue::from_str(value.trim())?;
+        trailers.append(name, value);
+    }
+    Ok(trailers)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::channel::{GrpcChannel, Transport};
+    use crate::client::GrpcClient;
+    use crate::descriptor::RequestMessage;
+    use crate::error::Error;
+    use crate::ProtoDescriptor;
+    use hyper::{server::conn::http1 as server, service::service_fn};
+    use hyper_util::rt::TokioIo;
+    use prost::Message;
+    use std::collections::VecDeque;
+    use tokio::net::TcpListener;
+
+    fn load_test_message(method: &str) -> RequestMessage {
+        let desc = ProtoDescriptor::new(vec!["."], vec!["test_files/test.proto"]).unwrap();
+        let method = desc
+            .get_method_by_name("proto.TestService", method)
+            .unwrap();
+        RequestMessage::new(method.input(), method)
+    }
+
+    fn frame(flag: u8, payload: &[u8]) -> Vec<u8> {
+        let mut frame = vec![flag];
+        frame.extend(u32::try_from(payload.len()).unwrap().to_be_bytes());
+        frame.extend(payload);
+        frame
+    }
+
+    /// Serves a single connection like a `gRPC-Web` proxy. Each frame of
+    /// the response is base64 encoded on its own in text mode.
+    async fn serve_grpc_web(listener: TcpListener, status: u8) {
+        let (stream, _) = listener.accept().await.unwrap();
+        let service = service_fn(move |request: Request<hyper::body::Incoming>| async move {
+            let content_type = request.headers()[CONTENT_TYPE]
+                .to_str()
+                .unwrap()
+                .to_string();
+            assert_eq!(request.headers()["x-grpc-web"], "1");
+            assert_eq!(request.version(), Version::HTTP_11);
+            let text = content_type == "application/grpc-web-text+proto";
+
+            let body = request.into_body().collect().await.unwrap().to_bytes();
+            let body = if text {
+                STANDARD.decode(&body).unwrap()
+            } else {
+                body.to_vec()
+            };
+            assert_eq!(body[0], 0);
+
+            let trailers = format!("grpc-status:{status}\r\ngrpc-message:not found\r\n");
+            let frames = [frame(0, &[]), frame(TRAILERS_FLAG, trailers.as_bytes())];
+            let body: Vec<u8> = if text {
+                frames
+                    .iter()
+                    .flat_map(|frame| STANDARD.encode(frame).into_bytes())
+                    .collect()
+            } else {
+                frames.concat()
+            };
+            let response = Response::builder()
+                .header(CONTENT_TYPE, content_type)
+                .body(Full::new(Bytes::from(body)))
+                .unwrap();
+            Ok::<_, std::convert::Infallible>(response)
+        });
+        let _ = server::Builder::new()
+            .serve_connection(TokioIo::new(stream), service)
+            .await;
+    }
+
+    fn run_call(transport: Transport, status: u8) -> crate::Result<()> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            tokio::spawn(serve_grpc_web(listener, status));
+
+            let uri: Uri = format!("http://{addr}").parse().unwrap();
+            let channel = GrpcChannel::with_transport(uri, None, transport)?;
+            let mut client = GrpcClient::from_channel(&channel);
+            client.unary(&load_test_message("Simple")).await.map(|_| ())
+        })
+    }
+
+    #[test]
+    fn test_grpc_web_unary() {
+        assert!(run_call(Transport::GrpcWeb, 0).is_ok());
+        assert!(run_call(Transport::GrpcWebText, 0).is_ok());
+    }
+
+    #[test]
+    fn test_grpc_web_status() {
+        let result = run_call(Transport::GrpcWebText, 5);
+        let Err(Error::GrpcError(status)) = result else {
+            panic!("expected grpc error, got {result:?}");
+        };
+        assert_eq!(status.code, tonic::Code::NotFound);
+        assert_eq!(status.message, "not found");
+    }
+
+    /// A response body that yields its chunks one at a time.
+    struct ChunkedBody(VecDeque<Bytes>);
+
+    impl HttpBody for ChunkedBody {
+        type Data = Bytes;
+        type Error = std::convert::Infallible;
+
+        fn poll_frame(
+            mut self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+        ) -> Poll<Option<std::result::Result<Frame<Bytes>, Self::Error>>> {
+            Poll::Ready(self.0.pop_front().map(|chunk| Ok(Frame::data(chunk))))
+        }
+    }
+
+    /// Serves a single connection that streams `messages` followed by a
+    /// trailer frame. The body is split into chunks of three bytes, so
+    /// that the frames and their base64 encoding span several chunks.
+    async fn serve_grpc_web_stream(listener: TcpListener, messages: Vec<Vec<u8>>) {
+        let (stream, _) = listener.accept().await.unwrap();
+        let service = service_fn(move |request: Request<hyper::body::Incoming>| {
+            let messages = messages.clone();
+            async move {
+                let content_type = request.headers()[CONTENT_TYPE]
+                    .to_str()
+                    .unwrap()
+                    .to_string();
+                let text = content_type == "application/grpc-web-text+proto";
+
+                let mut frames: Vec<Vec<u8>> =
+                    messages.iter().map(|message| frame(0, message)).collect();
+                frames.push(frame(TRAILERS_FLAG, b"grpc-status:0\r\nx-stream:done\r\n"));
+                let body: Vec<u8> = if text {
+                    frames
+                        .iter()
+                        .flat_map(|frame| STANDARD.encode(frame).into_bytes())
+                        .collect()
+                } else {
+                    frames.concat()
+                };
+                let chunks = body.chunks(3).map(Bytes::copy_from_slice).collect();
+                let response = Response::builder()
+                    .header(CONTENT_TYPE, content_type)
+                    .body(ChunkedBody(chunks))
+                    .unwrap();
+                Ok::<_, std::convert::Infallible>(response)
+            }
+        });
+        let _ = server::Builder::new()
+            .serve_connection(TokioIo::new(stream), service)
+            .await;
+    }
+
+    fn run_server_streaming(transport: Transport, messages: Vec<Vec<u8>>) -> Vec<Vec<u8>> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            tokio::spawn(serve_grpc_web_stream(listener, messages));
+
+            let uri: Uri = format!("http://{addr}").parse().unwrap();
+            let channel = GrpcChannel::with_transport(uri, None, transport).unwrap();
+            let mut client = GrpcClient::from_channel(&channel);
+            let mut response = client
+                .server_streaming(&load_test_message("Simple"))
+                .await
+                .unwrap();
+            let mut received = Vec::new();
+            while let Some(message) = response.message().await.unwrap() {
+                received.push(message.message.encode_to_vec());
+            }
+            let details = response.details().await.unwrap();
+            assert!(details
+                .trailers
+                .entries()
+                .contains(&("x-stream".to_string(), "done".to_string())));
+            received
+        })
+    }
+
+    #[test]
+    fn test_grpc_web_server_streaming() {
+        // given
+        let messages = vec![vec![8, 1], vec![8, 150, 1], vec![8, 3]];
+
+        // when
+        let binary = run_server_streaming(Transport::GrpcWeb, messages.clone());
+        let text = run_server_streaming(Transport::GrpcWebText, messages.clone());
+
+        // then
+        assert_eq!(binary, messages);
+        assert_eq!(text, messages);
+    }
+
+    #[test]
+    fn test_parse_trailers() {
+        let trailers = parse_trailers(b"grpc-status: 0\r\ngrpc-message: ok\r\n").unwrap();
+        assert_eq!(trailers["grpc-status"], "0");
+        assert_eq!(trailers["grpc-message"], "ok");
+        assert!(parse_trailers(b"invalid").is_err());
+    }
+}
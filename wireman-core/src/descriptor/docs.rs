@@ -0,0 +1,139 @@
+use prost_reflect::{
+    EnumValueDescriptor, FieldDescriptor, FileDescriptor, MessageDescriptor, MethodDescriptor,
+    ServiceDescriptor,
+};
+
+/// Documentation of a proto element taken from its source file.
+pub trait Documented {
+    /// Returns the comments of the element, i.e. its leading comments or,
+    /// if it has none, its trailing comments. Returns `None` if the
+    /// element is not commented or the source info was not retained.
+    fn comments(&self) -> Option<String>;
+
+    /// Whether the element is marked with `deprecated = true`.
+    fn is_deprecated(&self) -> bool;
+}
+
+impl Documented for ServiceDescriptor {
+    fn comments(&self) -> Option<String> {
+        comments(&self.parent_file(), self.path())
+    }
+
+    fn is_deprecated(&self) -> bool {
+        self.service_descriptor_proto()
+            .options
+            .as_ref()
+            .is_some_and(prost_types::ServiceOptions::deprecated)
+    }
+}
+
+impl Documented for MethodDescriptor {
+    fn comments(&self) -> Option<String> {
+        comments(&self.parent_file(), self.path())
+    }
+
+    fn is_deprecated(&self) -> bool {
+        self.method_descriptor_proto()
+            .options
+            .as_ref()
+            .is_some_and(prost_types::MethodOptions::deprecated)
+    }
+}
+
+impl Documented for MessageDescriptor {
+    fn comments(&self) -> Option<String> {
+        comments(&self.parent_file(), self.path())
+    }
+
+    fn is_deprecated(&self) -> bool {
+        self.descriptor_proto()
+            .options
+            .as_ref()
+            .is_some_and(prost_types::MessageOptions::deprecated)
+    }
+}
+
+impl Documented for FieldDescriptor {
+    fn comments(&self) -> Option<String> {
+        comments(&self.parent_file(), self.path())
+    }
+
+    fn is_deprecated(&self) -> bool {
+        self.field_descriptor_proto()
+            .options
+            .as_ref()
+            .is_some_and(prost_types::FieldOptions::deprecated)
+    }
+}
+
+impl Documented for EnumValueDescriptor {
+    fn comments(&self) -> Option<String> {
+        comments(&self.parent_file(), self.path())
+    }
+
+    fn is_deprecated(&self) -> bool {
+        self.enum_value_descriptor_proto()
+            .options
+            .as_ref()
+            .is_some_and(prost_types::EnumValueOptions::deprecated)
+    }
+}
+
+/// Looks up the comments of the element at `path` in the source code
+/// info of a file. The space that follows the `//` of each comment line
+/// is removed.
+fn comments(file: &FileDescriptor, path: &[i32]) -> Option<String> {
+    let info = file.file_descriptor_proto().source_code_info.as_ref()?;
+    let location = info.location.iter().find(|loc| loc.path == path)?;
+    let comments = location
+        .leading_comments
+        .as_deref()
+        .filter(|comments| !comments.trim().is_empty())
+        .or(location.trailing_comments.as_deref())?;
+    let comments = comments
+        .lines()
+        .map(|line| line.strip_prefix(' ').unwrap_or(line).trim_end())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let comments = comments.trim();
+    (!comments.is_empty()).then(|| comments.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ProtoDescriptor;
+
+    #[test]
+    fn test_documented() {
+        // given
+        let desc = ProtoDescriptor::new(["."], ["test_files/docs.proto"]).unwrap();
+        let service = desc.get_service_by_name("docs.DocsService").unwrap();
+        let simple = desc
+            .get_method_by_name("docs.DocsService", "Simple")
+            .unwrap();
+        let legacy = desc
+            .get_method_by_name("docs.DocsService", "Legacy")
+            .unwrap();
+        let number = simple.input().get_field_by_name("number").unwrap();
+        let id = legacy.input().get_field_by_name("id").unwrap();
+
+        // when
+        let service_comments = service.comments();
+        let method_comments = simple.comments();
+        let field_comments = number.comments();
+
+        // then
+        assert_eq!(
+            service_comments.as_deref(),
+            Some("A service to test the docs.")
+        );
+        assert_eq!(method_comments.as_deref(), Some("Sends a single number."));
+        assert_eq!(field_comments.as_deref(), Some("The number to send."));
+        assert_eq!(legacy.comments(), None);
+        assert!(legacy.is_deprecated());
+        assert!(!simple.is_deprecated());
+        assert!(id.is_deprecated());
+        assert!(!number.is_deprecated());
+    }
+}
@@ -1,15 +1,19 @@
 mod template;
+mod validate;
 
-use self::template::apply_template_for_message;
+use self::template::{apply_template_for_message, format_json_template};
+pub use self::validate::{validate_json, JsonProblem, Position};
 use crate::{
     error::{Error, FROM_UTF8},
     Result,
 };
+use prost::Message;
 use prost_reflect::{
-    DeserializeOptions, DynamicMessage as DynMessage, MessageDescriptor, ReflectMessage,
-    SerializeOptions,
+    text_format::FormatOptions, DeserializeOptions, DynamicMessage as DynMessage, Kind,
+    MessageDescriptor, ReflectMessage, SerializeOptions,
 };
 use serde::{Serialize, Serializer};
+use std::borrow::Cow;
 use std::ops::{Deref, DerefMut};
 
 /// Represents a dynamic `gRPC` message that can be used
@@ -61,11 +65,22 @@ impl DynamicMessage {
     ///
     /// - Failed to deserialize message.
     pub fn from_json(&mut self, json: &str) -> Result<()> {
-        let mut de = serde_json::Deserializer::from_str(json);
+        self.from_json_with_options(json, &JsonOptions::default())
+    }
+
+    /// Deserialize a `DynamicMessage` from a JSON string with the given
+    /// options. Line comments starting with `//` are ignored.
+    ///
+    /// # Errors
+    ///
+    /// - Failed to deserialize message.
+    pub fn from_json_with_options(&mut self, json: &str, options: &JsonOptions) -> Result<()> {
+        let json = strip_json_comments(json);
+        let mut de = serde_json::Deserializer::from_str(&json);
         let msg = DynMessage::deserialize_with_options(
             self.descriptor(),
             &mut de,
-            &DeserializeOptions::new(),
+            &options.deserialize_options(),
         )
         .map_err(Error::DeserializeMessage)?;
         de.end().map_err(Error::DeserializeMessage)?;
@@ -80,11 +95,78 @@ impl DynamicMessage {
     /// - Failed to convert utf8 to String
     /// - Failed to serialize message
     pub fn to_json(&self) -> Result<String> {
+        self.to_json_with_options(&JsonOptions::default())
+    }
+
+    /// Serialize a `DynamicMessage` to a JSON string with the given options.
+    ///
+    /// # Errors
+    ///
+    /// - Failed to convert utf8 to String
+    /// - Failed to serialize message
+    pub fn to_json_with_options(&self, options: &JsonOptions) -> Result<String> {
         let mut s = serde_json::Serializer::new(Vec::new());
-        self.serialize(&mut s).map_err(Error::SerializeJsonError)?;
+        self.inner
+            .serialize_with_options(&mut s, &options.serialize_options())
+            .map_err(Error::SerializeJsonError)?;
         String::from_utf8(s.into_inner()).map_err(|_| Error::Internal(FROM_UTF8.to_string()))
     }
 
+    /// Serialize a `DynamicMessage` to a pretty printed JSON string that
+    /// is used as a request template. The first field of each oneof is
+    /// preceded by a comment that lists its alternatives.
+    ///
+    /// # Errors
+    ///
+    /// - Failed to serialize message
+    pub fn to_json_template(&self, options: &JsonOptions) -> Result<String> {
+        let value = self
+            .inner
+            .serialize_with_options(serde_json::value::Serializer, &options.serialize_options())
+            .map_err(Error::SerializeJsonError)?;
+        Ok(format_json_template(
+            &value,
+            &self.descriptor(),
+            options.proto_field_names,
+        ))
+    }
+
+    /// Deserialize a `DynamicMessage` from the protobuf text format.
+    ///
+    /// # Errors
+    ///
+    /// - Failed to parse message.
+    pub fn from_text_format(&mut self, text: &str) -> Result<()> {
+        self.inner = DynMessage::parse_text_format(self.descriptor(), text)
+            .map_err(Error::DeserializeTextFormat)?;
+        Ok(())
+    }
+
+    /// Serialize a `DynamicMessage` to a pretty printed string in the
+    /// protobuf text format.
+    #[must_use]
+    pub fn to_text_format(&self) -> String {
+        self.inner.to_text_format_with_options(
+            &FormatOptions::new().pretty(true).skip_default_fields(false),
+        )
+    }
+
+    /// Decode a `DynamicMessage` from the protobuf wire format.
+    ///
+    /// # Errors
+    ///
+    /// - Failed to decode message.
+    pub fn from_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        self.inner = DynMessage::decode(self.descriptor(), bytes).map_err(Error::DecodeMessage)?;
+        Ok(())
+    }
+
+    /// Encode a `DynamicMessage` to the protobuf wire format.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.inner.encode_to_vec()
+    }
+
     /// Apply default values to a `DynamicMessage`.
     pub fn apply_template(&mut self) {
         apply_template_for_message(self, 0);
@@ -96,12 +178,114 @@ impl Serialize for DynamicMessage {
     where
         S: Serializer,
     {
-        self.inner.serialize_with_options(
-            serializer,
-            &SerializeOptions::new()
-                .stringify_64_bit_integers(false)
-                .skip_default_fields(false),
-        )
+        self.inner
+            .serialize_with_options(serializer, &JsonOptions::default().serialize_options())
+    }
+}
+
+/// Returns the name of a field type as written in a proto file,
+/// e.g. `int32` or the full name of a message.
+#[must_use]
+pub fn kind_name(kind: &Kind) -> String {
+    let name = match kind {
+        Kind::Double => "double",
+        Kind::Float => "float",
+        Kind::Int32 => "int32",
+        Kind::Int64 => "int64",
+        Kind::Uint32 => "uint32",
+        Kind::Uint64 => "uint64",
+        Kind::Sint32 => "sint32",
+        Kind::Sint64 => "sint64",
+        Kind::Fixed32 => "fixed32",
+        Kind::Fixed64 => "fixed64",
+        Kind::Sfixed32 => "sfixed32",
+        Kind::Sfixed64 => "sfixed64",
+        Kind::Bool => "bool",
+        Kind::String => "string",
+        Kind::Bytes => "bytes",
+        Kind::Message(desc) => return desc.full_name().to_string(),
+        Kind::Enum(desc) => return desc.full_name().to_string(),
+    };
+    name.to_string()
+}
+
+/// Removes `//` line comments from a JSON string. Comments are replaced
+/// by whitespace so that error positions still match the input.
+#[must_use]
+pub fn strip_json_comments(json: &str) -> Cow<'_, str> {
+    if !json.contains("//") {
+        return Cow::Borrowed(json);
+    }
+    let mut out = String::with_capacity(json.len());
+    let mut chars = json.chars().peekable();
+    let (mut in_string, mut in_comment, mut escaped) = (false, false, false);
+    while let Some(c) = chars.next() {
+        if in_comment {
+            in_comment = c != '\n';
+            out.push(if in_comment { ' ' } else { c });
+            continue;
+        }
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if c == '/' && chars.peek() == Some(&'/') {
+            in_comment = true;
+            out.push(' ');
+            continue;
+        }
+        out.push(c);
+    }
+    Cow::Owned(out)
+}
+
+/// The options of the JSON serialization of messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonOptions {
+    /// Whether fields with default values are omitted
+    pub skip_default_fields: bool,
+    /// Whether enums are serialized as numbers instead of names
+    pub enums_as_numbers: bool,
+    /// Whether the proto field names are used instead of lowerCamelCase
+    pub proto_field_names: bool,
+    /// Whether 64-bit integers are serialized as strings
+    pub stringify_64_bit_integers: bool,
+    /// Whether unknown fields are rejected instead of ignored
+    pub deny_unknown_fields: bool,
+    /// Whether exported grpcurl commands pass `-emit-defaults`
+    pub emit_defaults: bool,
+}
+
+impl Default for JsonOptions {
+    fn default() -> Self {
+        Self {
+            skip_default_fields: false,
+            enums_as_numbers: false,
+            proto_field_names: false,
+            stringify_64_bit_integers: false,
+            deny_unknown_fields: true,
+            emit_defaults: false,
+        }
+    }
+}
+
+impl JsonOptions {
+    fn serialize_options(&self) -> SerializeOptions {
+        SerializeOptions::new()
+            .skip_default_fields(self.skip_default_fields)
+            .use_enum_numbers(self.enums_as_numbers)
+            .use_proto_field_name(self.proto_field_names)
+            .stringify_64_bit_integers(self.stringify_64_bit_integers)
+    }
+
+    fn deserialize_options(&self) -> DeserializeOptions {
+        DeserializeOptions::new().deny_unknown_fields(self.deny_unknown_fields)
     }
 }
 
@@ -153,8 +337,78 @@ mod test {
         assert_eq!(json, expected_json);
     }
 
+    #[test]
+    fn test_template_oneof() {
+        // given
+        let mut given_message = load_test_message_from("test_files/oneof.proto", "Oneof");
+
+        // when
+        given_message.apply_template();
+        let json = given_message.to_json().unwrap();
+        let template = given_message
+            .to_json_template(&JsonOptions::default())
+            .unwrap();
+        let mut parsed = load_test_message_from("test_files/oneof.proto", "Oneof");
+        parsed.from_json(&template).unwrap();
+
+        // then
+        assert_eq!(json, "{\"first\":\"\"}");
+        assert!(template.contains("  // oneof value, alternatives: last\n  \"first\": \"\"\n"));
+        assert_eq!(parsed.to_json().unwrap(), json);
+    }
+
+    #[test]
+    fn test_template_well_known_types() {
+        // given
+        let mut given_message = load_test_message_from("test_files/types.proto", "Types");
+
+        // when
+        given_message.apply_template();
+        let json = given_message.to_json().unwrap();
+
+        // then
+        assert_eq!(
+            json,
+            "{\"counts\":{\"\":0},\"created\":\"1970-01-01T00:00:00Z\"}"
+        );
+    }
+
+    #[test]
+    fn test_template_recursive() {
+        // given
+        let mut given_message = load_test_message_from("test_files/recursive.proto", "Recursive");
+
+        // when
+        given_message.apply_template();
+        let json = given_message.to_json().unwrap();
+
+        // then
+        assert!(json.starts_with("{\"inner\":{\"inner\":"));
+        assert_eq!(json.matches("\"inner\"").count(), 11);
+    }
+
+    #[test]
+    fn test_strip_json_comments() {
+        // given
+        let json = "{\n  // comment\n  \"url\": \"http://a\" // end\n}";
+
+        // when
+        let stripped = strip_json_comments(json);
+
+        // then
+        assert_eq!(stripped.len(), json.len());
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&stripped).unwrap(),
+            serde_json::json!({"url": "http://a"})
+        );
+    }
+
     fn load_test_message(method: &str) -> DynamicMessage {
-        let files = vec!["test_files/test.proto"];
+        load_test_message_from("test_files/test.proto", method)
+    }
+
+    fn load_test_message_from(file: &str, method: &str) -> DynamicMessage {
+        let files = vec![file];
         let includes = vec!["."];
 
         let desc = ProtoDescriptor::new(includes, files).unwrap();
@@ -193,4 +447,66 @@ mod test {
         let expected_json = "{\"id\":\"\",\"number\":1}";
         assert_eq!(json, expected_json);
     }
+
+    #[test]
+    fn test_text_format() {
+        // given
+        let mut given_message = load_test_message("Multiple");
+        given_message
+            .from_text_format("number: 1\nid: \"a\"")
+            .unwrap();
+
+        // when
+        let text = given_message.to_text_format();
+
+        // then
+        let expected_text = "id: \"a\"\nnumber: 1";
+        assert_eq!(text, expected_text);
+        assert!(given_message.from_text_format("number: \"a\"").is_err());
+    }
+
+    #[test]
+    fn test_wire_bytes() {
+        // given
+        let mut given_message = load_test_message("Simple");
+        given_message.from_json("{\"number\":150}").unwrap();
+
+        // when
+        let bytes = given_message.to_bytes();
+        let mut decoded = load_test_message("Simple");
+        decoded.from_bytes(&bytes).unwrap();
+
+        // then
+        assert_eq!(bytes, vec![0x08, 0x96, 0x01]);
+        assert_eq!(decoded.to_json().unwrap(), "{\"number\":150}");
+        assert!(decoded.from_bytes(&[0x08]).is_err());
+    }
+
+    #[test]
+    fn test_json_options() {
+        // given
+        let mut given_message = load_test_message("Enum");
+        given_message.from_json("{\"color\":\"RED\"}").unwrap();
+        let options = JsonOptions {
+            skip_default_fields: true,
+            enums_as_numbers: true,
+            deny_unknown_fields: false,
+            ..JsonOptions::default()
+        };
+
+        // when
+        let json = given_message.to_json_with_options(&options).unwrap();
+        let mut default_message = load_test_message("Enum");
+        let default_json = default_message.to_json_with_options(&options).unwrap();
+        let unknown = "{\"color\":2,\"unknown\":1}";
+
+        // then
+        assert_eq!(json, "{\"color\":1}");
+        assert_eq!(default_json, "{}");
+        assert!(default_message.from_json(unknown).is_err());
+        default_message
+            .from_json_with_options(unknown, &options)
+            .unwrap();
+        assert_eq!(default_message.to_json().unwrap(), "{\"color\":\"BLUE\"}");
+    }
 }
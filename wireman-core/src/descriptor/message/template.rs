@@ -1,4 +1,8 @@
-use prost_reflect::{DynamicMessage, Kind, MessageDescriptor, ReflectMessage, Value};
+use prost_reflect::{
+    DynamicMessage, FieldDescriptor, Kind, MapKey, MessageDescriptor, ReflectMessage, Value,
+};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
 
 /// The default length of vectors used for message templates.
 const TEMPLATE_VEC_LENGTH: usize = 1;
@@ -6,49 +10,248 @@ const TEMPLATE_VEC_LENGTH: usize = 1;
 /// The max message depth to avoid stack overflow on recursive protos.
 const MAX_MESSAGE_RECURSION: usize = 10;
 
+/// The message type that is packed into `google.protobuf.Any` templates.
+const ANY_TEMPLATE_TYPE: &str = "google.protobuf.Empty";
+
 /// Applies default values to fields of a `DynamicMessage`.
 ///
 /// This function iterates over each field of the message and sets default values
-/// based on the field's data type.
+/// based on the field's data type. Of the fields of a oneof only the first one is
+/// set, and well-known types are given a value that matches their JSON form.
 pub(super) fn apply_template_for_message(msg: &mut DynamicMessage, recursion_depth: usize) {
     if recursion_depth > MAX_MESSAGE_RECURSION {
         return;
     }
+    if apply_template_for_well_known_type(msg) {
+        return;
+    }
     for field in msg.descriptor().fields() {
-        let kind = field.kind();
-        let value = msg.get_field_mut(&field);
+        if !is_template_field(&field) {
+            continue;
+        }
+        if let Some(value) = default_value_field(&field, recursion_depth) {
+            msg.set_field(&field, value);
+        }
+    }
+}
 
-        match value {
-            Value::List(_) => *value = default_value_list(&kind, recursion_depth),
-            Value::Message(msg) => {
-                *value = default_value_message(&msg.descriptor(), recursion_depth);
-            }
-            Value::Map(_) => {
-                // println!("TODO: IMPLEMENT MAP!!");
+/// Applies the template of a well-known type such as `google.protobuf.Timestamp`.
+/// Returns false if the message is not a well-known type.
+fn apply_template_for_well_known_type(msg: &mut DynamicMessage) -> bool {
+    let desc = msg.descriptor();
+    match desc.full_name() {
+        "google.protobuf.Value" => {
+            msg.set_field_by_name("null_value", Value::EnumNumber(0));
+        }
+        "google.protobuf.Any" => {
+            if desc
+                .parent_pool()
+                .get_message_by_name(ANY_TEMPLATE_TYPE)
+                .is_some()
+            {
+                let type_url = format!("type.googleapis.com/{ANY_TEMPLATE_TYPE}");
+                msg.set_field_by_name("type_url", Value::String(type_url));
             }
-            _ => *value = default_value(&kind, recursion_depth),
         }
+        "google.protobuf.Timestamp"
+        | "google.protobuf.Duration"
+        | "google.protobuf.Struct"
+        | "google.protobuf.ListValue"
+        | "google.protobuf.FieldMask"
+        | "google.protobuf.Empty"
+        | "google.protobuf.DoubleValue"
+        | "google.protobuf.FloatValue"
+        | "google.protobuf.Int64Value"
+        | "google.protobuf.UInt64Value"
+        | "google.protobuf.Int32Value"
+        | "google.protobuf.UInt32Value"
+        | "google.protobuf.BoolValue"
+        | "google.protobuf.StringValue"
+        | "google.protobuf.BytesValue" => {}
+        _ => return false,
+    }
+    true
+}
+
+/// Whether a field is set in the template. Of the fields of a oneof,
+/// only the first one is set.
+fn is_template_field(field: &FieldDescriptor) -> bool {
+    match field.containing_oneof() {
+        Some(oneof) if !oneof.is_synthetic() => oneof
+            .fields()
+            .next()
+            .is_some_and(|first| first.number() == field.number()),
+        _ => true,
+    }
+}
+
+/// Get the default value for a field, which might be a list or a map.
+fn default_value_field(field: &FieldDescriptor, recursion_depth: usize) -> Option<Value> {
+    let kind = field.kind();
+    if field.is_map() {
+        return default_value_map(&kind, recursion_depth);
     }
+    if field.is_list() {
+        return default_value_list(&kind, recursion_depth);
+    }
+    default_value(&kind, recursion_depth)
 }
 
 /// Get the default value for a specific data type (Kind).
-fn default_value(kind: &Kind, recursion_depth: usize) -> Value {
+fn default_value(kind: &Kind, recursion_depth: usize) -> Option<Value> {
     match kind {
-        Kind::String => Value::String(String::new()),
+        Kind::String => Some(Value::String(String::new())),
         Kind::Message(desc) => default_value_message(desc, recursion_depth),
-        _ => Value::default_value(kind),
+        _ => Some(Value::default_value(kind)),
     }
 }
 
 /// Get the default value for a list of a specific data type (Kind).
-fn default_value_list(kind: &Kind, recursion_depth: usize) -> Value {
-    let default = default_value(kind, recursion_depth);
-    Value::List(vec![default; TEMPLATE_VEC_LENGTH])
+fn default_value_list(kind: &Kind, recursion_depth: usize) -> Option<Value> {
+    let default = default_value(kind, recursion_depth)?;
+    Some(Value::List(vec![default; TEMPLATE_VEC_LENGTH]))
+}
+
+/// Get the default value for a map with a single entry. The kind is
+/// the kind of the map entry message.
+fn default_value_map(kind: &Kind, recursion_depth: usize) -> Option<Value> {
+    let entry = kind.as_message()?;
+    let key = MapKey::default_value(&entry.map_entry_key_field().kind());
+    let value = default_value(&entry.map_entry_value_field().kind(), recursion_depth)?;
+    Some(Value::Map(HashMap::from([(key, value)])))
 }
 
-/// Get the default value for a message.
-fn default_value_message(desc: &MessageDescriptor, recursion_depth: usize) -> Value {
+/// Get the default value for a message. Returns `None` for a
+/// `google.protobuf.Any` that has no message type to pack.
+fn default_value_message(desc: &MessageDescriptor, recursion_depth: usize) -> Option<Value> {
+    if desc.full_name() == "google.protobuf.Any"
+        && desc
+            .parent_pool()
+            .get_message_by_name(ANY_TEMPLATE_TYPE)
+            .is_none()
+    {
+        return None;
+    }
     let mut msg = DynamicMessage::new(desc.clone());
     apply_template_for_message(&mut msg, recursion_depth + 1);
-    Value::Message(msg)
+    Some(Value::Message(msg))
+}
+
+/// Pretty formats the JSON of a message template. The first field of
+/// each oneof is preceded by a comment that lists its alternatives.
+pub(super) fn format_json_template(
+    value: &JsonValue,
+    desc: &MessageDescriptor,
+    proto_field_names: bool,
+) -> String {
+    let mut out = String::new();
+    write_json_template(&mut out, value, Some(desc), proto_field_names, 0);
+    out
+}
+
+fn write_json_template(
+    out: &mut String,
+    value: &JsonValue,
+    desc: Option<&MessageDescriptor>,
+    proto_field_names: bool,
+    indent: usize,
+) {
+    match value {
+        JsonValue::Object(map) if !map.is_empty() => {
+            out.push_str("{\n");
+            for (index, (key, value)) in map.iter().enumerate() {
+                let field = desc.and_then(|desc| {
+                    if proto_field_names {
+                        desc.get_field_by_name(key)
+                    } else {
+                        desc.get_field_by_json_name(key)
+                    }
+                });
+                if let Some(comment) = field
+                    .as_ref()
+                    .and_then(|field| oneof_comment(field, proto_field_names))
+                {
+                    push_indent(out, indent + 1);
+                    out.push_str(&comment);
+                    out.push('\n');
+                }
+                push_indent(out, indent + 1);
+                out.push_str(&JsonValue::String(key.clone()).to_string());
+                out.push_str(": ");
+                let field_desc = field.as_ref().and_then(template_message_kind);
+                write_json_template(
+                    out,
+                    value,
+                    field_desc.as_ref(),
+                    proto_field_names,
+                    indent + 1,
+                );
+                if index + 1 < map.len() {
+                    out.push(',');
+                }
+                out.push('\n');
+            }
+            push_indent(out, indent);
+            out.push('}');
+        }
+        JsonValue::Array(items) if !items.is_empty() => {
+            out.push_str("[\n");
+            for (index, item) in items.iter().enumerate() {
+                push_indent(out, indent + 1);
+                write_json_template(out, item, desc, proto_field_names, indent + 1);
+                if index + 1 < items.len() {
+                    out.push(',');
+                }
+                out.push('\n');
+            }
+            push_indent(out, indent);
+            out.push(']');
+        }
+        _ => out.push_str(&value.to_string()),
+    }
+}
+
+fn push_indent(out: &mut String, indent: usize) {
+    out.push_str(&"  ".repeat(indent));
+}
+
+/// Returns the message type of a field whose JSON form is an object with
+/// the message's fields. Maps and well-known types are skipped.
+fn template_message_kind(field: &FieldDescriptor) -> Option<MessageDescriptor> {
+    if field.is_map() {
+        return None;
+    }
+    let desc = field.kind().as_message()?.clone();
+    if desc.package_name() == "google.protobuf" {
+        return None;
+    }
+    Some(desc)
+}
+
+/// Returns a comment such as `// oneof value, alternatives: last` for
+/// the first field of a oneof.
+fn oneof_comment(field: &FieldDescriptor, proto_field_names: bool) -> Option<String> {
+    let oneof = field.containing_oneof()?;
+    if oneof.is_synthetic() || !is_template_field(field) {
+        return None;
+    }
+    let alternatives: Vec<String> = oneof
+        .fields()
+        .skip(1)
+        .map(|field| {
+            if proto_field_names {
+                field.name().to_string()
+            } else {
+                field.json_name().to_string()
+            }
+        })
+        .collect();
+    if alternatives.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "// oneof {}, alternatives: {}",
+        oneof.name(),
+        alternatives.join(", ")
+    ))
 }
@@ -0,0 +1,264 @@
+use super::{strip_json_comments, DynamicMessage, JsonOptions};
+use crate::error::Error;
+use prost_reflect::MessageDescriptor;
+use std::borrow::Cow;
+use std::fmt;
+
+/// A zero-based line and column, counted in characters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    #[must_use]
+    pub fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+}
+
+/// A problem of a JSON message and the span of the offending text.
+/// The end of the span is exclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonProblem {
+    pub message: String,
+    pub start: Position,
+    pub end: Position,
+}
+
+impl fmt::Display for JsonProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.start.line + 1,
+            self.start.column + 1,
+            self.message
+        )
+    }
+}
+
+/// Validates a JSON message against its descriptor. Returns the error
+/// that [`DynamicMessage::from_json_with_options`] reports, located at the
+/// offending token, or nothing if the message is valid.
+///
+/// `{{var}}` placeholders outside of strings are validated as `null`,
+/// since they are only substituted when the request is sent.
+#[must_use]
+pub fn validate_json(
+    desc: &MessageDescriptor,
+    json: &str,
+    options: &JsonOptions,
+) -> Vec<JsonProblem> {
+    let json = strip_json_comments(json);
+    let json = mask_placeholders(&json);
+    match DynamicMessage::new(desc.clone()).from_json_with_options(&json, options) {
+        Err(Error::DeserializeMessage(err)) => vec![problem(&json, &err)],
+        _ => Vec::new(),
+    }
+}
+
+/// Replaces each `{{var}}` outside of a string with `null`, padded with
+/// spaces so that the positions of the remaining text are kept.
+fn mask_placeholders(json: &str) -> Cow<'_, str> {
+    if !json.contains("{{") {
+        return Cow::Borrowed(json);
+    }
+    let chars: Vec<char> = json.chars().collect();
+    let mut out = String::with_capacity(json.len());
+    let (mut index, mut in_string, mut escaped) = (0, false, false);
+    while let Some(&c) = chars.get(index) {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if chars[index..].starts_with(&['{', '{']) {
+            let len = chars[index..]
+                .windows(2)
+                .take_while(|pair| pair[0] != '\n')
+                .position(|pair| pair == ['}', '}'])
+                .map(|end| end + 2);
+            if let Some(len) = len {
+                out.push_str(&format!("{:len$}", "null"));
+                index += len;
+                continue;
+            }
+        }
+        out.push(c);
+        index += 1;
+    }
+    Cow::Owned(out)
+}
+
+/// Locates a deserialization error. serde points at the last character
+/// of the offending token, the problem spans the whole token.
+fn problem(json: &str, err: &serde_json::Error) -> JsonProblem {
+    let chars: Vec<char> = json.chars().collect();
+    let end = error_index(json, err).min(chars.len().saturating_sub(1));
+    let start = if err.is_data() {
+        token_start(&chars, end)
+    } else {
+        end
+    };
+    let message = err.to_string();
+    let message = match message.rsplit_once(" at line ") {
+        Some((message, _)) => message.to_string(),
+        None => message,
+    };
+    let end = position(&chars, end);
+    JsonProblem {
+        message,
+        start: position(&chars, start),
+        end: Position::new(end.line, end.column + 1),
+    }
+}
+
+/// The character index of a deserialization error. serde counts lines
+/// from one and columns in bytes.
+fn error_index(json: &str, err: &serde_json::Error) -> usize {
+    let line = err.line().saturating_sub(1);
+    let column = err.column().saturating_sub(1);
+    let mut index = 0;
+    for (number, text) in json.split('\n').enumerate() {
+        if number == line {
+            return index + text.char_indices().take_while(|(i, _)| *i < column).count();
+        }
+        index += text.chars().count() + 1;
+    }
+    index
+}
+
+/// The start of the token that ends at `end`: a string, a number or
+/// literal, or an object or array.
+fn token_start(chars: &[char], end: usize) -> usize {
+    let is_word = |c: &char| c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.');
+    match chars.get(end) {
+        Some('"') => (0..end)
+            .rev()
+            .find(|&index| chars[index] == '"' && !is_escaped(chars, index))
+            .unwrap_or(end),
+        Some('}' | ']') => matching_bracket(chars, end),
+        Some(c) if is_word(c) => {
+            let len = chars[..end].iter().rev().take_while(|c| is_word(c)).count();
+            end - len
+        }
+        _ => end,
+    }
+}
+
+/// The index of the bracket that opens the object or array closed at `end`.
+fn matching_bracket(chars: &[char], end: usize) -> usize {
+    let (mut depth, mut in_string) = (0, false);
+    for index in (0..=end).rev() {
+        match chars[index] {
+            '"' if !is_escaped(chars, index) => in_string = !in_string,
+            '}' | ']' if !in_string => depth += 1,
+            '{' | '[' if !in_string => {
+                depth -= 1;
+                if depth == 0 {
+                    return index;
+                }
+            }
+            _ => {}
+        }
+    }
+    end
+}
+
+/// Whether the character at `index` is preceded by an odd number of
+/// backslashes.
+fn is_escaped(chars: &[char], index: usize) -> bool {
+    chars[..index]
+        .iter()
+        .rev()
+        .take_while(|c| **c == '\\')
+        .count()
+        % 2
+        == 1
+}
+
+/// The line and column of a character index.
+fn position(chars: &[char], index: usize) -> Position {
+    let before = &chars[..index.min(chars.len())];
+    let line = before.iter().filter(|c| **c == '\n').count();
+    let column = before.iter().rev().take_while(|c| **c != '\n').count();
+    Position::new(line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProtoDescriptor;
+
+    fn load_input(file: &str, method: &str) -> MessageDescriptor {
+        let desc = ProtoDescriptor::new(["."], [file]).unwrap();
+        desc.get_method_by_name("proto.TestService", method)
+            .unwrap()
+            .input()
+    }
+
+    fn messages(problems: &[JsonProblem]) -> Vec<String> {
+        problems.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_validate_json() {
+        // given
+        let nested = load_input("test_files/test.proto", "Nested");
+        let enums = load_input("test_files/test.proto", "Enum");
+        let oneof = load_input("test_files/oneof.proto", "Oneof");
+        let types = load_input("test_files/types.proto", "Types");
+        let options = JsonOptions::default();
+
+        // when
+        let valid = validate_json(&nested, "{\"items\": [{\"number\": \"1000\"}]}", &options);
+        let exponent = validate_json(&nested, "{\"items\": [{\"number\": \"1e3\"}]}", &options);
+        let invalid = validate_json(
+            &nested,
+            "{\n  \"items\": [{\"number\": 3000000000, \"text\": 1}],\n  \"foo\": 1\n}",
+            &options,
+        );
+        let syntax = validate_json(&nested, "{\"items\": [}", &options);
+        let enum_name = validate_json(&enums, "{\"color\": \"GREEN\"}", &options);
+        let oneof = validate_json(&oneof, "{\"first\": \"\", \"last\": \"\"}", &options);
+        let wkt = validate_json(&types, "{\"created\": \"yesterday\"}", &options);
+        let placeholder = validate_json(
+            &nested,
+            "{\"items\": [{\"number\": {{n}}, \"text\": \"{{text}}\"}]}",
+            &options,
+        );
+
+        // then
+        assert!(valid.is_empty());
+        assert_eq!(
+            messages(&exponent),
+            vec!["line 1, column 23: invalid digit found in string"]
+        );
+        assert_eq!(
+            messages(&invalid),
+            vec!["line 2, column 24: out of range integral type conversion attempted"]
+        );
+        assert_eq!(invalid[0].end, Position::new(1, 33));
+        assert_eq!(messages(&syntax), vec!["line 1, column 12: expected value"]);
+        assert_eq!(
+            messages(&enum_name),
+            vec!["line 1, column 11: unrecognized enum value 'GREEN'"]
+        );
+        assert_eq!(
+            messages(&oneof),
+            vec!["line 1, column 1: multiple fields provided for oneof 'value'"]
+        );
+        assert_eq!(
+            messages(&wkt),
+            vec!["line 1, column 13: invalid rfc3339 timestamp: invalid date"]
+        );
+        assert!(placeholder.is_empty());
+    }
+}
@@ -1,9 +1,26 @@
 use std::ops::{Deref, DerefMut};
 
+use base64::{
+    alphabet,
+    engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+    Engine,
+};
 use serde::{ser::SerializeMap, Serialize, Serializer};
-use tonic::metadata::{Ascii, KeyRef, MetadataKey, MetadataMap, MetadataValue};
+use tonic::metadata::{Ascii, Binary, KeyAndValueRef, MetadataKey, MetadataMap, MetadataValue};
 
-use crate::error::Error;
+use crate::error::{Error, Result};
+
+/// The suffix of binary metadata keys.
+const BINARY_SUFFIX: &str = "-bin";
+
+/// The prefix of hex encoded binary values.
+const HEX_PREFIX: &str = "hex:";
+
+/// Decodes base64 with or without padding.
+const BASE64_CONFIG: GeneralPurposeConfig =
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent);
+const BASE64: GeneralPurpose = GeneralPurpose::new(&alphabet::STANDARD, BASE64_CONFIG);
+const BASE64_URL_SAFE: GeneralPurpose = GeneralPurpose::new(&alphabet::URL_SAFE, BASE64_CONFIG);
 
 /// Represents `gRPC` metadata, which contains key-value pairs. Metadata is commonly used to
 /// provide additional information with `gRPC` requests and responses.
@@ -33,32 +50,81 @@ impl Metadata {
     /// # Errors
     ///
     /// Returns an error if parsing the key or value to ASCII fails.
-    pub fn insert_ascii(&mut self, key: &str, val: &str) -> crate::error::Result<()> {
+    pub fn insert_ascii(&mut self, key: &str, val: &str) -> Result<()> {
         let key: MetadataKey<Ascii> = key.parse().map_err(|_| Error::ParseToAsciiError)?;
         let val: MetadataValue<Ascii> = val.parse().map_err(|_| Error::ParseToAsciiError)?;
         self.insert(key, val);
         Ok(())
     }
 
+    /// Insert a key-value pair into the metadata. The values of binary
+    /// keys, which end in `-bin`, are decoded from base64 or, if prefixed
+    /// with `hex:`, from hex.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is invalid or the value cannot be
+    /// decoded.
+    pub fn insert_str(&mut self, key: &str, val: &str) -> Result<()> {
+        if !is_binary_key(key) {
+            return self.insert_ascii(key, val);
+        }
+        let key: MetadataKey<Binary> = key.parse().map_err(|_| Error::ParseToAsciiError)?;
+        let val = MetadataValue::from_bytes(&decode_binary(val)?);
+        self.insert_bin(key, val);
+        Ok(())
+    }
+
+    /// Returns all key-value pairs as strings. Binary values are
+    /// returned in their base64 encoded form.
+    #[must_use]
+    pub fn entries(&self) -> Vec<(String, String)> {
+        self.inner
+            .iter()
+            .map(|entry| match entry {
+                KeyAndValueRef::Ascii(key, val) => (
+                    key.to_string(),
+                    val.to_str().unwrap_or_default().to_string(),
+                ),
+                KeyAndValueRef::Binary(key, val) => (
+                    key.to_string(),
+                    String::from_utf8_lossy(val.as_encoded_bytes()).to_string(),
+                ),
+            })
+            .collect()
+    }
+
+    /// Returns all key-value pairs as strings for display. Binary values
+    /// are decoded and shown as text if they are printable, otherwise
+    /// they are shown as `hex:` prefixed hex.
+    #[must_use]
+    pub fn decoded_entries(&self) -> Vec<(String, String)> {
+        self.inner
+            .iter()
+            .map(|entry| match entry {
+                KeyAndValueRef::Ascii(key, val) => (
+                    key.to_string(),
+                    val.to_str().unwrap_or_default().to_string(),
+                ),
+                KeyAndValueRef::Binary(key, val) => {
+                    let bytes = val.to_bytes().unwrap_or_default();
+                    (key.to_string(), format_binary(&bytes))
+                }
+            })
+            .collect()
+    }
+
     /// Serialize the metadata to a format compatible with the given serializer.
+    /// Binary values are serialized in their base64 encoded form.
     ///
     /// # Errors
     ///
     /// Returns an error if serializing the metadata map fails
-    pub fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
-        let len = self.inner.len();
-        let mut map = ser.serialize_map(Some(len))?;
-        for key in self.inner.keys() {
-            if let KeyRef::Ascii(ascii_key) = key {
-                let Some(value) = self.inner.get(ascii_key) else {
-                    continue;
-                };
-                let key_str = ascii_key.to_string();
-                let Ok(value_str) = value.to_str() else {
-                    continue;
-                };
-                map.serialize_entry(&key_str, value_str)?;
-            }
+    pub fn serialize<S: Serializer>(&self, ser: S) -> std::result::Result<S::Ok, S::Error> {
+        let entries = self.entries();
+        let mut map = ser.serialize_map(Some(entries.len()))?;
+        for (key, value) in &entries {
+            map.serialize_entry(key, value)?;
         }
         map.end()
     }
@@ -69,17 +135,68 @@ impl Serialize for Metadata {
     where
         S: Serializer,
     {
-        let len = self.inner.len();
-        let mut map = serializer.serialize_map(Some(len))?;
-        for key in self.inner.keys() {
-            if let KeyRef::Ascii(ascii_key) = key {
-                let value = self.inner.get(ascii_key).unwrap();
-                let key_str = ascii_key.to_string();
-                let value_str = value.to_str().unwrap();
-                map.serialize_entry(&key_str, value_str)?;
-            }
+        Metadata::serialize(self, serializer)
+    }
+}
+
+/// Returns whether the key is a binary metadata key.
+#[must_use]
+pub fn is_binary_key(key: &str) -> bool {
+    key.to_ascii_lowercase().ends_with(BINARY_SUFFIX)
+}
+
+/// Decodes the value of a binary metadata key. The value is hex if it
+/// is prefixed with `hex:`, otherwise it is standard or url-safe base64
+/// with optional padding.
+///
+/// # Errors
+///
+/// Returns an error if the value cannot be decoded.
+pub fn decode_binary(val: &str) -> Result<Vec<u8>> {
+    let val = val.trim();
+    if let Some(hex) = val.strip_prefix(HEX_PREFIX) {
+        return decode_hex(hex);
+    }
+    BASE64
+        .decode(val)
+        .or_else(|_| BASE64_URL_SAFE.decode(val))
+        .map_err(|err| Error::ParseBinaryError(err.to_string()))
+}
+
+/// Formats a binary value as text if it is printable, otherwise as
+/// `hex:` prefixed hex that can be decoded by [`decode_binary`].
+#[must_use]
+pub fn format_binary(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) if !text.chars().any(char::is_control) => text.to_string(),
+        _ => {
+            let hex: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+            format!("{HEX_PREFIX}{hex}")
         }
-        map.end()
+    }
+}
+
+fn decode_hex(hex: &str) -> Result<Vec<u8>> {
+    let hex: Vec<u8> = hex.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    let pairs = hex.chunks_exact(2);
+    if !pairs.remainder().is_empty() {
+        return Err(Error::ParseBinaryError(String::from(
+            "hex value has an odd length",
+        )));
+    }
+    pairs
+        .map(|pair| {
+            std::str::from_utf8(pair)
+                .ok()
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or_else(|| Error::ParseBinaryError(String::from("invalid hex value")))
+        })
+        .collect()
+}
+
+impl From<MetadataMap> for Metadata {
+    fn from(inner: MetadataMap) -> Self {
+        Self { inner }
     }
 }
 
@@ -119,4 +236,66 @@ mod test {
         let expected_json = "{\"auth\":\"bearer\",\"alias\":\"xx\"}";
         assert_eq!(json, expected_json);
     }
+
+    #[test]
+    fn test_entries() {
+        // given
+        let mut metadata = Metadata::new();
+        metadata.insert_ascii("x-request-id", "abc").unwrap();
+        metadata.insert_bin("trace-bin", MetadataValue::from_bytes(b"hi"));
+
+        // when
+        let entries = metadata.entries();
+
+        // then
+        assert_eq!(
+            entries,
+            vec![
+                ("x-request-id".to_string(), "abc".to_string()),
+                ("trace-bin".to_string(), "aGk".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_insert_binary() {
+        // given
+        let mut metadata = Metadata::new();
+
+        // when
+        metadata.insert_str("x-request-id", "abc").unwrap();
+        metadata.insert_str("trace-bin", "aGk=").unwrap();
+        metadata.insert_str("url-bin", "_-8").unwrap();
+        metadata.insert_str("raw-bin", "hex:00ff").unwrap();
+
+        // then
+        let bytes = |key: &str| metadata.get_bin(key).unwrap().to_bytes().unwrap();
+        assert_eq!(bytes("trace-bin").as_ref(), b"hi");
+        assert_eq!(bytes("url-bin").as_ref(), [0xff, 0xef]);
+        assert_eq!(bytes("raw-bin").as_ref(), [0x00, 0xff]);
+        assert_eq!(
+            metadata.decoded_entries(),
+            vec![
+                ("x-request-id".to_string(), "abc".to_string()),
+                ("trace-bin".to_string(), "hi".to_string()),
+                ("url-bin".to_string(), "hex:ffef".to_string()),
+                ("raw-bin".to_string(), "hex:00ff".to_string()),
+            ]
+        );
+        assert!(metadata.insert_str("trace-bin", "hex:0").is_err());
+        assert!(metadata.insert_str("trace-bin", "not base64!").is_err());
+    }
+
+    #[test]
+    fn test_serialize_binary() {
+        // given
+        let mut metadata = Metadata::new();
+        metadata.insert_str("trace-bin", "aGk=").unwrap();
+
+        // when
+        let json = serde_json::to_string(&metadata).unwrap();
+
+        // then
+        assert_eq!(json, "{\"trace-bin\":\"aGk\"}");
+    }
 }
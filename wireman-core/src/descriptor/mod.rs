@@ -1,11 +1,18 @@
 #![allow(clippy::module_name_repetitions)]
+pub mod docs;
 pub mod message;
 pub mod metadata;
 pub mod reflection_request;
 pub mod request;
 pub mod response;
+pub mod status;
+pub mod wire;
 
-pub use message::DynamicMessage;
+pub use docs::Documented;
+pub use message::{
+    kind_name, strip_json_comments, validate_json, DynamicMessage, JsonOptions, JsonProblem,
+    Position,
+};
 pub use reflection_request::ReflectionRequest;
 pub use request::RequestMessage;
 pub use response::ResponseMessage;
@@ -37,6 +44,35 @@ impl ProtoDescriptor {
         Ok(Self { pool })
     }
 
+    /// Merges precompiled `FileDescriptorSet` files, as produced by
+    /// `protoc --descriptor_set_out` or `buf build -o`, into the pool.
+    /// Files that are already in the pool are skipped.
+    ///
+    /// # Errors
+    /// - Failed to read a protoset `ReadProtosetError`
+    /// - Failed to decode a protoset `DescriptorError`
+    pub fn add_protosets(
+        &mut self,
+        protosets: impl IntoIterator<Item = impl AsRef<Path>>,
+    ) -> Result<()> {
+        for protoset in protosets {
+            let path = protoset.as_ref();
+            let bytes = std::fs::read(path)
+                .map_err(|err| Error::ReadProtosetError(path.display().to_string(), err))?;
+            self.pool
+                .decode_file_descriptor_set(bytes.as_slice())
+                .map_err(Error::DescriptorError)?;
+        }
+        Ok(())
+    }
+
+    /// Encodes the pool as a `FileDescriptorSet`, e.g. to cache it as a
+    /// protoset that can be loaded with [`ProtoDescriptor::add_protosets`].
+    #[must_use]
+    pub fn encode_file_descriptor_set(&self) -> Vec<u8> {
+        self.pool.encode_to_vec()
+    }
+
     /// Instantiates a `DescriptorPool` from a grpc server that supports
     /// reflection.
     ///
@@ -114,3 +150,49 @@ impl ProtoDescriptor {
         ResponseMessage::new(self.get_response_descriptor(method), method.clone())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::temp_path;
+    use prost::Message;
+
+    #[test]
+    fn test_add_protosets() {
+        // given
+        let file_desc_set = protox::compile(["test_files/test.proto"], ["."]).unwrap();
+        let (_guard, path) = temp_path("test.protoset");
+        std::fs::write(&path, file_desc_set.encode_to_vec()).unwrap();
+        let mut desc = ProtoDescriptor::default();
+
+        // when
+        desc.add_protosets([&path]).unwrap();
+
+        // then
+        assert!(desc
+            .get_method_by_name("proto.TestService", "Simple")
+            .is_some());
+        assert!(matches!(
+            desc.add_protosets(["does/not/exist.protoset"]),
+            Err(Error::ReadProtosetError(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_encode_file_descriptor_set() {
+        // given
+        let desc = ProtoDescriptor::new(["."], ["test_files/test.proto"]).unwrap();
+        let (_guard, path) = temp_path("encoded.protoset");
+
+        // when
+        std::fs::write(&path, desc.encode_file_descriptor_set()).unwrap();
+        let mut decoded = ProtoDescriptor::default();
+        decoded.add_protosets([&path]).unwrap();
+
+        // then
+        assert_eq!(decoded.get_services().len(), desc.get_services().len());
+        assert!(decoded
+            .get_method_by_name("proto.TestService", "Simple")
+            .is_some());
+    }
+}
@@ -1,6 +1,6 @@
 use super::metadata::Metadata;
-use crate::error::{Error, Result};
-use tonic::metadata::{Ascii, MetadataKey, MetadataValue};
+use crate::client::tls::TlsConfig;
+use crate::error::Result;
 
 /// Holds all the necessary data for a reflection request.
 #[derive(Debug, Clone)]
@@ -9,6 +9,8 @@ pub struct ReflectionRequest {
     pub host: String,
     /// The requests metadata.
     pub metadata: Option<Metadata>,
+    /// The TLS config. Plain connections are used if `None`.
+    pub tls: Option<TlsConfig>,
 }
 
 impl ReflectionRequest {
@@ -18,19 +20,27 @@ impl ReflectionRequest {
         Self {
             host: host.to_string(),
             metadata: None,
+            tls: None,
         }
     }
 
-    /// Insert metadata into the reflection request.
+    /// Sets the TLS config of the reflection request.
+    #[must_use]
+    pub fn with_tls(mut self, tls: Option<TlsConfig>) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    /// Insert metadata into the reflection request. The values of binary keys,
+    /// which end in `-bin`, are decoded from base64 or `hex:` prefixed hex.
     ///
     /// # Errors
     ///
     /// - Failed to parse metadata value/key to ascii
+    /// - Failed to decode a binary metadata value
     pub fn insert_metadata(&mut self, key: &str, val: &str) -> Result<()> {
-        let key: MetadataKey<Ascii> = key.parse().map_err(|_| Error::ParseToAsciiError)?;
-        let val: MetadataValue<Ascii> = val.parse().map_err(|_| Error::ParseToAsciiError)?;
-        let map = self.metadata.get_or_insert(Metadata::new());
-        map.insert(key, val);
-        Ok(())
+        self.metadata
+            .get_or_insert(Metadata::new())
+            .insert_str(key, val)
     }
 }
@@ -1,5 +1,5 @@
 use super::{metadata::Metadata, DynamicMessage};
-use crate::client::codec::DynamicCodec;
+use crate::client::{address::Address, codec::DynamicCodec, compression::Compression};
 use crate::{
     error::{Error, FROM_UTF8},
     Result,
@@ -8,10 +8,8 @@ use http::{uri::PathAndQuery, Uri};
 use prost_reflect::{MessageDescriptor, MethodDescriptor};
 use serde::{ser::SerializeStruct, Serialize, Serializer};
 use std::str::FromStr;
-use tonic::{
-    metadata::{Ascii, MetadataKey, MetadataValue},
-    Request,
-};
+use std::time::Duration;
+use tonic::Request;
 
 /// Holds all the necessary data for a `gRPC` request, including
 /// the message, method descriptor, and optional metadata.
@@ -25,6 +23,10 @@ pub struct RequestMessage {
     metadata: Option<Metadata>,
     /// The host address.
     address: String,
+    /// The deadline of the request.
+    timeout: Option<Duration>,
+    /// The compression of the request and response messages.
+    compression: Compression,
 }
 
 impl RequestMessage {
@@ -38,6 +40,8 @@ impl RequestMessage {
             method_desc,
             metadata: None,
             address: String::new(),
+            timeout: None,
+            compression: Compression::default(),
         }
     }
 
@@ -82,13 +86,21 @@ impl RequestMessage {
         &self.address
     }
 
-    /// Get the host address as uri.
+    /// Get the host address as uri. Requests over a unix domain socket
+    /// are sent to `http://localhost`.
     ///
     /// # Errors
     /// - Failed to parse address to uri.
     pub fn uri(&self) -> Result<Uri> {
-        Uri::try_from(self.address())
-            .map_err(|_| Error::Internal(String::from("Failed to parse address")))
+        Ok(self.target()?.uri())
+    }
+
+    /// Get the parsed host address.
+    ///
+    /// # Errors
+    /// - Failed to parse the address.
+    pub fn target(&self) -> Result<Address> {
+        Address::parse(self.address())
     }
 
     /// Sets the host address.
@@ -96,23 +108,46 @@ impl RequestMessage {
         self.address = address.to_string();
     }
 
+    /// Get the deadline of the request.
+    #[must_use]
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Sets the deadline of the request. It is sent to the server
+    /// as `grpc-timeout` header.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// Get the compression settings.
+    #[must_use]
+    pub fn compression(&self) -> &Compression {
+        &self.compression
+    }
+
+    /// Sets the compression settings.
+    pub fn set_compression(&mut self, compression: Compression) {
+        self.compression = compression;
+    }
+
     /// Get the metadata associated with the request.
     #[must_use]
     pub fn metadata(&self) -> &Option<Metadata> {
         &self.metadata
     }
 
-    /// Insert metadata into the request.
+    /// Insert metadata into the request. The values of binary keys,
+    /// which end in `-bin`, are decoded from base64 or `hex:` prefixed hex.
     ///
     /// # Errors
     ///
     /// - Failed to parse metadata value/key to ascii
+    /// - Failed to decode a binary metadata value
     pub fn insert_metadata(&mut self, key: &str, val: &str) -> Result<()> {
-        let key: MetadataKey<Ascii> = key.parse().map_err(|_| Error::ParseToAsciiError)?;
-        let val: MetadataValue<Ascii> = val.parse().map_err(|_| Error::ParseToAsciiError)?;
-        let map = self.metadata.get_or_insert(Metadata::new());
-        map.insert(key, val);
-        Ok(())
+        self.metadata
+            .get_or_insert(Metadata::new())
+            .insert_str(key, val)
     }
 
     /// Get the URI path for `gRPC` calls based on the method descriptor.
@@ -152,10 +187,14 @@ impl RequestMessage {
 impl From<RequestMessage> for Request<RequestMessage> {
     fn from(value: RequestMessage) -> Self {
         let metadata = value.metadata().clone();
+        let timeout = value.timeout();
         let mut req = Request::new(value);
         if let Some(meta) = metadata {
             *req.metadata_mut() = meta.inner;
         }
+        if let Some(timeout) = timeout {
+            req.set_timeout(timeout);
+        }
         req
     }
 }
@@ -218,6 +257,20 @@ mod test {
         assert_eq!(given_req.get_ref().message_descriptor(), message_descriptor);
     }
 
+    #[test]
+    fn test_into_request_with_timeout() {
+        // given
+        let mut given_message = load_test_message("Simple");
+        given_message.set_timeout(Some(Duration::from_secs(5)));
+
+        // when
+        let given_req: Request<RequestMessage> = given_message.into();
+
+        // then
+        let metadata = given_req.metadata();
+        assert_eq!(metadata.get("grpc-timeout").unwrap(), "5000000u");
+    }
+
     #[test]
     fn test_to_json() {
         // given
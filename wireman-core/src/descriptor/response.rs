@@ -1,13 +1,20 @@
 use std::ops::{Deref, DerefMut};
 
+use super::metadata::Metadata;
 use super::DynamicMessage;
+use crate::client::metrics::{CallMetrics, MetricsRecorder};
+use crate::Result;
 use prost_reflect::{MessageDescriptor, MethodDescriptor};
+use tonic::metadata::MetadataMap;
 
 /// Holds all the necessary data for a `gRPC` response.
 #[derive(Debug, Clone)]
 pub struct ResponseMessage {
     pub message: DynamicMessage,
     method_desc: MethodDescriptor,
+    /// The headers, trailers and timing of the call. Only set on
+    /// the final response of a unary or client-streaming call.
+    pub details: Option<ResponseDetails>,
 }
 
 impl ResponseMessage {
@@ -18,6 +25,7 @@ impl ResponseMessage {
         Self {
             message,
             method_desc,
+            details: None,
         }
     }
 
@@ -27,14 +35,83 @@ impl ResponseMessage {
     }
 }
 
+/// The metadata sent by the server alongside the response messages.
+#[derive(Debug, Clone, Default)]
+pub struct ResponseDetails {
+    /// The initial metadata, sent before the first message.
+    pub headers: Metadata,
+    /// The trailing metadata, sent after the last message.
+    pub trailers: Metadata,
+    /// The timing and payload sizes of the call.
+    pub metrics: CallMetrics,
+    /// The encoding of the response messages, as sent in the
+    /// `grpc-encoding` header. `None` if uncompressed.
+    pub encoding: Option<String>,
+}
+
+impl ResponseDetails {
+    /// Create a new `ResponseDetails` from the raw header and trailer maps.
+    #[must_use]
+    pub fn new(headers: MetadataMap, trailers: MetadataMap, metrics: CallMetrics) -> Self {
+        let encoding = headers
+            .get("grpc-encoding")
+            .and_then(|encoding| encoding.to_str().ok())
+            .filter(|encoding| *encoding != "identity")
+            .map(String::from);
+        Self {
+            encoding,
+            headers: headers.into(),
+            trailers: trailers.into(),
+            metrics,
+        }
+    }
+}
+
 /// Streaming requests and responses.
 pub struct StreamingResponse {
     pub inner: tonic::Streaming<ResponseMessage>,
+    headers: MetadataMap,
+    metrics: MetricsRecorder,
 }
 
 impl StreamingResponse {
-    pub fn new(inner: tonic::Streaming<ResponseMessage>) -> Self {
-        Self { inner }
+    pub fn new(
+        inner: tonic::Streaming<ResponseMessage>,
+        headers: MetadataMap,
+        metrics: MetricsRecorder,
+    ) -> Self {
+        Self {
+            inner,
+            headers,
+            metrics,
+        }
+    }
+
+    /// The timing and payload sizes of the messages received so far.
+    #[must_use]
+    pub fn metrics(&self) -> CallMetrics {
+        self.metrics.snapshot()
+    }
+
+    /// The initial metadata sent by the server.
+    #[must_use]
+    pub fn headers(&self) -> Metadata {
+        self.headers.clone().into()
+    }
+
+    /// Waits for the trailers and returns the details of the call. Should be
+    /// called once all messages have been received.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server closes the stream with a non-ok status.
+    pub async fn details(&mut self) -> Result<ResponseDetails> {
+        let trailers = self.inner.trailers().await?.unwrap_or_default();
+        Ok(ResponseDetails::new(
+            self.headers.clone(),
+            trailers,
+            self.metrics.snapshot(),
+        ))
     }
 }
 
@@ -51,3 +128,31 @@ impl DerefMut for StreamingResponse {
         &mut self.inner
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_response_details_encoding() {
+        // given
+        let mut headers = MetadataMap::new();
+        headers.insert("grpc-encoding", "gzip".parse().unwrap());
+        let mut identity = MetadataMap::new();
+        identity.insert("grpc-encoding", "identity".parse().unwrap());
+
+        // when
+        let gzip = ResponseDetails::new(headers, MetadataMap::new(), CallMetrics::default());
+        let identity = ResponseDetails::new(identity, MetadataMap::new(), CallMetrics::default());
+        let none = ResponseDetails::new(
+            MetadataMap::new(),
+            MetadataMap::new(),
+            CallMetrics::default(),
+        );
+
+        // then
+        assert_eq!(gzip.encoding.as_deref(), Some("gzip"));
+        assert_eq!(identity.encoding, None);
+        assert_eq!(none.encoding, None);
+    }
+}
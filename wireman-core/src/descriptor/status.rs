@@ -0,0 +1,125 @@
+//! Decodes the rich error model of `gRPC`: a `google.rpc.Status` that is
+//! sent in the `grpc-status-details-bin` trailer.
+use crate::error::{Error, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use prost::Message;
+use prost_reflect::{DescriptorPool, DynamicMessage};
+use serde_json::{Map, Value};
+use std::sync::OnceLock;
+
+/// The key of the type url in the json representation of an `Any`.
+const TYPE_KEY: &str = "@type";
+
+/// Decodes the `google.rpc.Status` details of a failed call. Each detail
+/// is returned as the json representation of an `Any`. Detail types are
+/// looked up in the pool and in the standard `google.rpc` error details,
+/// such as `BadRequest`, `ErrorInfo`, `RetryInfo` or `QuotaFailure`.
+/// Details of unknown types keep their base64 encoded value.
+///
+/// # Errors
+///
+/// Errors if the details are not a valid `google.rpc.Status`.
+pub fn decode_status_details(details: &[u8], pool: &DescriptorPool) -> Result<Vec<Value>> {
+    let status = tonic_types::pb::Status::decode(details)
+        .map_err(|err| Error::Internal(format!("invalid status details: {err}")))?;
+    Ok(status
+        .details
+        .iter()
+        .map(|any| decode_any(&any.type_url, &any.value, pool))
+        .collect())
+}
+
+fn decode_any(type_url: &str, value: &[u8], pool: &DescriptorPool) -> Value {
+    let name = type_url.rsplit('/').next().unwrap_or(type_url);
+    let message = pool
+        .get_message_by_name(name)
+        .or_else(|| rpc_pool()?.get_message_by_name(name))
+        .and_then(|desc| DynamicMessage::decode(desc, value).ok())
+        .and_then(|message| serde_json::to_value(message).ok());
+
+    let mut json = Map::new();
+    json.insert(TYPE_KEY.to_string(), Value::from(type_url));
+    match message {
+        Some(Value::Object(fields)) => json.extend(fields),
+        // Well known types, such as wrappers, are not serialized as objects.
+        Some(message) => {
+            json.insert("value".to_string(), message);
+        }
+        None => {
+            json.insert("value".to_string(), Value::from(STANDARD.encode(value)));
+        }
+    }
+    Value::Object(json)
+}
+
+/// The descriptors of the standard `google.rpc` error details.
+fn rpc_pool() -> Option<&'static DescriptorPool> {
+    static POOL: OnceLock<Option<DescriptorPool>> = OnceLock::new();
+    POOL.get_or_init(|| DescriptorPool::decode(tonic_types::pb::FILE_DESCRIPTOR_SET).ok())
+        .as_ref()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ProtoDescriptor;
+    use prost_types::Any;
+    use tonic_types::pb::{bad_request::FieldViolation, BadRequest, Status};
+
+    fn any<M: Message>(type_name: &str, message: &M) -> Any {
+        Any {
+            type_url: format!("type.googleapis.com/{type_name}"),
+            value: message.encode_to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_decode_status_details() {
+        // given
+        let desc = ProtoDescriptor::new(vec!["."], vec!["test_files/test.proto"]).unwrap();
+        let pool = desc.get_message_by_name("proto.SimpleReq").unwrap();
+        let pool = pool.parent_pool();
+        let bad_request = BadRequest {
+            field_violations: vec![FieldViolation {
+                field: "name".to_string(),
+                description: "must not be empty".to_string(),
+                ..Default::default()
+            }],
+        };
+        let mut custom = DynamicMessage::new(pool.get_message_by_name("proto.SimpleReq").unwrap());
+        custom.set_field_by_name("number", prost_reflect::Value::I32(42));
+        let status = Status {
+            code: 3,
+            message: "invalid".to_string(),
+            details: vec![
+                any("google.rpc.BadRequest", &bad_request),
+                any("proto.SimpleReq", &custom),
+                Any {
+                    type_url: "type.googleapis.com/acme.Unknown".to_string(),
+                    value: vec![1, 2],
+                },
+            ],
+        };
+
+        // when
+        let details = decode_status_details(&status.encode_to_vec(), pool).unwrap();
+
+        // then
+        let expected = serde_json::json!([
+            {
+                "@type": "type.googleapis.com/google.rpc.BadRequest",
+                "fieldViolations": [{"field": "name", "description": "must not be empty"}]
+            },
+            {
+                "@type": "type.googleapis.com/proto.SimpleReq",
+                "number": 42
+            },
+            {
+                "@type": "type.googleapis.com/acme.Unknown",
+                "value": "AQI="
+            }
+        ]);
+        assert_eq!(Value::from(details), expected);
+        assert!(decode_status_details(&[0xff], pool).is_err());
+    }
+}
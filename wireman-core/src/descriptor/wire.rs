@@ -0,0 +1,98 @@
+use super::metadata::decode_binary;
+use crate::error::{Error, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+/// The prefix of a file path that holds the raw wire bytes.
+const FILE_PREFIX: &str = "@";
+
+/// The prefix of base64 encoded wire bytes.
+const BASE64_PREFIX: &str = "base64:";
+
+/// Parses the wire bytes of an encoded message. Supported inputs are:
+///
+/// - `@path/to/message.bin`, a file with the raw bytes
+/// - hex, optionally prefixed with `hex:` or `0x`
+/// - standard or url-safe base64, optionally prefixed with `base64:`
+///
+/// Input without a prefix is read as hex if it only consists of hex digits
+/// and contains at least one decimal digit, and as base64 otherwise or if it
+/// is not valid hex. Whitespace, e.g. of wrapped lines, is ignored.
+///
+/// # Errors
+///
+/// Errors if the input cannot be decoded or the file cannot be read.
+pub fn parse_wire_bytes(input: &str) -> Result<Vec<u8>> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(invalid("the input is empty"));
+    }
+    if let Some(path) = input.strip_prefix(FILE_PREFIX) {
+        let path = path.trim();
+        return std::fs::read(path).map_err(|err| invalid(&format!("{path}: {err}")));
+    }
+
+    let input: String = input.split_whitespace().collect();
+    let decoded = if let Some(base64) = input.strip_prefix(BASE64_PREFIX) {
+        decode_binary(base64)
+    } else if let Some(hex) = input.strip_prefix("0x") {
+        decode_binary(&format!("hex:{hex}"))
+    } else if looks_like_hex(&input) {
+        decode_binary(&format!("hex:{input}")).or_else(|err| decode_binary(&input).map_err(|_| err))
+    } else {
+        decode_binary(&input)
+    };
+    decoded.map_err(|err| match err {
+        Error::ParseBinaryError(reason) => invalid(&reason),
+        err => err,
+    })
+}
+
+/// Encodes wire bytes as standard base64 with padding.
+#[must_use]
+pub fn encode_base64(bytes: &[u8]) -> String {
+    STANDARD.encode(bytes)
+}
+
+/// Encodes wire bytes as lowercase hex.
+#[must_use]
+pub fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Whether input without a prefix is guessed to be hex. Base64 that
+/// only uses the letters `A-F` and `a-f`, such as `ABCD`, is not taken
+/// for hex.
+fn looks_like_hex(input: &str) -> bool {
+    input.chars().all(|ch| ch.is_ascii_hexdigit()) && input.chars().any(|ch| ch.is_ascii_digit())
+}
+
+fn invalid(reason: &str) -> Error {
+    Error::InvalidWireBytes(reason.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_wire_bytes() {
+        // given
+        let expected = vec![0x08, 0x96, 0x01];
+
+        for given in ["08 96 01", "hex:089601", "0x089601", "CJYB", "base64:CJYB"] {
+            // when
+            let bytes = parse_wire_bytes(given).unwrap();
+
+            // then
+            assert_eq!(bytes, expected, "{given}");
+        }
+        assert_eq!(parse_wire_bytes("CAE").unwrap(), vec![0x08, 0x01]);
+        assert_eq!(parse_wire_bytes("ABCD").unwrap(), vec![0x00, 0x10, 0x83]);
+        assert_eq!(parse_wire_bytes("0x0801").unwrap(), vec![0x08, 0x01]);
+        assert_eq!(encode_base64(&expected), "CJYB");
+        assert_eq!(encode_hex(&expected), "089601");
+        assert!(parse_wire_bytes("").is_err());
+        assert!(parse_wire_bytes("hex:0896x1").is_err());
+        assert!(parse_wire_bytes("@does/not/exist.bin").is_err());
+    }
+}
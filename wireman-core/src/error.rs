@@ -1,5 +1,7 @@
 #![allow(clippy::module_name_repetitions, clippy::enum_variant_names)]
-use prost_reflect::DescriptorError;
+use crate::descriptor::metadata::Metadata;
+use crate::descriptor::status::decode_status_details;
+use prost_reflect::{text_format::ParseError, DescriptorError, DescriptorPool};
 use thiserror::Error as ThisError;
 
 /// The result type for this library
@@ -16,6 +18,18 @@ pub enum Error {
     #[error("error deserializing message from json")]
     DeserializeMessage(#[source] serde_json::Error),
 
+    /// Failed to parse `DynamicMessage` from the text format
+    #[error("error deserializing message from text format: {0}")]
+    DeserializeTextFormat(#[source] ParseError),
+
+    /// Failed to decode `DynamicMessage` from wire bytes
+    #[error("error decoding message from wire bytes: {0}")]
+    DecodeMessage(#[source] prost::DecodeError),
+
+    /// The wire bytes of a message cannot be parsed
+    #[error("invalid wire bytes: {0}")]
+    InvalidWireBytes(String),
+
     /// Protox failed to compile the proto files
     #[error("error compiling proto files")]
     ProtoxCompileError(#[source] protox::Error),
@@ -24,22 +38,50 @@ pub enum Error {
     #[error("error generating the descriptor pool")]
     DescriptorError(#[source] DescriptorError),
 
+    /// Failed to read a protoset file
+    #[error("failed to read protoset {0}")]
+    ReadProtosetError(String, #[source] std::io::Error),
+
     /// Failed to create a grpc channel
     #[error("error creating grpc channel")]
     GrpcChannelCreateError(#[source] protox::Error),
 
+    /// The server address cannot be parsed
+    #[error("invalid address: {0}")]
+    InvalidAddress(String),
+
+    /// The compression settings cannot be parsed
+    #[error("invalid compression: {0}")]
+    InvalidCompression(String),
+
     /// Grpc channel is not ready
     #[error("grpc channel is not ready: {0}")]
     GrpcNotReady(#[from] tonic::transport::Error),
 
     /// Failed to make a unary grpc call
     #[error("grpc: {0}")]
-    GrpcError(GrpcStatus),
+    GrpcError(Box<GrpcStatus>),
+
+    /// The deadline of a grpc call expired
+    #[error("deadline exceeded: {0}")]
+    DeadlineExceeded(Box<GrpcStatus>),
+
+    /// The transport does not support streaming messages from the client
+    #[error("{0} does not support client streaming")]
+    UnsupportedClientStreaming(String),
 
     /// Failed to load the custom TLS certificate
     #[error("failed to load custom TLS certificate")]
     LoadTLSCertificateError(#[source] std::io::Error),
 
+    /// Failed to load the TLS client certificate or key
+    #[error("failed to load TLS client certificate or key")]
+    LoadTLSIdentityError(#[source] std::io::Error),
+
+    /// Failed to build the TLS config
+    #[error("invalid TLS config: {0}")]
+    TLSConfigError(String),
+
     /// Failed to serialize proto messages
     #[error("failed to serialize proto message")]
     SerializeJsonError(#[source] serde_json::Error),
@@ -51,11 +93,22 @@ pub enum Error {
     /// Failed to parse to ascii
     #[error("error parsing to ascii")]
     ParseToAsciiError,
+
+    /// Failed to decode the value of a binary metadata key
+    #[error("invalid binary metadata value: {0}")]
+    ParseBinaryError(String),
 }
 
 impl From<tonic::Status> for Error {
     fn from(status: tonic::Status) -> Self {
-        Self::GrpcError(status.into())
+        // Tonic cancels the call when the client side deadline expires
+        // before the server responds.
+        let timeout_expired = status.code() == tonic::Code::Cancelled
+            && status.message() == tonic::TimeoutExpired(()).to_string();
+        if status.code() == tonic::Code::DeadlineExceeded || timeout_expired {
+            return Self::DeadlineExceeded(Box::new(status.into()));
+        }
+        Self::GrpcError(Box::new(status.into()))
     }
 }
 
@@ -66,6 +119,10 @@ pub struct GrpcStatus {
     pub code: tonic::Code,
     /// The error message
     pub message: String,
+    /// The binary error details
+    pub details: Vec<u8>,
+    /// The headers and trailers sent along with the status
+    pub metadata: Metadata,
 }
 
 impl std::fmt::Display for GrpcStatus {
@@ -74,11 +131,29 @@ impl std::fmt::Display for GrpcStatus {
     }
 }
 
+impl GrpcStatus {
+    /// Decodes the rich error details sent in `grpc-status-details-bin`.
+    /// Custom detail types are looked up in the pool. Returns an empty
+    /// list if the status has no details.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the details are not a valid `google.rpc.Status`.
+    pub fn decode_details(&self, pool: &DescriptorPool) -> Result<Vec<serde_json::Value>> {
+        if self.details.is_empty() {
+            return Ok(Vec::new());
+        }
+        decode_status_details(&self.details, pool)
+    }
+}
+
 impl From<tonic::Status> for GrpcStatus {
     fn from(status: tonic::Status) -> Self {
         GrpcStatus {
             code: status.code(),
             message: status.message().to_owned(),
+            details: status.details().to_vec(),
+            metadata: status.metadata().clone().into(),
         }
     }
 }
@@ -1,9 +1,15 @@
 use std::collections::HashMap;
 use std::fmt::Write;
 
+use base64::{engine::general_purpose::STANDARD, Engine};
 use http::Uri;
 use prost_reflect::MethodDescriptor;
 
+use crate::descriptor::{
+    metadata::{decode_binary, is_binary_key},
+    JsonOptions,
+};
+
 /// Generate a `grpcurl` command as a string for sending a `gRPC` request.
 ///
 /// This function constructs a `grpcurl` command that can be used to send a `gRPC` request
@@ -16,6 +22,8 @@ use prost_reflect::MethodDescriptor;
 /// - `message`: The request data in JSON format.
 /// - `method_desc`: The method descriptor for the `gRPC` method.
 /// - `metadata`: Key-value metadata headers to be included in the request.
+/// - `options`: The JSON options, mapped to the `-emit-defaults` and
+///   `-allow-unknown-fields` flags.
 #[allow(clippy::implicit_hasher)]
 pub fn grpcurl<T: Into<Uri>>(
     _includes: &[String],
@@ -23,6 +31,7 @@ pub fn grpcurl<T: Into<Uri>>(
     message: &str,
     method_desc: &MethodDescriptor,
     metadata: &HashMap<String, String>,
+    options: &JsonOptions,
 ) -> String {
     // The host
     let uri = uri.into();
@@ -36,11 +45,25 @@ pub fn grpcurl<T: Into<Uri>>(
     let metadata = metadata
         .iter()
         .fold(String::new(), |mut result, (key, val)| {
+            // grpcurl expects base64 encoded values for binary keys.
+            let val = match decode_binary(val) {
+                Ok(bytes) if is_binary_key(key) => STANDARD.encode(bytes),
+                _ => val.clone(),
+            };
             let _ = write!(result, " -H \"{key}: {val}\"");
             result
         });
 
-    format!("grpcurl -d @ -plaintext{metadata} {host}:{port} {method} <<EOM\n{message}\nEOM")
+    // The json flags
+    let mut flags = String::new();
+    if options.emit_defaults {
+        flags.push_str(" -emit-defaults");
+    }
+    if !options.deny_unknown_fields {
+        flags.push_str(" -allow-unknown-fields");
+    }
+
+    format!("grpcurl -d @ -plaintext{flags}{metadata} {host}:{port} {method} <<EOM\n{message}\nEOM")
 }
 
 #[cfg(test)]
@@ -67,6 +90,7 @@ mod test {
             given_message,
             &given_method,
             &HashMap::new(),
+            &JsonOptions::default(),
         );
 
         // then
@@ -83,6 +107,7 @@ mod test {
         let given_message = "{\n  \"number\": 0\n}";
         let mut metadata = HashMap::new();
         metadata.insert("authorization".to_string(), "Bearer $TOKEN".to_string());
+        metadata.insert("trace-bin".to_string(), "hex:6869".to_string());
 
         // when
         let cmd = grpcurl(
@@ -91,12 +116,20 @@ mod test {
             given_message,
             &given_method,
             &metadata,
+            &JsonOptions {
+                deny_unknown_fields: false,
+                emit_defaults: true,
+                ..JsonOptions::default()
+            },
         );
 
         // then
         assert!(cmd.contains("-H \"authorization: Bearer $TOKEN\""));
+        assert!(cmd.contains("-H \"trace-bin: aGk=\""));
         assert!(cmd.contains("localhost:50051"));
         assert!(cmd.contains("proto.TestService.Simple"));
+        assert!(cmd.contains("-allow-unknown-fields"));
+        assert!(cmd.contains("-emit-defaults"));
     }
 
     fn load_test_message(method: &str) -> RequestMessage {
@@ -5,10 +5,15 @@ pub mod client;
 pub mod descriptor;
 pub mod error;
 pub mod features;
+#[cfg(test)]
+mod test_utils;
 
 pub use crate::descriptor::ProtoDescriptor;
 pub use crate::error::Result;
 
+pub use prost_reflect::DescriptorPool;
+pub use prost_reflect::FieldDescriptor;
+pub use prost_reflect::Kind;
 pub use prost_reflect::MessageDescriptor;
 pub use prost_reflect::MethodDescriptor;
 pub use prost_reflect::ServiceDescriptor;
@@ -41,6 +41,7 @@ impl App {
     #[allow(clippy::needless_pass_by_value)]
     pub async fn run(env: Config, config_file: Option<String>) -> Result<()> {
         let mut app = Self::new(env, config_file)?;
+        app.ctx.messages.borrow_mut().update_validation();
         while !app.should_quit {
             app.draw()?;
             app.handle_events().await?;
@@ -67,6 +68,7 @@ impl App {
                         Event::Paste(text) => self.handle_crossterm_paste_event(text),
                         _ => (),
                     }
+                    self.ctx.messages.borrow_mut().update_validation();
                  }
             },
             internal_event = self.internal_stream.rx.recv() => {
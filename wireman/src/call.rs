@@ -0,0 +1,224 @@
+//! Headless mode that sends a single request and prints the response.
+use crate::model::{
+    headers::{try_expand, AuthHeader},
+    CoreClient,
+};
+use crate::widgets::editor::pretty_format_json;
+use std::io::Read;
+use wireman_config::{cli::CallArgs, parse_duration, Config};
+use wireman_core::{
+    client::compression::Compression,
+    descriptor::{JsonOptions, RequestMessage, ResponseMessage},
+    MethodDescriptor,
+};
+
+/// Sends the request described by the cli arguments and prints the
+/// response as JSON to stdout. Returns the exit code of the process.
+pub async fn run(cfg: &Config, args: &CallArgs) -> i32 {
+    match call(cfg, args).await {
+        Ok(()) => 0,
+        Err(err) => {
+            eprintln!("{err}");
+            1
+        }
+    }
+}
+
+async fn call(cfg: &Config, args: &CallArgs) -> Result<(), String> {
+    let mut core_client =
+        CoreClient::new(cfg).map_err(|err| format!("Failed to load proto files: {err}"))?;
+    if let Some(env) = &args.env {
+        if !core_client.get_environments().contains(env) {
+            return Err(format!("Environment {env} not found"));
+        }
+        core_client.set_environment(Some(env.clone()));
+    }
+    let method = find_method(&core_client, &args.method)?;
+    let messages = build_requests(&core_client, cfg, &method, args)?;
+    let tls = core_client.get_tls_config().map_err(|err| err.string())?;
+
+    let head = messages[0].clone();
+    let is_client = method.is_client_streaming();
+    let is_server = method.is_server_streaming();
+    if !is_client && messages.len() > 1 {
+        return Err(String::from(
+            "Only client streaming methods accept more than one message",
+        ));
+    }
+
+    let options = core_client.json_options();
+    let stream = futures::stream::iter(messages);
+    let mut client = core_client
+        .get_client(&head, tls)
+        .map_err(|err| err.string())?;
+    match (is_client, is_server) {
+        (false, false) => {
+            let resp = client.unary(&head).await;
+            print_response(&resp.map_err(|err| err.to_string())?, &options)
+        }
+        (true, false) => {
+            let resp = client.client_streaming(&head, stream).await;
+            print_response(&resp.map_err(|err| err.to_string())?, &options)
+        }
+        (false, true) => {
+            let resp = client.server_streaming(&head).await;
+            print_stream(resp.map_err(|err| err.to_string())?, &options).await
+        }
+        (true, true) => {
+            let resp = client.bidirectional_streaming(&head, stream).await;
+            print_stream(resp.map_err(|err| err.to_string())?, &options).await
+        }
+    }
+}
+
+/// Finds a method by its name, e.g. `package.Service/Method`.
+fn find_method(core_client: &CoreClient, name: &str) -> Result<MethodDescriptor, String> {
+    let (service, method) = name
+        .trim_start_matches('/')
+        .split_once('/')
+        .ok_or_else(|| format!("Invalid method {name}, expected <service>/<method>"))?;
+    core_client
+        .get_method_by_name(service, method)
+        .ok_or_else(|| format!("Method {name} not found"))
+}
+
+/// Builds one request message per data argument. Falls back to the
+/// configured address, auth header, timeout and compression. Variables of the selected
+/// environment are substituted.
+fn build_requests(
+    core_client: &CoreClient,
+    cfg: &Config,
+    method: &MethodDescriptor,
+    args: &CallArgs,
+) -> Result<Vec<RequestMessage>, String> {
+    let address = args
+        .address
+        .clone()
+        .unwrap_or_else(|| core_client.get_default_address());
+    let address = core_client.substitute(&address);
+    let timeout = match &args.timeout {
+        Some(timeout) => {
+            Some(parse_duration(timeout).ok_or_else(|| format!("Invalid timeout {timeout}"))?)
+        }
+        None => cfg
+            .server
+            .default_timeout()
+            .map_err(|err| err.to_string())?,
+    };
+    let (send, accept) = core_client.get_default_compression();
+    let compression = Compression::parse(&send, &accept).map_err(|err| err.to_string())?;
+    let headers = parse_headers(core_client, &args.headers)?;
+
+    let data = if args.data.is_empty() {
+        vec![String::from("{}")]
+    } else {
+        args.data
+            .iter()
+            .map(|data| read_data(data))
+            .collect::<Result<Vec<_>, _>>()?
+    };
+
+    let mut requests = Vec::new();
+    for json in data {
+        let mut req = core_client.get_request(method);
+        req.message_mut()
+            .from_json_with_options(&core_client.substitute(&json), &core_client.json_options())
+            .map_err(|err| format!("Invalid request message: {err}"))?;
+        for (key, val) in &headers {
+            req.insert_metadata(key, &core_client.substitute(val))
+                .map_err(|err| format!("Invalid header {key}: {err}"))?;
+        }
+        req.set_address(&address);
+        req.set_timeout(timeout);
+        req.set_compression(compression.clone());
+        requests.push(req);
+    }
+    Ok(requests)
+}
+
+/// Parses the `key:value` headers and expands their values. The configured
+/// auth header is used if no authorization header is given.
+fn parse_headers(
+    core_client: &CoreClient,
+    headers: &[String],
+) -> Result<Vec<(String, String)>, String> {
+    let mut parsed = Vec::new();
+    for header in headers {
+        let (key, val) = header
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid header {header}, expected key:value"))?;
+        parsed.push((key.trim().to_lowercase(), try_expand(val.trim())));
+    }
+
+    let auth_key = AuthHeader::key();
+    let default_auth = core_client.get_default_auth_header();
+    if !default_auth.is_empty() && !parsed.iter().any(|(key, _)| *key == auth_key) {
+        let mut auth = AuthHeader::default();
+        auth.set_text(&default_auth);
+        parsed.push((auth_key, auth.value_expanded()));
+    }
+    Ok(parsed)
+}
+
+/// Reads the request data. `@file.json` reads a file, `@-` reads stdin.
+fn read_data(data: &str) -> Result<String, String> {
+    match data.strip_prefix('@') {
+        Some("-") => {
+            let mut buf = String::new();
+            std::io::stdin()
+                .read_to_string(&mut buf)
+                .map_err(|err| format!("Failed to read stdin: {err}"))?;
+            Ok(buf)
+        }
+        Some(file) => std::fs::read_to_string(file)
+            .map_err(|err| format!("Failed to read data from {file}: {err}")),
+        None => Ok(data.to_string()),
+    }
+}
+
+fn print_response(response: &ResponseMessage, options: &JsonOptions) -> Result<(), String> {
+    let json = response
+        .message
+        .to_json_with_options(options)
+        .map_err(|err| err.to_string())?;
+    println!("{}", pretty_format_json(&json).unwrap_or(json));
+    Ok(())
+}
+
+async fn print_stream(
+    mut response: wireman_core::descriptor::response::StreamingResponse,
+    options: &JsonOptions,
+) -> Result<(), String> {
+    while let Some(message) = response.message().await.map_err(|err| err.to_string())? {
+        print_response(&message, options)?;
+    }
+    // Surfaces a non-ok status sent with the trailers.
+    response.details().await.map_err(|err| err.to_string())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_headers() {
+        // given
+        let core_client = CoreClient::default();
+        let headers = vec![
+            "X-Request-Id: 42".to_string(),
+            "authorization:Bearer token".to_string(),
+        ];
+
+        // when
+        let parsed = parse_headers(&core_client, &headers).unwrap();
+
+        // then
+        let expected = vec![
+            ("x-request-id".to_string(), "42".to_string()),
+            ("authorization".to_string(), "Bearer token".to_string()),
+        ];
+        assert_eq!(parsed, expected);
+        assert!(parse_headers(&core_client, &["invalid".to_string()]).is_err());
+    }
+}
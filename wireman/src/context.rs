@@ -4,17 +4,26 @@ use ratatui::{layout::Direction, prelude::Rect};
 use wireman_config::Config;
 
 use crate::model::{
-    configuration::ConfigurationModel, headers::HeadersModel, history::HistoryModel,
-    reflection::ReflectionModel, CoreClient, MessagesModel, SelectionModel,
+    collections::CollectionsModel, configuration::ConfigurationModel, headers::HeadersModel,
+    history::HistoryModel, reflection::ReflectionModel, reflection_cache::ReflectionCache,
+    request_log::RequestLogModel, CoreClient, MessagesModel, SelectionModel,
 };
 
 type Result<T> = std::result::Result<T, Box<dyn Error>>;
 
 #[derive(Default)]
 pub struct UiState {
-    pub navbar_tabs: Option<[Rect; 3]>,
+    pub navbar_tabs: Option<[Rect; 4]>,
     pub history_tabs: Option<[Rect; 5]>,
     pub main_split: Direction,
+    pub show_docs: bool,
+}
+
+impl UiState {
+    /// Shows or hides the documentation panel.
+    pub fn toggle_docs(&mut self) {
+        self.show_docs = !self.show_docs;
+    }
 }
 
 pub struct AppContext {
@@ -50,6 +59,12 @@ pub struct AppContext {
     /// The model for the history
     pub history: Rc<RefCell<HistoryModel>>,
 
+    /// The model for the request collections
+    pub collections: Rc<RefCell<CollectionsModel>>,
+
+    /// The model for the log of dispatched calls
+    pub request_log: Rc<RefCell<RequestLogModel>>,
+
     /// Common UI state (e.g., navbar tabs hit-test areas)
     pub ui: Rc<RefCell<UiState>>,
 
@@ -75,14 +90,27 @@ impl AppContext {
         // The history model
         let history = Rc::new(RefCell::new(HistoryModel::new(env)?));
 
+        // The collections model
+        let collections = Rc::new(RefCell::new(CollectionsModel::new(env)));
+
+        // The request log model
+        let request_log = Rc::new(RefCell::new(RequestLogModel::new(env)));
+
         // The metadata model
         let server_address = &core_client_rc.borrow().get_default_address();
         let server_auth_header = &core_client_rc.borrow().get_default_auth_header();
+        env.server.default_timeout()?;
+        let server_timeout = &core_client_rc.borrow().get_default_timeout();
         let headers = Rc::new(RefCell::new(HeadersModel::new(
             server_address,
             server_auth_header,
+            server_timeout,
             Rc::clone(&history),
         )));
+        let environments = core_client_rc.borrow().get_environments();
+        headers.borrow_mut().set_environments(environments);
+        let (send, accept) = core_client_rc.borrow().get_default_compression();
+        headers.borrow_mut().set_default_compression(&send, &accept);
 
         // The selection model
         let selection = Rc::new(RefCell::new(SelectionModel::new(Rc::clone(
@@ -97,6 +125,7 @@ impl AppContext {
             Rc::clone(&core_client_rc),
             Rc::clone(&headers),
             Rc::clone(&selection),
+            ReflectionCache::new(env),
         )));
 
         // The messages model
@@ -121,6 +150,8 @@ impl AppContext {
             messages,
             headers,
             history,
+            collections,
+            request_log,
             reflection,
             ui: Rc::new(RefCell::new(ui)),
             configuration,
@@ -147,20 +178,23 @@ pub enum Tab {
     Selection,
     Messages,
     Headers,
+    Collections,
 }
 impl Tab {
     pub fn next(self) -> Self {
         match &self {
             Self::Selection => Self::Headers,
             Self::Headers => Self::Messages,
-            Self::Messages => Self::Selection,
+            Self::Messages => Self::Collections,
+            Self::Collections => Self::Selection,
         }
     }
     pub fn prev(self) -> Self {
         match &self {
-            Self::Selection => Self::Messages,
+            Self::Selection => Self::Collections,
             Self::Headers => Self::Selection,
             Self::Messages => Self::Headers,
+            Self::Collections => Self::Messages,
         }
     }
     pub fn index(self) -> usize {
@@ -168,6 +202,7 @@ impl Tab {
             Self::Selection => 0,
             Self::Messages => 2,
             Self::Headers => 1,
+            Self::Collections => 3,
         }
     }
 }
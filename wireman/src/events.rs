@@ -1,6 +1,8 @@
+pub(crate) mod collections;
 pub(crate) mod configuration;
 pub(crate) mod headers;
 pub(crate) mod messages;
+pub(crate) mod request_log;
 pub(crate) mod selection;
 use std::fmt::Display;
 use std::pin::Pin;
@@ -11,10 +13,13 @@ use crate::context::{AppContext, HelpContext, MessagesTab, SelectionTab, Tab};
 use crate::model::messages::{
     bidi_streaming, client_streaming, server_streaming, unary, RequestResult,
 };
+use crate::model::request_log::LogEntry;
 use crate::model::selection::SelectionMode;
+use collections::{CollectionsEventHandler, CollectionsInputEventHandler};
 use configuration::ConfigurationEventHandler;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use futures::{stream::once, Stream, StreamExt};
+use request_log::{RequestLogEventHandler, RequestLogSearchEventHandler};
 pub(crate) use selection::methods::MethodsSelectionEventsHandler;
 pub(crate) use selection::methods_search::MethodsSearchEventsHandler;
 use selection::reflection::ReflectionDialogEventHandler;
@@ -25,12 +30,14 @@ use wireman_event_handler::EventHandler;
 use wireman_logger::Logger;
 
 use self::headers::HeadersEventHandler;
+use self::messages::json_profile::JsonProfileEventHandler;
 use self::messages::request::RequestEventHandler;
 use self::messages::response::ResponseEventHandler;
+use self::messages::wire::WireEventHandler;
 
 pub(crate) enum InternalStreamData {
     Request(RequestResult),
-    Reflection(Result<ProtoDescriptor, String>),
+    Reflection(Result<(String, ProtoDescriptor), String>),
     Done,
 }
 
@@ -64,6 +71,7 @@ impl App {
     pub(crate) fn handle_crossterm_key_event(&mut self, event: KeyEvent) {
         let sx1 = self.internal_stream.sx.clone();
         let sx2 = self.internal_stream.sx.clone();
+        let prev_tab = self.ctx.tab;
         match event.code {
             KeyCode::Char('c') if event.modifiers == KeyModifiers::CONTROL => {
                 self.should_quit = true;
@@ -102,7 +110,27 @@ impl App {
                     }
                     return;
                 }
-
+                // Request log key events
+                if self.ctx.request_log.borrow().toggled() {
+                    if self.ctx.request_log.borrow().searching {
+                        RequestLogSearchEventHandler::handle_key_event(
+                            &mut self.ctx,
+                            event,
+                            &mut self.term,
+                        );
+                    } else {
+                        RequestLogEventHandler::handle_key_event(
+                            &mut self.ctx,
+                            event,
+                            &mut self.term,
+                        );
+                        if event.code == HELP_KEY {
+                            Self::toggle_help(&mut self.ctx, RequestLogEventHandler);
+                        }
+                    }
+                    self.load_collections_on_enter(prev_tab);
+                    return;
+                }
                 // Route specific key event.
                 match self.ctx.tab {
                     Tab::Selection => match self.ctx.selection_tab {
@@ -160,6 +188,22 @@ impl App {
                             Self::toggle_help(&mut self.ctx, HeadersEventHandler);
                         }
                     }
+                    Tab::Messages if self.ctx.messages.borrow().json_profile.toggled() => {
+                        JsonProfileEventHandler::handle_key_event(
+                            &mut self.ctx,
+                            event,
+                            &mut self.term,
+                        );
+                        if event.code == HELP_KEY {
+                            Self::toggle_help(&mut self.ctx, JsonProfileEventHandler);
+                        }
+                    }
+                    Tab::Messages if self.ctx.messages.borrow().wire.toggled() => {
+                        WireEventHandler::handle_key_event(&mut self.ctx, event, &mut self.term);
+                        if event.code == HELP_KEY && !self.ctx.disable_root_events {
+                            Self::toggle_help(&mut self.ctx, WireEventHandler);
+                        }
+                    }
                     Tab::Messages => match self.ctx.messages_tab {
                         MessagesTab::Request => {
                             RequestEventHandler::handle_key_event(
@@ -182,17 +226,49 @@ impl App {
                             }
                         }
                     },
+                    Tab::Collections => {
+                        if self.ctx.collections.borrow().input.is_some() {
+                            CollectionsInputEventHandler::handle_key_event(
+                                &mut self.ctx,
+                                event,
+                                &mut self.term,
+                            );
+                        } else {
+                            CollectionsEventHandler::handle_key_event(
+                                &mut self.ctx,
+                                event,
+                                &mut self.term,
+                            );
+                            if event.code == HELP_KEY && !self.ctx.disable_root_events {
+                                Self::toggle_help(&mut self.ctx, CollectionsEventHandler);
+                            }
+                        }
+                    }
                 }
             }
         }
+        self.load_collections_on_enter(prev_tab);
 
         // Dispatch a grpc request event in a separate thread.
         if self.ctx.messages.borrow().dispatch {
             let mut messages_model = self.ctx.messages.borrow_mut();
-            let tls = messages_model.request.core_client.borrow().get_tls_config();
+            let core_client = messages_model.request.core_client.clone();
+            let tls = core_client.borrow().get_tls_config();
             messages_model.dispatch = false;
-            match messages_model.get_request() {
-                Ok(head) => {
+            let request = tls.and_then(|tls| {
+                let head = messages_model.get_request()?;
+                let client = core_client.borrow_mut().get_client(&head, tls)?;
+                Ok((head, client))
+            });
+            match request {
+                Ok((head, client)) => {
+                    let request = messages_model
+                        .request_json()
+                        .unwrap_or_else(|_| messages_model.request.editor.get_text_raw());
+                    let entry =
+                        LogEntry::new(&head, request, messages_model.headers.borrow().address());
+                    self.ctx.request_log.borrow_mut().start(entry);
+
                     let method = head.method_descriptor();
                     let is_client = method.is_client_streaming();
                     let is_server = method.is_server_streaming();
@@ -208,13 +284,16 @@ impl App {
                         None
                     };
 
+                    let options = messages_model.json_options();
                     let handler = tokio::spawn(async move {
                         let stream: Pin<Box<dyn Stream<Item = RequestResult> + Send>> =
                             match (rx, is_server) {
-                                (Some(rx), true) => bidi_streaming(head, rx, tls).await,
-                                (Some(rx), false) => once(client_streaming(head, rx, tls)).boxed(),
-                                (None, true) => server_streaming(head, tls).await,
-                                (None, false) => once(unary(head, tls)).boxed(),
+                                (Some(rx), true) => bidi_streaming(head, rx, client, options).await,
+                                (Some(rx), false) => {
+                                    once(client_streaming(head, rx, client, options)).boxed()
+                                }
+                                (None, true) => server_streaming(head, client, options).await,
+                                (None, false) => once(unary(head, client, options)).boxed(),
                             };
                         drain_to(stream, &sx1).await;
                     });
@@ -238,6 +317,11 @@ impl App {
             ConfigurationEventHandler::handle_mouse_event(&mut self.ctx, event);
             return;
         }
+        if self.ctx.request_log.borrow().toggled() {
+            RequestLogEventHandler::handle_mouse_event(&mut self.ctx, event);
+            return;
+        }
+        let prev_tab = self.ctx.tab;
 
         // Handle navbar tab area click to switch pages (only on left mouse down)
         if let MouseEvent {
@@ -255,8 +339,11 @@ impl App {
                     self.ctx.tab = Tab::Headers;
                 } else if areas[2].contains(pos) {
                     self.ctx.tab = Tab::Messages;
+                } else if areas[3].contains(pos) {
+                    self.ctx.tab = Tab::Collections;
                 }
             }
+            self.load_collections_on_enter(prev_tab);
 
             if let Some(areas) = self.ctx.ui.borrow().history_tabs {
                 for (i, area) in areas.iter().enumerate() {
@@ -288,6 +375,9 @@ impl App {
                     HeadersEventHandler::handle_mouse_event(&mut self.ctx, event);
                 }
             }
+            Tab::Messages
+                if self.ctx.messages.borrow().wire.toggled()
+                    || self.ctx.messages.borrow().json_profile.toggled() => {}
             Tab::Messages => {
                 // Hit-test: switch tabs on click in request/response areas
                 if let MouseEvent {
@@ -325,16 +415,41 @@ impl App {
                     }
                 };
             }
+            Tab::Collections => {
+                CollectionsEventHandler::handle_mouse_event(&mut self.ctx, event);
+            }
         };
     }
 
+    /// Lists the saved requests of the selected method when the
+    /// collections tab is entered.
+    fn load_collections_on_enter(&mut self, prev_tab: Tab) {
+        if self.ctx.tab == Tab::Collections && prev_tab != Tab::Collections {
+            let method = self.ctx.messages.borrow().selected_method.clone();
+            self.ctx
+                .collections
+                .borrow_mut()
+                .load(method.map(|method| method.full_name().to_string()));
+        }
+    }
+
     pub(crate) fn handle_crossterm_paste_event(&mut self, text: String) {
         if self.ctx.configuration.borrow().toggled() {
             ConfigurationEventHandler::handle_paste_event(&mut self.ctx, text);
             return;
         }
+        if self.ctx.request_log.borrow().toggled() {
+            if self.ctx.request_log.borrow().searching {
+                RequestLogSearchEventHandler::handle_paste_event(&mut self.ctx, text);
+            }
+            return;
+        }
 
         match self.ctx.tab {
+            Tab::Messages if self.ctx.messages.borrow().json_profile.toggled() => {}
+            Tab::Messages if self.ctx.messages.borrow().wire.toggled() => {
+                WireEventHandler::handle_paste_event(&mut self.ctx, text);
+            }
             Tab::Messages => {
                 match self.ctx.messages_tab {
                     MessagesTab::Request => {
@@ -348,6 +463,11 @@ impl App {
             Tab::Headers => {
                 HeadersEventHandler::handle_paste_event(&mut self.ctx, text);
             }
+            Tab::Collections => {
+                if self.ctx.collections.borrow().input.is_some() {
+                    CollectionsInputEventHandler::handle_paste_event(&mut self.ctx, text);
+                }
+            }
             Tab::Selection => {}
         }
     }
@@ -355,14 +475,18 @@ impl App {
     pub(crate) fn handle_internal_event(&mut self, data: &InternalStreamData) {
         match data {
             InternalStreamData::Request(resp) => {
-                resp.set(&mut self.ctx.messages.borrow_mut().response.editor);
+                self.ctx.request_log.borrow_mut().record(resp);
+                let mut messages = self.ctx.messages.borrow_mut();
+                let options = messages.json_options();
+                resp.set(&mut messages.response, &options);
             }
             InternalStreamData::Reflection(desc) => match desc {
-                Ok(desc) => {
+                Ok((address, desc)) => {
                     let d = desc.clone();
-                    self.ctx.selection.borrow_mut().update_descriptor(d);
-                    self.ctx.reflection.borrow_mut().error = None;
-                    self.ctx.selection.borrow_mut().selection_mode = SelectionMode::Reflection;
+                    self.ctx.selection.borrow_mut().add_reflection(address, d);
+                    let mut reflection = self.ctx.reflection.borrow_mut();
+                    reflection.error = None;
+                    reflection.load_cached_at();
                 }
                 Err(err) => {
                     self.ctx.reflection.borrow_mut().error = Some(err.clone());
@@ -370,6 +494,7 @@ impl App {
                 }
             },
             InternalStreamData::Done => {
+                self.ctx.request_log.borrow_mut().finish();
                 let mut messages = self.ctx.messages.borrow_mut();
                 messages.handler = None;
                 messages.clear_stream_session();
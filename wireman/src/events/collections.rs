@@ -0,0 +1,233 @@
+use crate::{
+    context::{AppContext, MessagesTab, Tab},
+    model::collections::CollectionAction,
+};
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::backend::Backend;
+use ratatui::Terminal;
+use std::fmt;
+use tui_widget_list::hit_test::Hit;
+use wireman_event_handler::{EventHandler, KeyCode, KeyEvent};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollectionsEvents {
+    NextTab,
+    PrevTab,
+    Next,
+    Prev,
+    Load,
+    Save,
+    Rename,
+    Duplicate,
+    Delete,
+    ToggleRequestLog,
+}
+
+impl fmt::Display for CollectionsEvents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let display_str = match self {
+            CollectionsEvents::NextTab => "Next Page",
+            CollectionsEvents::PrevTab => "Prev Page",
+            CollectionsEvents::Next => "Next",
+            CollectionsEvents::Prev => "Prev",
+            CollectionsEvents::Load => "Load Request",
+            CollectionsEvents::Save => "Save Current Request",
+            CollectionsEvents::Rename => "Rename Request",
+            CollectionsEvents::Duplicate => "Duplicate Request",
+            CollectionsEvents::Delete => "Delete Request",
+            CollectionsEvents::ToggleRequestLog => "Toggle Request Log",
+        };
+        write!(f, "{display_str}")
+    }
+}
+
+pub struct CollectionsEventHandler;
+
+impl EventHandler for CollectionsEventHandler {
+    type Context = AppContext;
+
+    type Event = CollectionsEvents;
+
+    fn handle_event(event: &CollectionsEvents, ctx: &mut Self::Context) {
+        match event {
+            CollectionsEvents::NextTab => {
+                ctx.tab = ctx.tab.next();
+            }
+            CollectionsEvents::PrevTab => {
+                ctx.tab = ctx.tab.prev();
+            }
+            CollectionsEvents::Next => {
+                ctx.collections.borrow_mut().next();
+            }
+            CollectionsEvents::Prev => {
+                ctx.collections.borrow_mut().prev();
+            }
+            CollectionsEvents::Load => {
+                if ctx.collections.borrow().preview.is_some() {
+                    ctx.collections
+                        .borrow()
+                        .apply(&mut ctx.messages.borrow_mut());
+                    ctx.tab = Tab::Messages;
+                    ctx.messages_tab = MessagesTab::Request;
+                }
+            }
+            CollectionsEvents::Save => {
+                start_input(ctx, CollectionAction::Save);
+            }
+            CollectionsEvents::Rename => {
+                start_input(ctx, CollectionAction::Rename);
+            }
+            CollectionsEvents::Duplicate => {
+                start_input(ctx, CollectionAction::Duplicate);
+            }
+            CollectionsEvents::Delete => {
+                let mut collections = ctx.collections.borrow_mut();
+                if let Some(entry) = collections.selected().cloned() {
+                    let result = collections.delete(&entry);
+                    collections.error = result.err().map(|err| err.to_string());
+                }
+            }
+            CollectionsEvents::ToggleRequestLog => ctx.request_log.borrow_mut().toggle(),
+        }
+    }
+
+    fn key_event_mappings(ctx: &Self::Context) -> Vec<(KeyEvent, CollectionsEvents)> {
+        let mut map = Vec::from([
+            (KeyEvent::new(KeyCode::Tab), CollectionsEvents::NextTab),
+            (
+                KeyEvent::shift(KeyCode::BackTab),
+                CollectionsEvents::PrevTab,
+            ),
+            (KeyEvent::new(KeyCode::Down), CollectionsEvents::Next),
+            (KeyEvent::new(KeyCode::Char('j')), CollectionsEvents::Next),
+            (KeyEvent::new(KeyCode::Up), CollectionsEvents::Prev),
+            (KeyEvent::new(KeyCode::Char('k')), CollectionsEvents::Prev),
+        ]);
+        map.extend([(
+            KeyEvent::ctrl(KeyCode::Char('l')),
+            CollectionsEvents::ToggleRequestLog,
+        )]);
+        if ctx.messages.borrow().selected_method.is_some() {
+            map.extend([
+                (KeyEvent::new(KeyCode::Char('a')), CollectionsEvents::Save),
+                (KeyEvent::ctrl(KeyCode::Char('s')), CollectionsEvents::Save),
+            ]);
+        }
+        if ctx.collections.borrow().selected().is_some() {
+            map.extend([
+                (KeyEvent::new(KeyCode::Enter), CollectionsEvents::Load),
+                (KeyEvent::new(KeyCode::Char('r')), CollectionsEvents::Rename),
+                (
+                    KeyEvent::new(KeyCode::Char('c')),
+                    CollectionsEvents::Duplicate,
+                ),
+                (KeyEvent::new(KeyCode::Char('d')), CollectionsEvents::Delete),
+            ]);
+        }
+        map
+    }
+
+    fn pass_through_mouse_events(event: &MouseEvent, ctx: &mut Self::Context) {
+        let hit = ctx
+            .collections
+            .borrow()
+            .state
+            .hit_test(event.column, event.row);
+        match (event.kind, hit) {
+            (MouseEventKind::ScrollDown, Some(_)) => {
+                ctx.collections.borrow_mut().next();
+            }
+            (MouseEventKind::ScrollUp, Some(_)) => {
+                ctx.collections.borrow_mut().prev();
+            }
+            (MouseEventKind::Down(MouseButton::Left), Some(Hit::Item(index))) => {
+                ctx.collections.borrow_mut().select(index);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn start_input(ctx: &mut AppContext, action: CollectionAction) {
+    ctx.collections.borrow_mut().start_input(action);
+    ctx.disable_root_events = ctx.collections.borrow().input.is_some();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollectionsInputEvents {
+    Submit,
+    Cancel,
+    RemoveChar,
+}
+
+impl fmt::Display for CollectionsInputEvents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let display_str = match self {
+            CollectionsInputEvents::Submit => "Submit",
+            CollectionsInputEvents::Cancel => "Cancel",
+            CollectionsInputEvents::RemoveChar => "Remove Character",
+        };
+        write!(f, "{display_str}")
+    }
+}
+
+/// Handles the name input of the collections tab.
+pub struct CollectionsInputEventHandler;
+
+impl EventHandler for CollectionsInputEventHandler {
+    type Context = AppContext;
+
+    type Event = CollectionsInputEvents;
+
+    fn handle_event(event: &CollectionsInputEvents, ctx: &mut Self::Context) {
+        match event {
+            CollectionsInputEvents::Submit => {
+                ctx.collections
+                    .borrow_mut()
+                    .submit_input(&ctx.messages.borrow());
+                ctx.disable_root_events = false;
+            }
+            CollectionsInputEvents::Cancel => {
+                ctx.collections.borrow_mut().input = None;
+                ctx.disable_root_events = false;
+            }
+            CollectionsInputEvents::RemoveChar => {
+                if let Some(input) = &mut ctx.collections.borrow_mut().input {
+                    input.text.pop();
+                }
+            }
+        }
+    }
+
+    fn pass_through_key_events<B: Backend>(
+        key_event: &KeyEvent,
+        ctx: &mut Self::Context,
+        _terminal: &mut Terminal<B>,
+    ) {
+        if let KeyCode::Char(ch) = key_event.code {
+            if let Some(input) = &mut ctx.collections.borrow_mut().input {
+                input.text.push(ch);
+            }
+        }
+    }
+
+    fn pass_through_paste_events(text: String, ctx: &mut Self::Context) {
+        if let Some(input) = &mut ctx.collections.borrow_mut().input {
+            input.text.push_str(text.lines().next().unwrap_or_default());
+        }
+    }
+
+    fn key_event_mappings(_: &Self::Context) -> Vec<(KeyEvent, CollectionsInputEvents)> {
+        Vec::from([
+            (
+                KeyEvent::new(KeyCode::Enter),
+                CollectionsInputEvents::Submit,
+            ),
+            (KeyEvent::new(KeyCode::Esc), CollectionsInputEvents::Cancel),
+            (
+                KeyEvent::new(KeyCode::Backspace),
+                CollectionsInputEvents::RemoveChar,
+            ),
+        ])
+    }
+}
@@ -1,4 +1,8 @@
-use crate::{context::AppContext, model::headers::HeadersTab, widgets::editor::TextEditor};
+use crate::{
+    context::AppContext,
+    model::headers::{CompressionSelection, HeadersTab},
+    widgets::editor::TextEditor,
+};
 use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
 use edtui::EditorMode;
 use ratatui::backend::Backend;
@@ -20,6 +24,7 @@ pub enum HeadersEvents {
     PrevAuth,
     AddHeaders,
     DelHeaders,
+    NextEnvironment,
     SaveHistory,
     ResetHistory,
     LoadHistory1,
@@ -27,6 +32,7 @@ pub enum HeadersEvents {
     LoadHistory3,
     LoadHistory4,
     LoadHistory5,
+    ToggleRequestLog,
 }
 
 impl fmt::Display for HeadersEvents {
@@ -44,6 +50,7 @@ impl fmt::Display for HeadersEvents {
             HeadersEvents::PrevColForce => "Prev Column (Force)",
             HeadersEvents::AddHeaders => "Add Headers",
             HeadersEvents::DelHeaders => "Del Headers",
+            HeadersEvents::NextEnvironment => "Next Environment",
             HeadersEvents::SaveHistory => "Save Request",
             HeadersEvents::ResetHistory => "Reset Request",
             HeadersEvents::LoadHistory1 => "Load History 1",
@@ -51,6 +58,7 @@ impl fmt::Display for HeadersEvents {
             HeadersEvents::LoadHistory3 => "Load History 3",
             HeadersEvents::LoadHistory4 => "Load History 4",
             HeadersEvents::LoadHistory5 => "Load History 5",
+            HeadersEvents::ToggleRequestLog => "Toggle Request Log",
         };
         write!(f, "{display_str}")
     }
@@ -104,6 +112,16 @@ impl EventHandler for HeadersEventHandler {
                     }
                 }
             }
+            HeadersEvents::NextEnvironment => {
+                let core_client = ctx.messages.borrow().request.core_client.clone();
+                let environment = ctx.headers.borrow_mut().next_environment();
+                core_client.borrow_mut().set_environment(environment);
+                let address = core_client.borrow().get_default_address();
+                let auth_header = core_client.borrow().get_default_auth_header();
+                ctx.headers
+                    .borrow_mut()
+                    .set_defaults(&address, &auth_header);
+            }
             HeadersEvents::LoadHistory1 => {
                 ctx.messages.borrow_mut().handle_history_reload(1);
             }
@@ -131,6 +149,7 @@ impl EventHandler for HeadersEventHandler {
                     ctx.messages.borrow_mut().headers.borrow_mut().clear();
                 }
             }
+            HeadersEvents::ToggleRequestLog => ctx.request_log.borrow_mut().toggle(),
         }
     }
 
@@ -145,12 +164,13 @@ impl EventHandler for HeadersEventHandler {
         };
         let enable_switch_auth_tab = ctx.headers.borrow().tab == HeadersTab::Auth
             && selected_editor.map_or(true, TextEditor::is_empty);
-        let enable_switch_col_force = ctx.headers.borrow().tab == HeadersTab::Meta
+        let enable_del_headers = ctx.headers.borrow().tab == HeadersTab::Meta
             || ctx.headers.borrow().tab == HeadersTab::Auth;
-        let enable_next_col = enable_switch_col_force
-            && (is_last_col && ctx.headers.borrow().tab == HeadersTab::Meta);
-        let enable_prev_col = enable_switch_col_force
-            && (is_first_col && ctx.headers.borrow().tab == HeadersTab::Meta);
+        let has_cols = ctx.headers.borrow().tab == HeadersTab::Meta
+            || ctx.headers.borrow().tab == HeadersTab::Compression;
+        let enable_switch_col_force = enable_del_headers || has_cols;
+        let enable_next_col = has_cols && is_last_col;
+        let enable_prev_col = has_cols && is_first_col;
         let mut map = Vec::new();
         map.extend([(KeyEvent::new(KeyCode::Enter), HeadersEvents::NextTab)]);
         if !disabled_root_events {
@@ -193,6 +213,12 @@ impl EventHandler for HeadersEventHandler {
                 ),
             ]);
         }
+        if !disabled_root_events && !headers.environments.is_empty() {
+            map.extend([(
+                KeyEvent::ctrl(KeyCode::Char('n')),
+                HeadersEvents::NextEnvironment,
+            )]);
+        }
         if !disabled_root_events && enable_switch_auth_tab {
             map.extend([
                 (KeyEvent::new(KeyCode::Right), HeadersEvents::NextAuth),
@@ -233,12 +259,18 @@ impl EventHandler for HeadersEventHandler {
             KeyEvent::ctrl(KeyCode::Char('a')),
             HeadersEvents::AddHeaders,
         )]);
-        if enable_switch_col_force {
+        if enable_del_headers {
             map.extend([(
                 KeyEvent::ctrl(KeyCode::Char('d')),
                 HeadersEvents::DelHeaders,
             )]);
         }
+        if !disabled_root_events {
+            map.extend([(
+                KeyEvent::ctrl(KeyCode::Char('l')),
+                HeadersEvents::ToggleRequestLog,
+            )]);
+        }
 
         map
     }
@@ -261,12 +293,23 @@ impl EventHandler for HeadersEventHandler {
                 input.on_key(key_event.clone().into(), terminal);
                 ctx.disable_root_events = !(input.normal_mode());
             }
+            HeadersTab::Timeout => {
+                let input = &mut ctx.headers.borrow_mut().timeout;
+                input.on_key(key_event.clone().into(), terminal);
+                ctx.disable_root_events = !(input.normal_mode());
+            }
             HeadersTab::Auth => {
                 let mut headers = ctx.headers.borrow_mut();
                 let input = headers.auth.selected_editor_mut();
                 input.on_key(key_event.clone().into(), terminal);
                 ctx.disable_root_events = !(input.normal_mode());
             }
+            HeadersTab::Compression => {
+                let mut headers = ctx.headers.borrow_mut();
+                let input = headers.compression.selected_editor_mut();
+                input.on_key(key_event.clone().into(), terminal);
+                ctx.disable_root_events = !(input.normal_mode());
+            }
             HeadersTab::None => (),
         }
     }
@@ -283,11 +326,20 @@ impl EventHandler for HeadersEventHandler {
                 let input = &mut ctx.headers.borrow_mut().addr;
                 input.on_paste(text);
             }
+            HeadersTab::Timeout => {
+                let input = &mut ctx.headers.borrow_mut().timeout;
+                input.on_paste(text);
+            }
             HeadersTab::Auth => {
                 let mut headers = ctx.headers.borrow_mut();
                 let input = headers.auth.selected_editor_mut();
                 input.on_paste(text);
             }
+            HeadersTab::Compression => {
+                let mut headers = ctx.headers.borrow_mut();
+                let input = headers.compression.selected_editor_mut();
+                input.on_paste(text);
+            }
             HeadersTab::None => (),
         }
     }
@@ -316,11 +368,20 @@ impl EventHandler for HeadersEventHandler {
                         let input = &mut ctx.headers.borrow_mut().addr;
                         input.state.mode = EditorMode::Normal;
                     }
+                    HeadersTab::Timeout => {
+                        let input = &mut ctx.headers.borrow_mut().timeout;
+                        input.state.mode = EditorMode::Normal;
+                    }
                     HeadersTab::Auth => {
                         let mut headers = ctx.headers.borrow_mut();
                         let input = headers.auth.selected_editor_mut();
                         input.state.mode = EditorMode::Normal;
                     }
+                    HeadersTab::Compression => {
+                        let mut headers = ctx.headers.borrow_mut();
+                        let input = headers.compression.selected_editor_mut();
+                        input.state.mode = EditorMode::Normal;
+                    }
                     HeadersTab::None => {}
                 }
                 ctx.disable_root_events = false;
@@ -333,6 +394,7 @@ impl EventHandler for HeadersEventHandler {
             let mut target = None;
             let mut meta_key_clicked = false;
             let mut meta_value_clicked = false;
+            let mut accept_clicked = false;
 
             if headers_ref.addr_title_area.is_some_and(|r| r.contains(pos))
                 || headers_ref
@@ -340,12 +402,31 @@ impl EventHandler for HeadersEventHandler {
                     .is_some_and(|r| r.contains(pos))
             {
                 target = Some(HeadersTab::Addr);
+            } else if headers_ref
+                .timeout_title_area
+                .is_some_and(|r| r.contains(pos))
+                || headers_ref
+                    .timeout_content_area
+                    .is_some_and(|r| r.contains(pos))
+            {
+                target = Some(HeadersTab::Timeout);
             } else if headers_ref.auth_title_area.is_some_and(|r| r.contains(pos))
                 || headers_ref
                     .auth_content_area
                     .is_some_and(|r| r.contains(pos))
             {
                 target = Some(HeadersTab::Auth);
+            } else if headers_ref
+                .compression_title_area
+                .is_some_and(|r| r.contains(pos))
+                || headers_ref
+                    .compression_content_area
+                    .is_some_and(|r| r.contains(pos))
+            {
+                target = Some(HeadersTab::Compression);
+                if let Some(r) = headers_ref.compression_content_area {
+                    accept_clicked = column >= r.x + r.width / 2;
+                }
             } else if headers_ref.meta_title_area.is_some_and(|r| r.contains(pos)) {
                 target = Some(HeadersTab::Meta);
                 meta_key_clicked = true;
@@ -363,6 +444,14 @@ impl EventHandler for HeadersEventHandler {
             }
             drop(headers_ref);
 
+            if target == Some(HeadersTab::Compression) {
+                ctx.headers.borrow_mut().compression.selected = if accept_clicked {
+                    CompressionSelection::Accept
+                } else {
+                    CompressionSelection::Send
+                };
+            }
+
             if let Some(t) = target {
                 if t != current {
                     // First click focuses section; if meta is selected select appropriate column.
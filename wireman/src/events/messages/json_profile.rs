@@ -0,0 +1,55 @@
+use crate::context::AppContext;
+use std::fmt;
+use wireman_event_handler::{EventHandler, KeyCode, KeyEvent};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonProfileEvents {
+    Next,
+    Prev,
+    Toggle,
+    Close,
+}
+
+impl fmt::Display for JsonProfileEvents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let display_str = match self {
+            JsonProfileEvents::Next => "Next Option",
+            JsonProfileEvents::Prev => "Prev Option",
+            JsonProfileEvents::Toggle => "Toggle Option",
+            JsonProfileEvents::Close => "Close",
+        };
+        write!(f, "{display_str}")
+    }
+}
+
+/// Handles the key events of the JSON profile dialog.
+pub struct JsonProfileEventHandler;
+
+impl EventHandler for JsonProfileEventHandler {
+    type Context = AppContext;
+
+    type Event = JsonProfileEvents;
+
+    fn handle_event(event: &JsonProfileEvents, ctx: &mut Self::Context) {
+        let mut messages = ctx.messages.borrow_mut();
+        match event {
+            JsonProfileEvents::Next => messages.json_profile.next(),
+            JsonProfileEvents::Prev => messages.json_profile.prev(),
+            JsonProfileEvents::Toggle => messages.toggle_json_option(),
+            JsonProfileEvents::Close => messages.json_profile.close(),
+        }
+    }
+
+    fn key_event_mappings(_: &Self::Context) -> Vec<(KeyEvent, JsonProfileEvents)> {
+        vec![
+            (KeyEvent::new(KeyCode::Down), JsonProfileEvents::Next),
+            (KeyEvent::new(KeyCode::Char('j')), JsonProfileEvents::Next),
+            (KeyEvent::new(KeyCode::Up), JsonProfileEvents::Prev),
+            (KeyEvent::new(KeyCode::Char('k')), JsonProfileEvents::Prev),
+            (KeyEvent::new(KeyCode::Enter), JsonProfileEvents::Toggle),
+            (KeyEvent::new(KeyCode::Char(' ')), JsonProfileEvents::Toggle),
+            (KeyEvent::new(KeyCode::Esc), JsonProfileEvents::Close),
+            (KeyEvent::ctrl(KeyCode::Char('o')), JsonProfileEvents::Close),
+        ]
+    }
+}
@@ -1,2 +1,4 @@
+pub(crate) mod json_profile;
 pub(crate) mod request;
 pub(crate) mod response;
+pub(crate) mod wire;
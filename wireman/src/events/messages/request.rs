@@ -1,4 +1,5 @@
 use crate::context::{AppContext, MessagesTab};
+use crate::model::wire::WireTarget;
 use crossterm::event::MouseEvent;
 use ratatui::backend::Backend;
 use ratatui::layout::Direction;
@@ -27,6 +28,17 @@ pub enum RequestEvents {
     IncreaseSize,
     DecreaseSize,
     ToggleMainSplit,
+    ToggleSyntax,
+    WireBytes,
+    JsonProfile,
+    Complete,
+    NextCompletion,
+    PrevCompletion,
+    AcceptCompletion,
+    CloseCompletion,
+    NextProblem,
+    ToggleDocs,
+    ToggleRequestLog,
 }
 
 impl fmt::Display for RequestEvents {
@@ -51,6 +63,17 @@ impl fmt::Display for RequestEvents {
             RequestEvents::IncreaseSize => "Increase Size",
             RequestEvents::DecreaseSize => "Decrease Size",
             RequestEvents::ToggleMainSplit => "Toggle main split",
+            RequestEvents::ToggleSyntax => "Toggle Syntax",
+            RequestEvents::WireBytes => "Decode/Encode Wire Bytes",
+            RequestEvents::JsonProfile => "JSON Options",
+            RequestEvents::Complete => "Complete",
+            RequestEvents::NextCompletion => "Next Completion",
+            RequestEvents::PrevCompletion => "Prev Completion",
+            RequestEvents::AcceptCompletion => "Accept Completion",
+            RequestEvents::CloseCompletion => "Close Completion",
+            RequestEvents::NextProblem => "Next Problem",
+            RequestEvents::ToggleDocs => "Toggle Docs",
+            RequestEvents::ToggleRequestLog => "Toggle Request Log",
         };
         write!(f, "{display_str}")
     }
@@ -88,6 +111,7 @@ impl EventHandler for RequestEventHandler {
             }
             RequestEvents::AbortRequest => {
                 ctx.messages.borrow_mut().abort_request();
+                ctx.request_log.borrow_mut().cancel();
             }
             RequestEvents::FinishStream => {
                 ctx.messages.borrow_mut().finish_stream();
@@ -102,7 +126,7 @@ impl EventHandler for RequestEventHandler {
                 ctx.messages.borrow_mut().yank_request();
             }
             RequestEvents::FormatMessage => {
-                ctx.messages.borrow_mut().request.editor.format_json();
+                ctx.messages.borrow_mut().format_request();
             }
             RequestEvents::SaveHistory => {
                 let history = &ctx.messages.borrow().history;
@@ -140,6 +164,25 @@ impl EventHandler for RequestEventHandler {
                     Direction::Horizontal => Direction::Vertical,
                 };
             }
+            RequestEvents::ToggleSyntax => {
+                ctx.messages.borrow_mut().toggle_request_syntax();
+            }
+            RequestEvents::WireBytes => {
+                ctx.messages
+                    .borrow_mut()
+                    .open_wire_dialog(WireTarget::Request);
+            }
+            RequestEvents::JsonProfile => {
+                ctx.messages.borrow_mut().json_profile.open();
+            }
+            RequestEvents::Complete => ctx.messages.borrow_mut().update_completion(),
+            RequestEvents::NextCompletion => ctx.messages.borrow_mut().completion.next(),
+            RequestEvents::PrevCompletion => ctx.messages.borrow_mut().completion.prev(),
+            RequestEvents::AcceptCompletion => ctx.messages.borrow_mut().accept_completion(),
+            RequestEvents::CloseCompletion => ctx.messages.borrow_mut().completion.close(),
+            RequestEvents::NextProblem => ctx.messages.borrow_mut().next_problem(),
+            RequestEvents::ToggleDocs => ctx.ui.borrow_mut().toggle_docs(),
+            RequestEvents::ToggleRequestLog => ctx.request_log.borrow_mut().toggle(),
         }
     }
 
@@ -152,6 +195,25 @@ impl EventHandler for RequestEventHandler {
         };
 
         let mut map = Vec::new();
+        let messages = ctx.messages.borrow();
+        if messages.completion.is_open() {
+            map.extend([
+                (KeyEvent::new(KeyCode::Tab), RequestEvents::AcceptCompletion),
+                (KeyEvent::new(KeyCode::Down), RequestEvents::NextCompletion),
+                (
+                    KeyEvent::ctrl(KeyCode::Char('n')),
+                    RequestEvents::NextCompletion,
+                ),
+                (KeyEvent::new(KeyCode::Up), RequestEvents::PrevCompletion),
+                (
+                    KeyEvent::ctrl(KeyCode::Char('p')),
+                    RequestEvents::PrevCompletion,
+                ),
+                (KeyEvent::new(KeyCode::Esc), RequestEvents::CloseCompletion),
+            ]);
+        } else if messages.request.editor.insert_mode() {
+            map.push((KeyEvent::ctrl(KeyCode::Char(' ')), RequestEvents::Complete));
+        }
         if !disabled_root_events {
             map.extend([
                 (KeyEvent::new(KeyCode::Tab), RequestEvents::NextTab),
@@ -214,10 +276,31 @@ impl EventHandler for RequestEventHandler {
                     KeyEvent::alt(KeyCode::Char('s')),
                     RequestEvents::ToggleMainSplit,
                 ),
+                (
+                    KeyEvent::ctrl(KeyCode::Char('x')),
+                    RequestEvents::ToggleSyntax,
+                ),
+                (KeyEvent::ctrl(KeyCode::Char('b')), RequestEvents::WireBytes),
+                (
+                    KeyEvent::ctrl(KeyCode::Char('o')),
+                    RequestEvents::JsonProfile,
+                ),
+                (
+                    KeyEvent::ctrl(KeyCode::Char('g')),
+                    RequestEvents::NextProblem,
+                ),
+                (
+                    KeyEvent::ctrl(KeyCode::Char('k')),
+                    RequestEvents::ToggleDocs,
+                ),
+                (
+                    KeyEvent::ctrl(KeyCode::Char('l')),
+                    RequestEvents::ToggleRequestLog,
+                ),
             ]);
 
             // Only client- and bidirectional-streaming methods can close an outbound stream.
-            if ctx.messages.borrow().is_client_streaming() {
+            if messages.is_client_streaming() {
                 map.push((
                     KeyEvent::ctrl(KeyCode::Char('d')),
                     RequestEvents::FinishStream,
@@ -232,20 +315,33 @@ impl EventHandler for RequestEventHandler {
         ctx: &mut Self::Context,
         terminal: &mut Terminal<B>,
     ) {
-        let request = &mut ctx.messages.borrow_mut().request.editor;
+        let mut messages = ctx.messages.borrow_mut();
+        let request = &mut messages.request.editor;
         request.on_key(event.clone().into(), terminal);
         ctx.disable_root_events = !request.normal_mode();
+
+        // Typing a name or a quote offers completions, other keys close them.
+        match event.code {
+            KeyCode::Char(c) if c.is_ascii_alphanumeric() || c == '_' || c == '"' => {
+                messages.update_completion();
+            }
+            KeyCode::Backspace if messages.completion.is_open() => messages.update_completion(),
+            _ => messages.completion.close(),
+        }
     }
 
     fn pass_through_mouse_events(event: &MouseEvent, ctx: &mut Self::Context) {
         // Forward the mouse event to the request editor
-        let editor = &mut ctx.messages.borrow_mut().request.editor;
+        let mut messages = ctx.messages.borrow_mut();
+        messages.completion.close();
+        let editor = &mut messages.request.editor;
         editor.on_mouse(*event);
         ctx.disable_root_events = !editor.normal_mode();
     }
 
     fn pass_through_paste_events(text: String, ctx: &mut Self::Context) {
-        let editor = &mut ctx.messages.borrow_mut().request.editor;
-        editor.on_paste(text);
+        let mut messages = ctx.messages.borrow_mut();
+        messages.completion.close();
+        messages.request.editor.on_paste(text);
     }
 }
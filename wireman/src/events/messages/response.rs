@@ -1,4 +1,5 @@
 use crate::context::{AppContext, MessagesTab};
+use crate::model::wire::WireTarget;
 use crossterm::event::MouseEvent;
 use ratatui::backend::Backend;
 use ratatui::layout::Direction;
@@ -16,6 +17,12 @@ pub enum ResponseEvents {
     IncreaseSize,
     DecreaseSize,
     ToggleMainSplit,
+    ToggleSyntax,
+    WireBytes,
+    JsonProfile,
+    ToggleDetails,
+    ToggleDocs,
+    ToggleRequestLog,
 }
 
 impl fmt::Display for ResponseEvents {
@@ -29,6 +36,12 @@ impl fmt::Display for ResponseEvents {
             ResponseEvents::IncreaseSize => "Increase Size",
             ResponseEvents::DecreaseSize => "Decrease Size",
             ResponseEvents::ToggleMainSplit => "Toggle main split",
+            ResponseEvents::ToggleSyntax => "Toggle Syntax",
+            ResponseEvents::WireBytes => "Decode/Encode Wire Bytes",
+            ResponseEvents::JsonProfile => "JSON Options",
+            ResponseEvents::ToggleDetails => "Toggle Details",
+            ResponseEvents::ToggleDocs => "Toggle Docs",
+            ResponseEvents::ToggleRequestLog => "Toggle Request Log",
         };
         write!(f, "{display_str}")
     }
@@ -77,6 +90,22 @@ impl EventHandler for ResponseEventHandler {
                     Direction::Horizontal => Direction::Vertical,
                 };
             }
+            ResponseEvents::ToggleSyntax => {
+                ctx.messages.borrow_mut().toggle_response_syntax();
+            }
+            ResponseEvents::WireBytes => {
+                ctx.messages
+                    .borrow_mut()
+                    .open_wire_dialog(WireTarget::Response);
+            }
+            ResponseEvents::JsonProfile => {
+                ctx.messages.borrow_mut().json_profile.open();
+            }
+            ResponseEvents::ToggleDetails => {
+                ctx.messages.borrow_mut().response.toggle_details();
+            }
+            ResponseEvents::ToggleDocs => ctx.ui.borrow_mut().toggle_docs(),
+            ResponseEvents::ToggleRequestLog => ctx.request_log.borrow_mut().toggle(),
         }
     }
 
@@ -117,6 +146,30 @@ impl EventHandler for ResponseEventHandler {
                     KeyEvent::alt(KeyCode::Char('s')),
                     ResponseEvents::ToggleMainSplit,
                 ),
+                (
+                    KeyEvent::ctrl(KeyCode::Char('x')),
+                    ResponseEvents::ToggleSyntax,
+                ),
+                (
+                    KeyEvent::ctrl(KeyCode::Char('b')),
+                    ResponseEvents::WireBytes,
+                ),
+                (
+                    KeyEvent::ctrl(KeyCode::Char('o')),
+                    ResponseEvents::JsonProfile,
+                ),
+                (
+                    KeyEvent::ctrl(KeyCode::Char('t')),
+                    ResponseEvents::ToggleDetails,
+                ),
+                (
+                    KeyEvent::ctrl(KeyCode::Char('k')),
+                    ResponseEvents::ToggleDocs,
+                ),
+                (
+                    KeyEvent::ctrl(KeyCode::Char('l')),
+                    ResponseEvents::ToggleRequestLog,
+                ),
             ]);
         }
         map
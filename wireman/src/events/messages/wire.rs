@@ -0,0 +1,96 @@
+use crate::context::AppContext;
+use ratatui::backend::Backend;
+use ratatui::Terminal;
+use std::fmt;
+use wireman_event_handler::{EventHandler, KeyCode, KeyEvent};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WireEvents {
+    Decode,
+    NextField,
+    PrevField,
+    CopyBase64,
+    CopyHex,
+    Close,
+}
+
+impl fmt::Display for WireEvents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let display_str = match self {
+            WireEvents::Decode => "Decode",
+            WireEvents::NextField => "Next Field",
+            WireEvents::PrevField => "Prev Field",
+            WireEvents::CopyBase64 => "Copy Request as Base64",
+            WireEvents::CopyHex => "Copy Request as Hex",
+            WireEvents::Close => "Close",
+        };
+        write!(f, "{display_str}")
+    }
+}
+
+/// Handles the key events of the wire bytes dialog.
+pub struct WireEventHandler;
+
+impl EventHandler for WireEventHandler {
+    type Context = AppContext;
+
+    type Event = WireEvents;
+
+    fn handle_event(event: &WireEvents, ctx: &mut Self::Context) {
+        match event {
+            WireEvents::Decode => {
+                ctx.messages.borrow_mut().decode_wire_bytes();
+            }
+            WireEvents::NextField | WireEvents::PrevField => {
+                ctx.messages.borrow_mut().wire.toggle_selection();
+            }
+            WireEvents::CopyBase64 => {
+                ctx.messages.borrow().wire.yank_base64();
+            }
+            WireEvents::CopyHex => {
+                ctx.messages.borrow().wire.yank_hex();
+            }
+            WireEvents::Close => {
+                ctx.messages.borrow_mut().wire.close();
+            }
+        }
+    }
+
+    fn key_event_mappings(ctx: &Self::Context) -> Vec<(KeyEvent, WireEvents)> {
+        let mut map = Vec::new();
+        if !ctx.disable_root_events {
+            map.extend([
+                (KeyEvent::new(KeyCode::Enter), WireEvents::Decode),
+                (KeyEvent::new(KeyCode::Down), WireEvents::NextField),
+                (KeyEvent::new(KeyCode::Char('j')), WireEvents::NextField),
+                (KeyEvent::new(KeyCode::Up), WireEvents::PrevField),
+                (KeyEvent::new(KeyCode::Char('k')), WireEvents::PrevField),
+                (KeyEvent::new(KeyCode::Esc), WireEvents::Close),
+                (KeyEvent::ctrl(KeyCode::Char('b')), WireEvents::Close),
+            ]);
+            if ctx.messages.borrow().wire.encoded.is_some() {
+                map.extend([
+                    (KeyEvent::ctrl(KeyCode::Char('y')), WireEvents::CopyBase64),
+                    (KeyEvent::ctrl(KeyCode::Char('x')), WireEvents::CopyHex),
+                ]);
+            }
+        }
+        map
+    }
+
+    fn pass_through_key_events<B: Backend>(
+        event: &KeyEvent,
+        ctx: &mut Self::Context,
+        terminal: &mut Terminal<B>,
+    ) {
+        let mut messages = ctx.messages.borrow_mut();
+        let editor = messages.wire.selected_editor_mut();
+        editor.on_key(event.clone().into(), terminal);
+        ctx.disable_root_events = !editor.normal_mode();
+    }
+
+    fn pass_through_paste_events(text: String, ctx: &mut Self::Context) {
+        let mut messages = ctx.messages.borrow_mut();
+        messages.wire.selected_editor_mut().on_paste(text);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::context::{AppContext, MessagesTab, Tab};
+use crossterm::event::{MouseEvent, MouseEventKind};
+use ratatui::backend::Backend;
+use ratatui::Terminal;
+use std::fmt;
+use wireman_event_handler::{EventHandler, KeyCode, KeyEvent};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestLogEvents {
+    Next,
+    Prev,
+    Open,
+    Search,
+    ClearSearch,
+    Close,
+}
+
+impl fmt::Display for RequestLogEvents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let display_str = match self {
+            RequestLogEvents::Next => "Next",
+            RequestLogEvents::Prev => "Prev",
+            RequestLogEvents::Open => "Open in Request Editor",
+            RequestLogEvents::Search => "Search",
+            RequestLogEvents::ClearSearch => "Clear Search",
+            RequestLogEvents::Close => "Close",
+        };
+        write!(f, "{display_str}")
+    }
+}
+
+/// Handles the key events of the request log browser.
+pub struct RequestLogEventHandler;
+
+impl EventHandler for RequestLogEventHandler {
+    type Context = AppContext;
+
+    type Event = RequestLogEvents;
+
+    fn handle_event(event: &RequestLogEvents, ctx: &mut Self::Context) {
+        match event {
+            RequestLogEvents::Next => {
+                ctx.request_log.borrow_mut().next();
+            }
+            RequestLogEvents::Prev => {
+                ctx.request_log.borrow_mut().prev();
+            }
+            RequestLogEvents::Open => {
+                let Some(entry) = ctx.request_log.borrow().selected().cloned() else {
+                    return;
+                };
+                let Some((service, method)) = entry.service_and_method() else {
+                    return;
+                };
+                let core_client = ctx.messages.borrow().request.core_client.clone();
+                let Some(method) = core_client.borrow().get_method_by_name(service, method) else {
+                    ctx.messages
+                        .borrow_mut()
+                        .request
+                        .set_text(&format!("Method {} not found.", entry.method));
+                    return;
+                };
+                ctx.selection.borrow_mut().select_method(&method);
+                ctx.messages.borrow_mut().load_method(&method);
+                ctx.headers.borrow_mut().set_method(&method);
+                entry.apply(&mut ctx.messages.borrow_mut());
+
+                ctx.request_log.borrow_mut().toggle();
+                ctx.tab = Tab::Messages;
+                ctx.messages_tab = MessagesTab::Request;
+            }
+            RequestLogEvents::Search => {
+                ctx.request_log.borrow_mut().searching = true;
+            }
+            RequestLogEvents::ClearSearch => {
+                ctx.request_log.borrow_mut().clear_filter();
+            }
+            RequestLogEvents::Close => {
+                ctx.request_log.borrow_mut().toggle();
+            }
+        }
+    }
+
+    fn key_event_mappings(ctx: &Self::Context) -> Vec<(KeyEvent, RequestLogEvents)> {
+        let mut map = Vec::from([
+            (KeyEvent::new(KeyCode::Down), RequestLogEvents::Next),
+            (KeyEvent::new(KeyCode::Char('j')), RequestLogEvents::Next),
+            (KeyEvent::new(KeyCode::Up), RequestLogEvents::Prev),
+            (KeyEvent::new(KeyCode::Char('k')), RequestLogEvents::Prev),
+            (KeyEvent::new(KeyCode::Enter), RequestLogEvents::Open),
+            (KeyEvent::new(KeyCode::Char('/')), RequestLogEvents::Search),
+            (KeyEvent::ctrl(KeyCode::Char('l')), RequestLogEvents::Close),
+        ]);
+        if ctx.request_log.borrow().filter.is_some() {
+            map.push((KeyEvent::new(KeyCode::Esc), RequestLogEvents::ClearSearch));
+        } else {
+            map.push((KeyEvent::new(KeyCode::Esc), RequestLogEvents::Close));
+        }
+        map
+    }
+
+    fn pass_through_mouse_events(event: &MouseEvent, ctx: &mut Self::Context) {
+        match event.kind {
+            MouseEventKind::ScrollDown => ctx.request_log.borrow_mut().next(),
+            MouseEventKind::ScrollUp => ctx.request_log.borrow_mut().prev(),
+            _ => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestLogSearchEvents {
+    Finish,
+    RemoveChar,
+}
+
+impl fmt::Display for RequestLogSearchEvents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let display_str = match self {
+            RequestLogSearchEvents::Finish => "Finish",
+            RequestLogSearchEvents::RemoveChar => "Remove Character",
+        };
+        write!(f, "{display_str}")
+    }
+}
+
+/// Handles the search line of the request log browser.
+pub struct RequestLogSearchEventHandler;
+
+impl EventHandler for RequestLogSearchEventHandler {
+    type Context = AppContext;
+
+    type Event = RequestLogSearchEvents;
+
+    fn handle_event(event: &RequestLogSearchEvents, ctx: &mut Self::Context) {
+        match event {
+            RequestLogSearchEvents::Finish => {
+                ctx.request_log.borrow_mut().searching = false;
+            }
+            RequestLogSearchEvents::RemoveChar => {
+                ctx.request_log.borrow_mut().remove_char_filter();
+            }
+        }
+    }
+
+    fn pass_through_key_events<B: Backend>(
+        key_event: &KeyEvent,
+        ctx: &mut Self::Context,
+        _terminal: &mut Terminal<B>,
+    ) {
+        if let KeyCode::Char(ch) = key_event.code {
+            ctx.request_log.borrow_mut().push_char_filter(ch);
+        }
+    }
+
+    fn pass_through_paste_events(text: String, ctx: &mut Self::Context) {
+        for ch in text.chars().filter(|ch| !ch.is_control()) {
+            ctx.request_log.borrow_mut().push_char_filter(ch);
+        }
+    }
+
+    fn key_event_mappings(_: &Self::Context) -> Vec<(KeyEvent, RequestLogSearchEvents)> {
+        Vec::from([
+            (
+                KeyEvent::new(KeyCode::Enter),
+                RequestLogSearchEvents::Finish,
+            ),
+            (KeyEvent::new(KeyCode::Esc), RequestLogSearchEvents::Finish),
+            (
+                KeyEvent::new(KeyCode::Backspace),
+                RequestLogSearchEvents::RemoveChar,
+            ),
+        ])
+    }
+}
@@ -1,7 +1,4 @@
-use crate::{
-    context::{AppContext, SelectionTab},
-    model::selection::SelectionMode,
-};
+use crate::context::{AppContext, SelectionTab};
 use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
 use ratatui::layout::Direction;
 use std::fmt;
@@ -20,9 +17,10 @@ pub enum MethodsSelectionEvents {
     ClearSearch,
     GoToServices,
     ToggleReflectionMode,
-    UntoggleReflectionMode,
     EditConfig,
     ToggleMainSplit,
+    ToggleDocs,
+    ToggleRequestLog,
 }
 
 impl fmt::Display for MethodsSelectionEvents {
@@ -38,9 +36,10 @@ impl fmt::Display for MethodsSelectionEvents {
             MethodsSelectionEvents::ClearSearch => "Clear Search",
             MethodsSelectionEvents::GoToServices => "Go to Services",
             MethodsSelectionEvents::ToggleReflectionMode => "Toggle Reflection Mode",
-            MethodsSelectionEvents::UntoggleReflectionMode => "Untoggle Reflection Mode",
             MethodsSelectionEvents::EditConfig => "Edit Configuration",
             MethodsSelectionEvents::ToggleMainSplit => "Toggle main split",
+            MethodsSelectionEvents::ToggleDocs => "Toggle Docs",
+            MethodsSelectionEvents::ToggleRequestLog => "Toggle Request Log",
         };
         write!(f, "{display_str}")
     }
@@ -102,9 +101,9 @@ impl EventHandler for MethodsSelectionEventsHandler {
             MethodsSelectionEvents::GoToServices => {
                 ctx.selection_tab = SelectionTab::Services;
             }
-            MethodsSelectionEvents::ToggleReflectionMode
-            | MethodsSelectionEvents::UntoggleReflectionMode => {
+            MethodsSelectionEvents::ToggleReflectionMode => {
                 ctx.selection.borrow_mut().toggle_reflection_mode();
+                ctx.reflection.borrow_mut().load_cached_at();
             }
             MethodsSelectionEvents::EditConfig => {
                 ctx.configuration.borrow_mut().toggle();
@@ -116,6 +115,8 @@ impl EventHandler for MethodsSelectionEventsHandler {
                     Direction::Horizontal => Direction::Vertical,
                 };
             }
+            MethodsSelectionEvents::ToggleDocs => ctx.ui.borrow_mut().toggle_docs(),
+            MethodsSelectionEvents::ToggleRequestLog => ctx.request_log.borrow_mut().toggle(),
         }
     }
 
@@ -145,17 +146,10 @@ impl EventHandler for MethodsSelectionEventsHandler {
             )]);
         }
 
-        if ctx.selection.borrow().selection_mode == SelectionMode::File {
-            map.extend([(
-                KeyEvent::ctrl(KeyCode::Char('r')),
-                MethodsSelectionEvents::ToggleReflectionMode,
-            )]);
-        } else {
-            map.extend([(
-                KeyEvent::ctrl(KeyCode::Char('r')),
-                MethodsSelectionEvents::UntoggleReflectionMode,
-            )]);
-        }
+        map.extend([(
+            KeyEvent::ctrl(KeyCode::Char('r')),
+            MethodsSelectionEvents::ToggleReflectionMode,
+        )]);
 
         if filter_active {
             map.extend([(
@@ -192,6 +186,16 @@ impl EventHandler for MethodsSelectionEventsHandler {
             KeyEvent::ctrl(KeyCode::Char('e')),
             MethodsSelectionEvents::EditConfig,
         )]);
+        map.extend([
+            (
+                KeyEvent::ctrl(KeyCode::Char('k')),
+                MethodsSelectionEvents::ToggleDocs,
+            ),
+            (
+                KeyEvent::ctrl(KeyCode::Char('l')),
+                MethodsSelectionEvents::ToggleRequestLog,
+            ),
+        ]);
         map
     }
 
@@ -1,7 +1,4 @@
-use crate::{
-    events::ServicesSelectionEventsHandler,
-    model::{headers::HeadersTab, selection::SelectionMode},
-};
+use crate::{events::ServicesSelectionEventsHandler, model::headers::HeadersTab};
 use ratatui::backend::Backend;
 use ratatui::Terminal;
 use wireman_event_handler::{EventHandler, KeyCode, KeyEvent};
@@ -32,13 +29,22 @@ impl std::fmt::Display for ReflectionDialogEvents {
 pub enum ReflectionEvents {
     CloseDialog,
     ReflectServer,
+    UseCache,
+    NextRow,
+    PrevRow,
 }
 
+/// The header rows of the reflection dialog.
+const ROWS: [HeadersTab; 2] = [HeadersTab::Addr, HeadersTab::Auth];
+
 impl std::fmt::Display for ReflectionEvents {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let display_str = match self {
             ReflectionEvents::ReflectServer => "Reflect server",
             ReflectionEvents::CloseDialog => "Close dialog",
+            ReflectionEvents::UseCache => "Use cached schema",
+            ReflectionEvents::NextRow => "Next Row",
+            ReflectionEvents::PrevRow => "Prev Row",
         };
         write!(f, "{display_str}")
     }
@@ -53,7 +59,10 @@ impl EventHandler for ReflectionDialogEventHandler {
 
     fn handle_event(event: &ReflectionDialogEvents, ctx: &mut Self::Context) {
         match event {
-            ReflectionDialogEvents::Headers(event) => HeadersEventHandler::handle_event(event, ctx),
+            ReflectionDialogEvents::Headers(event) => {
+                HeadersEventHandler::handle_event(event, ctx);
+                ctx.reflection.borrow_mut().refresh_cached_at();
+            }
             ReflectionDialogEvents::Selection(event) => {
                 ServicesSelectionEventsHandler::handle_event(event, ctx);
             }
@@ -61,12 +70,24 @@ impl EventHandler for ReflectionDialogEventHandler {
                 ReflectionEvents::ReflectServer => {
                     ctx.reflection.borrow_mut().dispatch_reflection();
                 }
+                ReflectionEvents::UseCache => {
+                    let mut reflection = ctx.reflection.borrow_mut();
+                    if let Some(desc) = reflection.load_cache() {
+                        let host = reflection.host();
+                        reflection
+                            .selection
+                            .borrow_mut()
+                            .add_reflection(&host, desc);
+                    }
+                }
+                ReflectionEvents::NextRow => select_row(ctx, true),
+                ReflectionEvents::PrevRow => select_row(ctx, false),
                 ReflectionEvents::CloseDialog => {
                     ctx.reflection
                         .borrow()
                         .selection
                         .borrow_mut()
-                        .selection_mode = SelectionMode::File;
+                        .close_reflection_dialog();
                 }
             },
         }
@@ -94,6 +115,12 @@ impl EventHandler for ReflectionDialogEventHandler {
                     ),
                 ),
             ]);
+            if ctx.reflection.borrow().cached_at().is_some() {
+                map.push((
+                    KeyEvent::ctrl(KeyCode::Char('o')),
+                    ReflectionDialogEvents::Reflection(ReflectionEvents::UseCache),
+                ));
+            }
         }
         if !disabled_root_events && selected_tab == HeadersTab::Addr
             || selected_tab == HeadersTab::None
@@ -101,11 +128,11 @@ impl EventHandler for ReflectionDialogEventHandler {
             map.extend([
                 (
                     KeyEvent::new(KeyCode::Down),
-                    ReflectionDialogEvents::Headers(HeadersEvents::NextRow),
+                    ReflectionDialogEvents::Reflection(ReflectionEvents::NextRow),
                 ),
                 (
                     KeyEvent::new(KeyCode::Char('j')),
-                    ReflectionDialogEvents::Headers(HeadersEvents::NextRow),
+                    ReflectionDialogEvents::Reflection(ReflectionEvents::NextRow),
                 ),
             ]);
         }
@@ -113,11 +140,11 @@ impl EventHandler for ReflectionDialogEventHandler {
             map.extend([
                 (
                     KeyEvent::new(KeyCode::Up),
-                    ReflectionDialogEvents::Headers(HeadersEvents::PrevRow),
+                    ReflectionDialogEvents::Reflection(ReflectionEvents::PrevRow),
                 ),
                 (
                     KeyEvent::new(KeyCode::Char('k')),
-                    ReflectionDialogEvents::Headers(HeadersEvents::PrevRow),
+                    ReflectionDialogEvents::Reflection(ReflectionEvents::PrevRow),
                 ),
                 (
                     KeyEvent::shift(KeyCode::Char('L')),
@@ -151,7 +178,20 @@ impl EventHandler for ReflectionDialogEventHandler {
                 input.on_key(key_event.clone().into(), terminal);
                 ctx.disable_root_events = !(input.normal_mode());
             }
-            HeadersTab::Meta | HeadersTab::None => (),
+            HeadersTab::Timeout | HeadersTab::Compression | HeadersTab::Meta | HeadersTab::None => {
+            }
         }
     }
 }
+
+/// Selects the next or previous row of the reflection dialog.
+fn select_row(ctx: &mut AppContext, next: bool) {
+    let mut headers = ctx.headers.borrow_mut();
+    let index = ROWS.iter().position(|row| *row == headers.tab);
+    let index = match (index, next) {
+        (None, _) => 0,
+        (Some(index), true) => (index + 1).min(ROWS.len() - 1),
+        (Some(index), false) => index.saturating_sub(1),
+    };
+    headers.tab = ROWS[index].clone();
+}
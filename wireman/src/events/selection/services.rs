@@ -1,6 +1,5 @@
 use crate::{
     context::{AppContext, SelectionTab},
-    model::selection::SelectionMode,
     widgets::editor::yank_to_clipboard,
 };
 use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
@@ -22,6 +21,8 @@ pub enum ServicesSelectionEvents {
     EditConfig,
     YankWebsiteLink,
     ToggleMainSplit,
+    ToggleDocs,
+    ToggleRequestLog,
 }
 
 impl fmt::Display for ServicesSelectionEvents {
@@ -34,10 +35,12 @@ impl fmt::Display for ServicesSelectionEvents {
             ServicesSelectionEvents::ClearSearch => "Clear Search",
             ServicesSelectionEvents::GoToMethods => "Go to Methods",
             ServicesSelectionEvents::ToggleReflectionMode => "Toggle Reflection Mode",
-            ServicesSelectionEvents::UntoggleReflectionMode => "Untoggle Reflection Mode",
+            ServicesSelectionEvents::UntoggleReflectionMode => "Remove Reflected Services",
             ServicesSelectionEvents::EditConfig => "Edit Configuration",
             ServicesSelectionEvents::YankWebsiteLink => "Yank website link",
             ServicesSelectionEvents::ToggleMainSplit => "Toggle main split",
+            ServicesSelectionEvents::ToggleDocs => "Toggle Docs",
+            ServicesSelectionEvents::ToggleRequestLog => "Toggle Request Log",
         };
         write!(f, "{display_str}")
     }
@@ -78,9 +81,12 @@ impl EventHandler for ServicesSelectionEventsHandler {
             ServicesSelectionEvents::GoToMethods => {
                 ctx.selection_tab = SelectionTab::Methods;
             }
-            ServicesSelectionEvents::ToggleReflectionMode
-            | ServicesSelectionEvents::UntoggleReflectionMode => {
+            ServicesSelectionEvents::ToggleReflectionMode => {
                 ctx.selection.borrow_mut().toggle_reflection_mode();
+                ctx.reflection.borrow_mut().load_cached_at();
+            }
+            ServicesSelectionEvents::UntoggleReflectionMode => {
+                ctx.selection.borrow_mut().untoggle_reflection_mode();
             }
             ServicesSelectionEvents::EditConfig => {
                 ctx.configuration.borrow_mut().toggle();
@@ -95,6 +101,8 @@ impl EventHandler for ServicesSelectionEventsHandler {
                     Direction::Horizontal => Direction::Vertical,
                 };
             }
+            ServicesSelectionEvents::ToggleDocs => ctx.ui.borrow_mut().toggle_docs(),
+            ServicesSelectionEvents::ToggleRequestLog => ctx.request_log.borrow_mut().toggle(),
         }
     }
 
@@ -128,21 +136,24 @@ impl EventHandler for ServicesSelectionEventsHandler {
                 ServicesSelectionEvents::ToggleMainSplit,
             ),
         ]);
-        if ctx.selection.borrow().selection_mode == SelectionMode::File {
-            map.extend([(
-                KeyEvent::ctrl(KeyCode::Char('r')),
-                ServicesSelectionEvents::ToggleReflectionMode,
-            )]);
-        } else {
-            map.extend([(
-                KeyEvent::ctrl(KeyCode::Char('r')),
-                ServicesSelectionEvents::UntoggleReflectionMode,
-            )]);
-        }
+        map.extend([(
+            KeyEvent::ctrl(KeyCode::Char('r')),
+            ServicesSelectionEvents::ToggleReflectionMode,
+        )]);
         map.extend([(
             KeyEvent::ctrl(KeyCode::Char('e')),
             ServicesSelectionEvents::EditConfig,
         )]);
+        map.extend([
+            (
+                KeyEvent::ctrl(KeyCode::Char('k')),
+                ServicesSelectionEvents::ToggleDocs,
+            ),
+            (
+                KeyEvent::ctrl(KeyCode::Char('l')),
+                ServicesSelectionEvents::ToggleRequestLog,
+            ),
+        ]);
         if ctx.selection.borrow().services_filter.is_some() {
             map.extend([(
                 KeyEvent::new(KeyCode::Esc),
@@ -1,10 +1,13 @@
 #![allow(clippy::cast_possible_truncation, clippy::module_name_repetitions)]
 #![allow(dead_code)]
 mod app;
+mod call;
 mod context;
 mod events;
 mod model;
 mod term;
+#[cfg(test)]
+mod test_utils;
 mod view;
 mod widgets;
 use app::App;
@@ -17,6 +20,20 @@ type Result<T> = std::result::Result<T, Box<dyn Error>>;
 async fn main() -> Result<()> {
     let version = env!("CARGO_PKG_VERSION");
     let args = cli::parse(version);
+    if let Some(cli::Command::Call(call_args)) = &args.command {
+        let code = match init_from_env(&args) {
+            Ok((cfg, _)) => call::run(&cfg, call_args).await,
+            Err(wireman_config::error::Error::SetupError(err)) => {
+                eprintln!("Setup error: {err}");
+                1
+            }
+            Err(err) => {
+                eprintln!("An unknown error occurred:\n{err}");
+                1
+            }
+        };
+        std::process::exit(code);
+    }
     if args.command.is_some() {
         return Ok(());
     }
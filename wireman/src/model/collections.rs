@@ -0,0 +1,478 @@
+use super::{history::HistoryData, MessagesModel};
+use std::{
+    error::Error,
+    fmt,
+    path::{Path, PathBuf},
+};
+use tui_widget_list::ListState;
+use wireman_config::Config;
+use wireman_logger::Logger;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Named requests of a method, grouped into folders.
+///
+/// Each request is stored as a pretty printed json file under
+/// `<directory>/<method full name>/<folder>/<name>.json`, so that
+/// a collection can be committed next to the protos.
+#[derive(Default)]
+pub struct CollectionsModel {
+    /// The filepath where the collections are stored
+    base_path: PathBuf,
+
+    /// The full name of the selected method
+    method: Option<String>,
+
+    /// The saved requests of the selected method, sorted by folder and name
+    pub entries: Vec<CollectionEntry>,
+
+    /// The selection state of the entries
+    pub state: ListState,
+
+    /// The saved request of the selected entry
+    pub preview: Option<HistoryData>,
+
+    /// The name input. Only non-None while a name is edited.
+    pub input: Option<CollectionInput>,
+
+    /// The error of the last action
+    pub error: Option<String>,
+}
+
+impl CollectionsModel {
+    pub fn new(env: &Config) -> Self {
+        Self {
+            base_path: PathBuf::from(env.collections.directory_expanded()),
+            ..Self::default()
+        }
+    }
+
+    /// Lists the saved requests of a method.
+    pub fn load(&mut self, method: Option<String>) {
+        self.method = method;
+        self.error = None;
+        self.reload();
+    }
+
+    /// Lists the saved requests of the selected method again.
+    pub fn reload(&mut self) {
+        self.entries = self.method_dir().map(|dir| list(&dir)).unwrap_or_default();
+        let selected = match self.state.selected {
+            _ if self.entries.is_empty() => None,
+            Some(i) => Some(i.min(self.entries.len() - 1)),
+            None => Some(0),
+        };
+        self.state.select(selected);
+        self.load_preview();
+    }
+
+    /// Returns the selected entry.
+    pub fn selected(&self) -> Option<&CollectionEntry> {
+        self.state.selected.and_then(|i| self.entries.get(i))
+    }
+
+    /// Selects the next entry.
+    pub fn next(&mut self) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let i = match self.state.selected {
+            Some(i) if i + 1 < self.entries.len() => i + 1,
+            _ => 0,
+        };
+        self.state.select(Some(i));
+        self.load_preview();
+    }
+
+    /// Selects the previous entry.
+    pub fn prev(&mut self) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let i = match self.state.selected {
+            Some(i) if i > 0 => i - 1,
+            _ => self.entries.len() - 1,
+        };
+        self.state.select(Some(i));
+        self.load_preview();
+    }
+
+    /// Selects an entry by its index.
+    pub fn select(&mut self, index: usize) {
+        if index < self.entries.len() {
+            self.state.select(Some(index));
+            self.load_preview();
+        }
+    }
+
+    fn load_preview(&mut self) {
+        self.preview = self.selected().and_then(|entry| {
+            self.read(entry)
+                .map_err(|err| Logger::debug(format!("collections: {err}")))
+                .ok()
+        });
+    }
+
+    /// Reads a saved request.
+    pub fn read(&self, entry: &CollectionEntry) -> Result<HistoryData> {
+        let path = self.path(entry)?;
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Saves a request. Overwrites an existing request with the same name.
+    /// The authentication is left out, unless it is an unresolved `{{var}}`,
+    /// so that no secrets end up in the collection files.
+    pub fn save(&mut self, entry: &CollectionEntry, data: &HistoryData) -> Result<()> {
+        let path = self.path(entry)?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let data = HistoryData {
+            authentication: data
+                .authentication
+                .clone()
+                .filter(|auth| is_placeholder(auth)),
+            ..data.clone()
+        };
+        std::fs::write(path, data.to_json() + "\n")?;
+        self.reload_and_select(entry);
+        Ok(())
+    }
+
+    /// Renames a saved request. The request can be moved to another folder.
+    pub fn rename(&mut self, from: &CollectionEntry, to: &CollectionEntry) -> Result<()> {
+        let (from_path, to_path) = (self.path(from)?, self.path(to)?);
+        if to_path.exists() {
+            return Err(format!("{to} already exists").into());
+        }
+        if let Some(dir) = to_path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::rename(&from_path, to_path)?;
+        remove_empty_folder(&from_path);
+        self.reload_and_select(to);
+        Ok(())
+    }
+
+    /// Copies a saved request under a new name.
+    pub fn duplicate(&mut self, from: &CollectionEntry, to: &CollectionEntry) -> Result<()> {
+        if self.path(to)?.exists() {
+            return Err(format!("{to} already exists").into());
+        }
+        let data = self.read(from)?;
+        self.save(to, &data)
+    }
+
+    /// Deletes a saved request.
+    pub fn delete(&mut self, entry: &CollectionEntry) -> Result<()> {
+        let path = self.path(entry)?;
+        std::fs::remove_file(&path)?;
+        remove_empty_folder(&path);
+        self.reload();
+        Ok(())
+    }
+
+    /// Loads the selected request into the request and headers. Keeps
+    /// the current authentication if the request has none.
+    pub fn apply(&self, messages: &mut MessagesModel) {
+        if let Some(data) = &self.preview {
+            let auth = messages.headers.borrow().auth.value();
+            data.apply(messages);
+            if data.authentication.is_none() {
+                messages.headers.borrow_mut().auth.set_text(&auth);
+            }
+        }
+    }
+
+    /// Opens the name input for an action. The input is prefilled
+    /// with the selected entry.
+    pub fn start_input(&mut self, action: CollectionAction) {
+        let selected = self.selected();
+        let text = match (action, selected) {
+            (CollectionAction::Save, Some(entry)) => entry
+                .folder
+                .as_ref()
+                .map(|folder| format!("{folder}/"))
+                .unwrap_or_default(),
+            (CollectionAction::Rename, Some(entry)) => entry.to_string(),
+            (CollectionAction::Duplicate, Some(entry)) => format!("{entry} copy"),
+            (CollectionAction::Save, None) => String::new(),
+            (CollectionAction::Rename | CollectionAction::Duplicate, None) => return,
+        };
+        self.error = None;
+        self.input = Some(CollectionInput { action, text });
+    }
+
+    /// Performs the action of the name input and closes it.
+    pub fn submit_input(&mut self, messages: &MessagesModel) {
+        let Some(input) = self.input.take() else {
+            return;
+        };
+        let Some(to) = CollectionEntry::parse(&input.text) else {
+            self.error = Some(format!("Invalid name: {}", input.text));
+            return;
+        };
+        let result = match (input.action, self.selected().cloned()) {
+            (CollectionAction::Save, _) => match HistoryData::from_messages(messages) {
+                Some(data) => self.save(&to, &data),
+                None => Err("The request is not valid json".into()),
+            },
+            (CollectionAction::Rename, Some(from)) => self.rename(&from, &to),
+            (CollectionAction::Duplicate, Some(from)) => self.duplicate(&from, &to),
+            (CollectionAction::Rename | CollectionAction::Duplicate, None) => Ok(()),
+        };
+        self.error = result.err().map(|err| err.to_string());
+    }
+
+    fn reload_and_select(&mut self, entry: &CollectionEntry) {
+        self.reload();
+        if let Some(i) = self.entries.iter().position(|e| e == entry) {
+            self.select(i);
+        }
+    }
+
+    /// Returns the directory of the selected method.
+    fn method_dir(&self) -> Option<PathBuf> {
+        if self.base_path.as_os_str().is_empty() {
+            return None;
+        }
+        let method = self.method.as_ref()?;
+        Some(self.base_path.join(method))
+    }
+
+    /// Returns the file path of an entry.
+    fn path(&self, entry: &CollectionEntry) -> Result<PathBuf> {
+        let mut path = self.method_dir().ok_or("No method selected")?;
+        if let Some(folder) = &entry.folder {
+            path.push(folder);
+        }
+        path.push(format!("{}.json", entry.name));
+        Ok(path)
+    }
+}
+
+/// Lists the entries of a method directory. Folders are not nested.
+fn list(dir: &Path) -> Vec<CollectionEntry> {
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut entries = Vec::new();
+    for path in read_dir.flatten().map(|e| e.path()) {
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if path.is_dir() {
+            let Ok(sub_dir) = std::fs::read_dir(&path) else {
+                continue;
+            };
+            let folder = file_name.to_string();
+            entries.extend(sub_dir.flatten().filter_map(|e| {
+                let name = json_file_stem(&e.path())?;
+                Some(CollectionEntry::new(Some(folder.clone()), name))
+            }));
+        } else if let Some(name) = json_file_stem(&path) {
+            entries.push(CollectionEntry::new(None, name));
+        }
+    }
+    entries.sort();
+    entries
+}
+
+fn json_file_stem(path: &Path) -> Option<String> {
+    if !path.is_file() || path.extension().map_or(true, |ext| ext != "json") {
+        return None;
+    }
+    path.file_stem()?.to_str().map(ToString::to_string)
+}
+
+/// Removes the folder of a deleted or moved entry if it is empty.
+fn remove_empty_folder(path: &Path) {
+    if let Some(dir) = path.parent() {
+        // Fails if the folder is not empty, which is intended.
+        let _ = std::fs::remove_dir(dir);
+    }
+}
+
+/// A named request of a collection.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CollectionEntry {
+    /// The folder of the request. `None` for requests on the top level.
+    pub folder: Option<String>,
+
+    /// The name of the request
+    pub name: String,
+}
+
+impl CollectionEntry {
+    pub fn new<T: Into<String>>(folder: Option<String>, name: T) -> Self {
+        Self {
+            folder,
+            name: name.into(),
+        }
+    }
+
+    /// Parses an entry from `folder/name` or `name`. Names must be
+    /// valid file names and folders cannot be nested.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (folder, name) = match text.trim().split_once('/') {
+            Some((folder, name)) => (Some(folder.trim()), name.trim()),
+            None => (None, text.trim()),
+        };
+        let is_valid =
+            |s: &str| !s.is_empty() && !s.starts_with('.') && !s.contains(['/', '\\', '\0']);
+        if !is_valid(name) || folder.is_some_and(|folder| !is_valid(folder)) {
+            return None;
+        }
+        Some(Self::new(folder.map(ToString::to_string), name))
+    }
+}
+
+impl fmt::Display for CollectionEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.folder {
+            Some(folder) => write!(f, "{folder}/{}", self.name),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+/// The action of the name input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollectionAction {
+    Save,
+    Rename,
+    Duplicate,
+}
+
+impl fmt::Display for CollectionAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let display_str = match self {
+            CollectionAction::Save => "Save as",
+            CollectionAction::Rename => "Rename to",
+            CollectionAction::Duplicate => "Duplicate as",
+        };
+        write!(f, "{display_str}")
+    }
+}
+
+/// The name input of the collections tab.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollectionInput {
+    /// The action that is performed on submit
+    pub action: CollectionAction,
+
+    /// The entered name, e.g. `folder/name`
+    pub text: String,
+}
+
+/// Whether an auth header such as `Bearer {{token}}` holds only an
+/// unresolved variable instead of the credential.
+fn is_placeholder(auth: &str) -> bool {
+    let credential = auth
+        .split_once(' ')
+        .map_or(auth, |(_, credential)| credential);
+    credential
+        .trim()
+        .strip_prefix("{{")
+        .and_then(|name| name.strip_suffix("}}"))
+        .is_some_and(|name| !name.contains('{') && !name.contains('}'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::temp_path;
+    use std::collections::BTreeMap;
+
+    fn history_data(message: &str) -> HistoryData {
+        HistoryData::new(
+            message.to_string(),
+            "http://localhost:50051".to_string(),
+            None,
+            BTreeMap::new(),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_parse_entry() {
+        assert_eq!(
+            CollectionEntry::parse("smoke/get user"),
+            Some(CollectionEntry::new(Some("smoke".to_string()), "get user"))
+        );
+        assert_eq!(
+            CollectionEntry::parse(" get user "),
+            Some(CollectionEntry::new(None, "get user"))
+        );
+        assert_eq!(CollectionEntry::parse("a/b/c"), None);
+        assert_eq!(CollectionEntry::parse("smoke/"), None);
+        assert_eq!(CollectionEntry::parse("../name"), None);
+    }
+
+    #[test]
+    fn test_collection_actions() {
+        // given
+        let (_guard, dir) = temp_path("collections");
+        let mut model = CollectionsModel {
+            base_path: dir.clone(),
+            ..CollectionsModel::default()
+        };
+        model.load(Some("proto.TestService.Simple".to_string()));
+        let first = CollectionEntry::new(Some("smoke".to_string()), "first");
+        let second = CollectionEntry::new(None, "second");
+        let copy = CollectionEntry::new(Some("other".to_string()), "copy");
+
+        // when
+        model.save(&first, &history_data("{}")).unwrap();
+        model.duplicate(&first, &second).unwrap();
+        model.rename(&second, &copy).unwrap();
+        let duplicate_exists = model.duplicate(&first, &copy).is_err();
+
+        // then
+        assert_eq!(model.entries, vec![copy.clone(), first.clone()]);
+        assert_eq!(model.selected(), Some(&copy));
+        assert_eq!(model.read(&copy).unwrap(), history_data("{}"));
+        assert!(duplicate_exists);
+        let file = dir.join("proto.TestService.Simple/smoke/first.json");
+        assert!(file.exists());
+
+        // when
+        model.delete(&first).unwrap();
+
+        // then
+        assert_eq!(model.entries, vec![copy]);
+        assert!(!dir.join("proto.TestService.Simple/smoke").exists());
+    }
+
+    #[test]
+    fn test_save_leaves_out_authentication() {
+        // given
+        let (_guard, dir) = temp_path("collections");
+        let mut model = CollectionsModel {
+            base_path: dir.clone(),
+            ..CollectionsModel::default()
+        };
+        model.load(Some("proto.TestService.Simple".to_string()));
+        let secret = CollectionEntry::new(None, "secret");
+        let variable = CollectionEntry::new(None, "variable");
+        let mut data = history_data("{}");
+
+        // when
+        data.authentication = Some("Bearer secret-token".to_string());
+        model.save(&secret, &data).unwrap();
+        data.authentication = Some("Bearer {{token}}".to_string());
+        model.save(&variable, &data).unwrap();
+
+        // then
+        let file = dir.join("proto.TestService.Simple/secret.json");
+        let content = std::fs::read_to_string(file).unwrap();
+        assert!(!content.contains("secret-token"));
+        assert_eq!(model.read(&secret).unwrap().authentication, None);
+        assert_eq!(
+            model.read(&variable).unwrap().authentication,
+            Some("Bearer {{token}}".to_string())
+        );
+        assert!(!is_placeholder("Bearer abc{{token}}"));
+    }
+}
@@ -0,0 +1,519 @@
+#![allow(clippy::module_name_repetitions)]
+use wireman_core::descriptor::kind_name;
+use wireman_core::{FieldDescriptor, Kind, MessageDescriptor};
+
+/// The maximum number of completions that are offered.
+const MAX_COMPLETIONS: usize = 50;
+
+/// A completion that is offered in the request editor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    /// The field name or value shown in the popup
+    pub label: String,
+
+    /// The type and label of a field, e.g. `repeated string`
+    pub detail: String,
+
+    /// The text that replaces the typed prefix
+    pub insert: String,
+}
+
+/// The completion popup of the request editor.
+#[derive(Default)]
+pub struct CompletionModel {
+    /// The offered completions. The popup is open if not empty.
+    items: Vec<Completion>,
+
+    /// The index of the selected completion
+    pub selected: usize,
+
+    /// The number of characters before the cursor that are replaced
+    prefix_len: usize,
+}
+
+impl CompletionModel {
+    /// Whether the popup is open.
+    pub fn is_open(&self) -> bool {
+        !self.items.is_empty()
+    }
+
+    /// Returns the offered completions.
+    pub fn items(&self) -> &[Completion] {
+        &self.items
+    }
+
+    /// Opens the popup with the completions for the text before the cursor,
+    /// or closes it if there are none.
+    pub fn update(&mut self, text: &str, desc: &MessageDescriptor) {
+        let (prefix_len, items) = complete(text, desc);
+        if items.len() != self.items.len() {
+            self.selected = 0;
+        }
+        self.prefix_len = prefix_len;
+        self.items = items;
+    }
+
+    /// Closes the popup.
+    pub fn close(&mut self) {
+        self.items.clear();
+        self.selected = 0;
+    }
+
+    /// Selects the next completion.
+    pub fn next(&mut self) {
+        if self.is_open() {
+            self.selected = (self.selected + 1) % self.items.len();
+        }
+    }
+
+    /// Selects the previous completion.
+    pub fn prev(&mut self) {
+        if self.is_open() {
+            self.selected = (self.selected + self.items.len() - 1) % self.items.len();
+        }
+    }
+
+    /// Returns the selected completion and the number of characters
+    /// before the cursor that it replaces.
+    pub fn selected_item(&self) -> Option<(&Completion, usize)> {
+        self.items
+            .get(self.selected)
+            .map(|item| (item, self.prefix_len))
+    }
+}
+
+/// The type of the JSON object that encloses the cursor.
+#[derive(Clone)]
+enum ObjectType {
+    Message(MessageDescriptor),
+    Map(Kind),
+    Unknown,
+}
+
+/// The type of a JSON value.
+#[derive(Clone)]
+enum ValueType {
+    Single(Kind),
+    List(Kind),
+    Map(Kind),
+    Unknown,
+}
+
+enum Frame {
+    Object {
+        object: ObjectType,
+        key: Option<String>,
+        after_colon: bool,
+    },
+    Array {
+        element: ValueType,
+    },
+}
+
+impl Frame {
+    /// The type of the value that is written next in this frame.
+    fn value_type(&self) -> ValueType {
+        match self {
+            Self::Object {
+                object: ObjectType::Message(desc),
+                key: Some(key),
+                ..
+            } => find_field(desc, key).map_or(ValueType::Unknown, |field| value_type(&field)),
+            Self::Object {
+                object: ObjectType::Map(kind),
+                ..
+            } => ValueType::Single(kind.clone()),
+            Self::Object { .. } => ValueType::Unknown,
+            Self::Array { element } => element.clone(),
+        }
+    }
+
+    fn expects_key(&self) -> bool {
+        matches!(
+            self,
+            Self::Object {
+                after_colon: false,
+                ..
+            }
+        )
+    }
+}
+
+/// The state of the JSON text before the cursor.
+struct Scan {
+    /// The enclosing objects and arrays, the innermost last
+    stack: Vec<Frame>,
+
+    /// Whether the cursor is inside a string
+    in_string: bool,
+
+    /// Whether the cursor is inside a comment
+    in_comment: bool,
+
+    /// Whether the cursor is behind a complete string, object or array
+    after_value: bool,
+
+    /// The content of the string that encloses the cursor
+    string: String,
+}
+
+/// Scans the JSON text before the cursor of a request of the given
+/// message type.
+fn scan(text: &str, desc: &MessageDescriptor) -> Scan {
+    let mut stack: Vec<Frame> = Vec::new();
+    let (mut in_string, mut in_comment, mut escaped) = (false, false, false);
+    let mut after_value = false;
+    let mut string = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_comment {
+            in_comment = c != '\n';
+            continue;
+        }
+        if in_string {
+            if escaped {
+                escaped = false;
+                string.push(c);
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+                after_value = true;
+                if let Some(Frame::Object {
+                    key,
+                    after_colon: false,
+                    ..
+                }) = stack.last_mut()
+                {
+                    *key = Some(std::mem::take(&mut string));
+                }
+            } else {
+                string.push(c);
+            }
+            continue;
+        }
+        if !c.is_whitespace() {
+            after_value = false;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                string.clear();
+            }
+            '/' if chars.peek() == Some(&'/') => in_comment = true,
+            '{' => {
+                let object = match next_value_type(&stack, desc) {
+                    ValueType::Single(Kind::Message(desc)) if !is_well_known_type(&desc) => {
+                        ObjectType::Message(desc)
+                    }
+                    ValueType::Map(kind) => ObjectType::Map(kind),
+                    _ => ObjectType::Unknown,
+                };
+                stack.push(Frame::Object {
+                    object,
+                    key: None,
+                    after_colon: false,
+                });
+            }
+            '[' => {
+                let element = match next_value_type(&stack, desc) {
+                    ValueType::List(kind) => ValueType::Single(kind),
+                    _ => ValueType::Unknown,
+                };
+                stack.push(Frame::Array { element });
+            }
+            '}' | ']' => {
+                stack.pop();
+                after_value = true;
+            }
+            ':' | ',' => {
+                if let Some(Frame::Object {
+                    key, after_colon, ..
+                }) = stack.last_mut()
+                {
+                    *after_colon = c == ':';
+                    if c == ',' {
+                        *key = None;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Scan {
+        stack,
+        in_string,
+        in_comment,
+        after_value,
+        string,
+    }
+}
+
+/// Returns the field whose value or name encloses the cursor in the
+/// JSON text of a request of the given message type. Inside a list,
+/// map or message value, this is the field that holds the value unless
+/// a field of the message itself is being edited.
+pub fn field_at_cursor(text: &str, desc: &MessageDescriptor) -> Option<FieldDescriptor> {
+    let scan = scan(text, desc);
+    if scan.in_comment {
+        return None;
+    }
+    let innermost = scan.stack.len().checked_sub(1)?;
+    for (index, frame) in scan.stack.iter().enumerate().rev() {
+        let Frame::Object {
+            object: ObjectType::Message(desc),
+            key,
+            ..
+        } = frame
+        else {
+            continue;
+        };
+        let key = match key {
+            Some(key) => key,
+            None if index == innermost && scan.in_string => &scan.string,
+            None => continue,
+        };
+        return find_field(desc, key);
+    }
+    None
+}
+
+/// Returns the completions for the JSON text before the cursor of a
+/// request of the given message type, together with the number of
+/// characters before the cursor that they replace.
+pub fn complete(text: &str, desc: &MessageDescriptor) -> (usize, Vec<Completion>) {
+    let Scan {
+        stack,
+        in_string,
+        in_comment,
+        after_value,
+        string,
+    } = scan(text, desc);
+    if in_comment || after_value {
+        return (0, Vec::new());
+    }
+
+    let prefix: String = if in_string {
+        string
+    } else {
+        let mut prefix: Vec<char> = text
+            .chars()
+            .rev()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+            .collect();
+        prefix.reverse();
+        prefix.into_iter().collect()
+    };
+
+    let Some(frame) = stack.last() else {
+        return (0, Vec::new());
+    };
+    let items = if frame.expects_key() {
+        match frame {
+            Frame::Object {
+                object: ObjectType::Message(desc),
+                ..
+            } => field_completions(desc, in_string),
+            _ => Vec::new(),
+        }
+    } else {
+        value_completions(&frame.value_type(), in_string)
+    };
+
+    let items = items
+        .into_iter()
+        .filter(|item| item.label.starts_with(&prefix) && item.label != prefix)
+        .take(MAX_COMPLETIONS)
+        .collect();
+    (prefix.chars().count(), items)
+}
+
+/// The type of the next value in the innermost frame, or of the
+/// request itself if there is no frame.
+fn next_value_type(stack: &[Frame], desc: &MessageDescriptor) -> ValueType {
+    match stack.last() {
+        Some(frame) => frame.value_type(),
+        None => ValueType::Single(Kind::Message(desc.clone())),
+    }
+}
+
+/// Looks up a field by its JSON or proto name.
+fn find_field(desc: &MessageDescriptor, name: &str) -> Option<FieldDescriptor> {
+    desc.get_field_by_json_name(name)
+        .or_else(|| desc.get_field_by_name(name))
+}
+
+fn value_type(field: &FieldDescriptor) -> ValueType {
+    let kind = field.kind();
+    if field.is_map() {
+        kind.as_message().map_or(ValueType::Unknown, |entry| {
+            ValueType::Map(entry.map_entry_value_field().kind())
+        })
+    } else if field.is_list() {
+        ValueType::List(kind)
+    } else {
+        ValueType::Single(kind)
+    }
+}
+
+fn is_well_known_type(desc: &MessageDescriptor) -> bool {
+    desc.package_name() == "google.protobuf"
+}
+
+/// The field names of a message, both as JSON and as proto names.
+fn field_completions(desc: &MessageDescriptor, in_string: bool) -> Vec<Completion> {
+    let mut items = Vec::new();
+    for field in desc.fields() {
+        let detail = field_detail(&field);
+        let mut names = vec![field.json_name().to_string()];
+        if field.name() != field.json_name() {
+            names.push(field.name().to_string());
+        }
+        for name in names {
+            let insert = if in_string {
+                name.clone()
+            } else {
+                format!("\"{name}\": ")
+            };
+            items.push(Completion {
+                label: name,
+                detail: detail.clone(),
+                insert,
+            });
+        }
+    }
+    items
+}
+
+/// The enum value names, booleans and `null` that fit a value.
+fn value_completions(value: &ValueType, in_string: bool) -> Vec<Completion> {
+    let mut items = Vec::new();
+    match value {
+        ValueType::Single(Kind::Enum(desc)) => {
+            for value in desc.values() {
+                let name = value.name().to_string();
+                let insert = if in_string {
+                    name.clone()
+                } else {
+                    format!("\"{name}\"")
+                };
+                items.push(Completion {
+                    label: name,
+                    detail: desc.full_name().to_string(),
+                    insert,
+                });
+            }
+        }
+        ValueType::Single(Kind::Bool) if !in_string => {
+            for value in ["true", "false"] {
+                items.push(Completion {
+                    label: value.to_string(),
+                    detail: "bool".to_string(),
+                    insert: value.to_string(),
+                });
+            }
+        }
+        _ => {}
+    }
+    if !in_string && !matches!(value, ValueType::Unknown) {
+        items.push(Completion {
+            label: "null".to_string(),
+            detail: "default value".to_string(),
+            insert: "null".to_string(),
+        });
+    }
+    items
+}
+
+/// Describes the type and label of a field, e.g. `repeated string`.
+pub fn field_detail(field: &FieldDescriptor) -> String {
+    let kind = field.kind();
+    if field.is_map() {
+        if let Some(entry) = kind.as_message() {
+            return format!(
+                "map<{}, {}>",
+                kind_name(&entry.map_entry_key_field().kind()),
+                kind_name(&entry.map_entry_value_field().kind())
+            );
+        }
+    }
+    let name = kind_name(&kind);
+    if field.is_list() {
+        return format!("repeated {name}");
+    }
+    match field.containing_oneof() {
+        Some(oneof) if oneof.is_synthetic() => format!("optional {name}"),
+        Some(oneof) => format!("{name} (oneof {})", oneof.name()),
+        None => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wireman_core::ProtoDescriptor;
+
+    fn load_input(file: &str, method: &str) -> MessageDescriptor {
+        let desc = ProtoDescriptor::new(["../wireman-core/test_files"], [file]).unwrap();
+        desc.get_method_by_name("proto.TestService", method)
+            .unwrap()
+            .input()
+    }
+
+    fn labels(items: &[Completion]) -> Vec<&str> {
+        items.iter().map(|item| item.label.as_str()).collect()
+    }
+
+    #[test]
+    fn test_complete() {
+        // given
+        let nested = load_input("test.proto", "Nested");
+        let enums = load_input("test.proto", "Enum");
+        let oneof = load_input("oneof.proto", "Oneof");
+        let types = load_input("types.proto", "Types");
+
+        // when
+        let (_, keys) = complete("{\n  ", &oneof);
+        let (prefix_len, in_string) = complete("{\n  \"fi", &oneof);
+        let (_, type_keys) = complete("{\n  ", &types);
+        let (_, nested_keys) = complete("{\"items\": [{\"number\": 1, ", &nested);
+        let (_, values) = complete("{\"color\": ", &enums);
+        let (_, closed) = complete("{\"items\": [{}], ", &nested);
+        let (_, after_string) = complete("{\"text\": \"a\"", &nested);
+
+        // then
+        assert_eq!(labels(&keys), vec!["first", "last"]);
+        assert_eq!(keys[0].insert, "\"first\": ");
+        assert_eq!(keys[0].detail, "string (oneof value)");
+        assert_eq!(type_keys[0].detail, "map<string, int32>");
+        assert_eq!(prefix_len, 2);
+        assert_eq!(labels(&in_string), vec!["first"]);
+        assert_eq!(in_string[0].insert, "first");
+        assert_eq!(labels(&nested_keys), vec!["number", "text"]);
+        assert_eq!(labels(&values), vec!["NONE", "RED", "BLUE", "null"]);
+        assert_eq!(values[1].insert, "\"RED\"");
+        assert_eq!(labels(&closed), vec!["items"]);
+        assert!(after_string.is_empty());
+    }
+
+    #[test]
+    fn test_field_at_cursor() {
+        // given
+        let nested = load_input("test.proto", "Nested");
+        let name = |field: Option<FieldDescriptor>| field.map(|f| f.name().to_string());
+
+        // when
+        let items = field_at_cursor("{\"items\": [", &nested);
+        let number = field_at_cursor("{\"items\": [{\"number\": 1", &nested);
+        let in_key = field_at_cursor("{\"items\": [{\"text", &nested);
+        let between = field_at_cursor("{\"items\": [{\"number\": 1, ", &nested);
+        let top = field_at_cursor("{\n  ", &nested);
+
+        // then
+        assert_eq!(name(items).as_deref(), Some("items"));
+        assert_eq!(name(number).as_deref(), Some("number"));
+        assert_eq!(name(in_key).as_deref(), Some("text"));
+        assert_eq!(name(between).as_deref(), Some("items"));
+        assert_eq!(name(top), None);
+    }
+}
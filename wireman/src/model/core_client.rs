@@ -1,10 +1,21 @@
 use crate::widgets::editor::ErrorKind;
 use http::Uri;
-use std::{collections::HashMap, error::Error};
-use wireman_config::Config;
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fmt,
+};
+use wireman_config::{
+    config::{EnvironmentConfig, JsonConfig, Transport as TransportConfig},
+    Config,
+};
 use wireman_core::{
-    client::tls::TlsConfig,
-    descriptor::{response::StreamingResponse, RequestMessage, ResponseMessage},
+    client::{
+        channel::{ConnectionState, GrpcChannel, Transport},
+        tls::TlsConfig,
+        GrpcClient,
+    },
+    descriptor::{JsonOptions, RequestMessage},
     features::grpcurl,
     MethodDescriptor, ProtoDescriptor, ServiceDescriptor,
 };
@@ -13,16 +24,28 @@ use wireman_core::{
 /// core package.
 #[derive(Debug, Clone)]
 pub struct CoreClient {
-    /// The proto descriptor
+    /// The proto descriptor of the local protos and protosets
     desc: ProtoDescriptor,
+    /// The proto descriptors of reflected servers, keyed by address
+    reflected: BTreeMap<String, ProtoDescriptor>,
     /// Config to create a new grpc client
     grpc: GrpcClientConfig,
     /// Proto path includes
     includes: Vec<String>,
     /// Proto files
     files: Vec<String>,
+    /// Precompiled `FileDescriptorSet` files
+    protosets: Vec<String>,
+    /// The JSON serialization profile
+    json_options: JsonOptions,
+    /// The selected environment
+    environment: Option<String>,
+    /// The open channels, keyed by address, tls config and transport
+    channels: HashMap<ChannelKey, GrpcChannel>,
 }
 
+type ChannelKey = (String, Option<TlsConfig>, Transport);
+
 impl Default for CoreClient {
     fn default() -> Self {
         Self::new(&Config::default()).unwrap()
@@ -36,33 +59,78 @@ impl CoreClient {
     pub fn new(cfg: &Config) -> Result<Self, Box<dyn Error>> {
         let includes = cfg.includes();
         let files = cfg.files();
-        let desc = ProtoDescriptor::new(includes.clone(), files.clone())?;
+        let protosets = cfg.protosets();
+        let desc = load_descriptor(&includes, &files, &protosets)?;
         let grpc = GrpcClientConfig(cfg.clone());
         Ok(Self {
             desc,
+            reflected: BTreeMap::new(),
             grpc,
             includes,
             files,
+            protosets,
+            json_options: json_options(&cfg.json),
+            environment: None,
+            channels: HashMap::new(),
         })
     }
 
-    pub fn update_proto_descriptor(&mut self, desc: ProtoDescriptor) {
-        self.desc = desc;
+    /// Adds the descriptor of a reflected server. Replaces the previous
+    /// descriptor of the same address.
+    pub fn add_reflection(&mut self, address: &str, desc: ProtoDescriptor) {
+        self.reflected.insert(address.to_string(), desc);
+    }
+
+    /// Removes the descriptors of all reflected servers.
+    pub fn clear_reflection(&mut self) {
+        self.reflected.clear();
+    }
+
+    /// Whether the descriptor of any server was reflected.
+    pub fn has_reflection(&self) -> bool {
+        !self.reflected.is_empty()
     }
 
     pub fn reset(&mut self) -> Result<(), Box<dyn Error>> {
-        self.desc = ProtoDescriptor::new(self.includes.clone(), self.files.clone())?;
+        self.desc = load_descriptor(&self.includes, &self.files, &self.protosets)?;
         Ok(())
     }
 
-    /// Return the proto Services
+    /// The descriptors in order of precedence: the local protos first,
+    /// then the reflected servers ordered by address.
+    fn sources(&self) -> impl Iterator<Item = (ServiceSource, &ProtoDescriptor)> {
+        std::iter::once((ServiceSource::Local, &self.desc)).chain(
+            self.reflected
+                .iter()
+                .map(|(address, desc)| (ServiceSource::Reflection(address.clone()), desc)),
+        )
+    }
+
+    /// Return the proto Services of all sources. A service that is
+    /// defined by several sources is taken from the first source.
     pub fn get_services(&self) -> Vec<ServiceDescriptor> {
-        self.desc.get_services()
+        let mut services: BTreeMap<String, ServiceDescriptor> = BTreeMap::new();
+        for (_, desc) in self.sources() {
+            for service in desc.get_services() {
+                services
+                    .entry(service.full_name().to_string())
+                    .or_insert(service);
+            }
+        }
+        services.into_values().collect()
     }
 
     /// Return a proto services by its name
     pub fn get_service_by_name(&self, name: &str) -> Option<ServiceDescriptor> {
-        self.desc.get_service_by_name(name)
+        self.sources()
+            .find_map(|(_, desc)| desc.get_service_by_name(name))
+    }
+
+    /// Returns where a service is defined
+    pub fn get_service_source(&self, name: &str) -> Option<ServiceSource> {
+        self.sources()
+            .find(|(_, desc)| desc.get_service_by_name(name).is_some())
+            .map(|(source, _)| source)
     }
 
     /// Returns the proto methods of a given service
@@ -76,7 +144,9 @@ impl CoreClient {
         service_name: &str,
         method_name: &str,
     ) -> Option<MethodDescriptor> {
-        self.desc.get_method_by_name(service_name, method_name)
+        self.get_service_by_name(service_name)?
+            .methods()
+            .find(|m| m.name() == method_name)
     }
 
     /// Returns the proto request of a given method
@@ -86,75 +156,158 @@ impl CoreClient {
         req
     }
 
-    /// Returns the default address as defined in the wireman.toml
+    /// Returns the JSON serialization profile
+    pub fn json_options(&self) -> JsonOptions {
+        self.json_options
+    }
+
+    /// Sets the JSON serialization profile
+    pub fn set_json_options(&mut self, options: JsonOptions) {
+        self.json_options = options;
+    }
+
+    /// Returns the names of the environments defined in the wireman.toml
+    pub fn get_environments(&self) -> Vec<String> {
+        self.grpc.0.environments.keys().cloned().collect()
+    }
+
+    /// Returns the selected environment
+    pub fn get_environment(&self) -> Option<String> {
+        self.environment.clone()
+    }
+
+    /// Selects an environment. Unknown environments are ignored.
+    pub fn set_environment(&mut self, environment: Option<String>) {
+        self.environment = environment.filter(|name| self.grpc.0.environments.contains_key(name));
+    }
+
+    /// Returns the config of the selected environment
+    fn environment_config(&self) -> Option<&EnvironmentConfig> {
+        self.environment
+            .as_ref()
+            .and_then(|name| self.grpc.0.environments.get(name))
+    }
+
+    /// Replaces the `{{var}}` placeholders with the variables of the
+    /// selected environment.
+    pub fn substitute(&self, text: &str) -> String {
+        match self.environment_config() {
+            Some(env) => env.substitute(text),
+            None => text.to_string(),
+        }
+    }
+
+    /// Returns the default address as defined in the wireman.toml. The
+    /// address of the selected environment takes precedence.
     pub fn get_default_address(&self) -> String {
-        self.grpc
-            .0
-            .server
-            .default_address
-            .clone()
+        self.environment_config()
+            .and_then(|env| env.address.clone())
+            .or_else(|| self.grpc.0.server.default_address.clone())
             .unwrap_or_default()
     }
 
-    /// Returns the default auth header as defined in the wireman.toml
+    /// Returns the default auth header as defined in the wireman.toml. The
+    /// auth header of the selected environment takes precedence.
     pub fn get_default_auth_header(&self) -> String {
+        self.environment_config()
+            .and_then(|env| env.auth_header.clone())
+            .or_else(|| self.grpc.0.server.default_auth_header.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the default timeout as defined in the wireman.toml
+    pub fn get_default_timeout(&self) -> String {
         self.grpc
             .0
             .server
-            .default_auth_header
+            .default_timeout
             .clone()
             .unwrap_or_default()
     }
 
+    /// Returns the default send encoding and the comma separated accepted
+    /// encodings as defined in the wireman.toml
+    pub fn get_default_compression(&self) -> (String, String) {
+        let server = &self.grpc.0.server;
+        let send = server.default_send_encoding.clone().unwrap_or_default();
+        (send, server.default_accept_encodings.join(", "))
+    }
+
     /// Returns the tls config.
-    pub fn get_tls_config(&self) -> Option<TlsConfig> {
-        let tls_config = self.grpc.0.tls.clone();
-        match (tls_config.use_native, tls_config.custom_cert) {
+    ///
+    /// # Errors
+    /// - Failed to load the certificates or the client key.
+    pub fn get_tls_config(&self) -> Result<Option<TlsConfig>, ErrorKind> {
+        let tls_config = self
+            .environment_config()
+            .and_then(|env| env.tls.clone())
+            .unwrap_or_else(|| self.grpc.0.tls.clone());
+        let mut tls = match (tls_config.use_native, tls_config.custom_cert) {
             (Some(use_native), _) => {
-                if use_native {
-                    return Some(TlsConfig::native());
+                if !use_native {
+                    return Ok(None);
                 }
-                None
+                TlsConfig::native()
+            }
+            (None, Some(custom)) => TlsConfig::custom(custom).map_err(config_error)?,
+            _ => TlsConfig::native(),
+        };
+        match (tls_config.client_cert, tls_config.client_key) {
+            (Some(cert), Some(key)) => {
+                tls = tls.with_client_identity(cert, key).map_err(config_error)?;
+            }
+            (None, None) => {}
+            _ => {
+                return Err(ErrorKind::config_error(String::from(
+                    "client_cert and client_key must be set together",
+                )))
             }
-            (None, Some(custom)) => Some(TlsConfig::custom(custom).unwrap()),
-            _ => Some(TlsConfig::native()),
         }
+        if let Some(domain_name) = tls_config.domain_name {
+            tls = tls.with_domain_name(domain_name);
+        }
+        let skip = tls_config.skip_hostname_verification.unwrap_or_default();
+        Ok(Some(tls.with_skip_hostname_verification(skip)))
     }
 
-    pub async fn call_unary_async(
-        req: &RequestMessage,
-        tls: Option<TlsConfig>,
-    ) -> Result<ResponseMessage, ErrorKind> {
-        Ok(wireman_core::client::call_unary_async(req, tls).await?)
-    }
-
-    pub async fn call_server_streaming(
+    /// Returns a client that reuses the channel of the request address,
+    /// the tls config and the transport. A channel is kept open for each
+    /// address. If the tls config or the transport of an address changed,
+    /// its previous channel is closed and a new channel is opened.
+    ///
+    /// # Errors
+    /// - Failed to parse the address or to build the tls config.
+    pub fn get_client(
+        &mut self,
         req: &RequestMessage,
         tls: Option<TlsConfig>,
-    ) -> Result<StreamingResponse, ErrorKind> {
-        Ok(wireman_core::client::call_server_streaming(req, tls).await?)
+    ) -> Result<GrpcClient, ErrorKind> {
+        let transport = self.get_transport(req.address());
+        let key = (req.address().to_string(), tls, transport);
+        if let Some(channel) = self.channels.get(&key) {
+            return Ok(GrpcClient::from_channel(channel));
+        }
+        let channel = GrpcChannel::with_transport(req.target()?, key.1.clone(), transport)?;
+        self.channels.retain(|(address, _, _), _| *address != key.0);
+        let client = GrpcClient::from_channel(&channel);
+        self.channels.insert(key, channel);
+        Ok(client)
     }
 
-    pub async fn call_client_streaming<S>(
-        head: &RequestMessage,
-        messages: S,
-        tls: Option<TlsConfig>,
-    ) -> Result<ResponseMessage, ErrorKind>
-    where
-        S: futures::Stream<Item = RequestMessage> + Send + 'static,
-    {
-        Ok(wireman_core::client::call_client_streaming(head, messages, tls).await?)
+    /// Returns the configured transport of an address.
+    pub fn get_transport(&self, address: &str) -> Transport {
+        transport(self.grpc.0.server.transport(address))
     }
 
-    pub async fn call_bidirectional_streaming<S>(
-        head: &RequestMessage,
-        messages: S,
-        tls: Option<TlsConfig>,
-    ) -> Result<StreamingResponse, ErrorKind>
-    where
-        S: futures::Stream<Item = RequestMessage> + Send + 'static,
-    {
-        Ok(wireman_core::client::call_bidirectional_streaming(head, messages, tls).await?)
+    /// Returns the connection state of the channel for the address.
+    /// The address may contain environment variables.
+    pub fn connection_state(&self, address: &str) -> ConnectionState {
+        let address = self.substitute(address);
+        self.channels
+            .iter()
+            .find(|((addr, _, _), _)| *addr == address)
+            .map(|(_, channel)| channel.state())
+            .unwrap_or_default()
     }
 
     /// Return a grpcurl request
@@ -171,6 +324,149 @@ impl CoreClient {
             message,
             method_desc,
             metadata,
+            &self.json_options,
         ))
     }
 }
+
+/// Where a service is defined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServiceSource {
+    /// The local protos and protosets
+    Local,
+    /// A server reflected at the address
+    Reflection(String),
+}
+
+impl fmt::Display for ServiceSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Local => write!(f, "local"),
+            Self::Reflection(address) => write!(f, "{address}"),
+        }
+    }
+}
+
+/// Compiles the proto files and merges the precompiled protosets.
+fn load_descriptor(
+    includes: &[String],
+    files: &[String],
+    protosets: &[String],
+) -> wireman_core::Result<ProtoDescriptor> {
+    let mut desc = ProtoDescriptor::new(includes, files)?;
+    desc.add_protosets(protosets)?;
+    Ok(desc)
+}
+
+/// Maps the configured JSON profile to the options of the core.
+fn json_options(cfg: &JsonConfig) -> JsonOptions {
+    JsonOptions {
+        skip_default_fields: cfg.skip_default_fields,
+        enums_as_numbers: cfg.enums_as_numbers,
+        proto_field_names: cfg.proto_field_names,
+        stringify_64_bit_integers: cfg.stringify_64_bit_integers,
+        deny_unknown_fields: cfg.deny_unknown_fields,
+        emit_defaults: cfg.emit_defaults,
+    }
+}
+
+/// Maps the configured transport to the transport of the core.
+fn transport(cfg: TransportConfig) -> Transport {
+    match cfg {
+        TransportConfig::Grpc => Transport::Grpc,
+        TransportConfig::GrpcWeb => Transport::GrpcWeb,
+        TransportConfig::GrpcWebText => Transport::GrpcWebText,
+    }
+}
+
+/// Converts a core error into a config error, including its source.
+fn config_error(err: wireman_core::error::Error) -> ErrorKind {
+    match err.source() {
+        Some(source) => ErrorKind::config_error(format!("{err}: {source}")),
+        None => ErrorKind::config_error(err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_test_file(file: &str) -> ProtoDescriptor {
+        ProtoDescriptor::new(["../wireman-core/test_files"], [file]).unwrap()
+    }
+
+    #[test]
+    fn test_merge_reflection() {
+        // given
+        let mut client = CoreClient::default();
+        let service = "proto.TestService";
+
+        // when
+        client.add_reflection("http://b:50051", load_test_file("oneof.proto"));
+        client.add_reflection("http://a:50051", load_test_file("recursive.proto"));
+
+        // then
+        assert_eq!(client.get_services().len(), 1);
+        assert_eq!(
+            client.get_service_source(service),
+            Some(ServiceSource::Reflection("http://a:50051".to_string()))
+        );
+        assert!(client.get_method_by_name(service, "Recursive").is_some());
+        assert!(client.get_method_by_name(service, "Oneof").is_none());
+
+        // when
+        client.desc = load_test_file("test.proto");
+
+        // then
+        assert_eq!(client.get_services().len(), 1);
+        assert_eq!(
+            client.get_service_source(service),
+            Some(ServiceSource::Local)
+        );
+        assert!(client.get_method_by_name(service, "Simple").is_some());
+
+        // when
+        client.clear_reflection();
+
+        // then
+        assert!(!client.has_reflection());
+        assert_eq!(client.get_service_source("proto.Unknown"), None);
+    }
+
+    #[test]
+    fn test_get_client_keeps_channels() {
+        // given
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let _guard = runtime.enter();
+        let mut client = CoreClient::default();
+        let method = load_test_file("test.proto")
+            .get_method_by_name("proto.TestService", "Simple")
+            .unwrap();
+        let mut req = RequestMessage::new(method.input(), method);
+
+        // when
+        req.set_address("http://localhost:50051");
+        client.get_client(&req, None).unwrap();
+        req.set_address("http://localhost:50052");
+        client.get_client(&req, None).unwrap();
+        req.set_address("http://localhost:50051");
+        client.get_client(&req, None).unwrap();
+
+        // then
+        assert_eq!(client.channels.len(), 2);
+
+        // when
+        client.get_client(&req, Some(TlsConfig::native())).unwrap();
+
+        // then
+        assert_eq!(client.channels.len(), 2);
+        assert!(client.channels.contains_key(&(
+            "http://localhost:50051".to_string(),
+            Some(TlsConfig::native()),
+            Transport::Grpc
+        )));
+    }
+}
@@ -1,11 +1,17 @@
 pub mod auth;
 pub use auth::{AuthHeader, AuthSelection};
+pub mod compression;
+pub use compression::{CompressionHeader, CompressionSelection};
 pub mod meta;
-use crate::{model::history::HistoryModel, widgets::editor::TextEditor};
+use crate::{
+    model::history::HistoryModel,
+    widgets::editor::{ErrorKind, TextEditor},
+};
 use edtui::EditorMode;
 pub use meta::MetaHeaders;
 use ratatui::prelude::Rect;
-use std::{cell::RefCell, collections::HashMap, process::Command, rc::Rc};
+use std::{cell::RefCell, collections::HashMap, process::Command, rc::Rc, time::Duration};
+use wireman_config::parse_duration;
 use wireman_core::MethodDescriptor;
 
 /// The data model for the `gRPC` headers. Contains authorization
@@ -14,9 +20,15 @@ pub struct HeadersModel {
     /// The host address.
     pub addr: TextEditor,
 
+    /// The deadline of the request.
+    pub timeout: TextEditor,
+
     /// The authentication header.
     pub auth: AuthHeader,
 
+    /// The compression settings.
+    pub compression: CompressionHeader,
+
     /// The metadata headers.
     pub meta: MetaHeaders,
 
@@ -35,18 +47,34 @@ pub struct HeadersModel {
     /// The default auth header
     pub default_auth_header: String,
 
+    /// The default timeout
+    pub default_timeout: String,
+
+    /// The default send encoding and accepted encodings
+    pub default_compression: (String, String),
+
+    /// The names of the configured environments
+    pub environments: Vec<String>,
+
+    /// The selected environment
+    pub environment: Option<String>,
+
     /// UI hit-test areas for headers sections
     pub addr_title_area: Option<Rect>,
     pub addr_content_area: Option<Rect>,
+    pub timeout_title_area: Option<Rect>,
+    pub timeout_content_area: Option<Rect>,
     pub auth_title_area: Option<Rect>,
     pub auth_content_area: Option<Rect>,
+    pub compression_title_area: Option<Rect>,
+    pub compression_content_area: Option<Rect>,
     pub meta_title_area: Option<Rect>,
     pub meta_content_area: Option<Rect>,
 }
 
 impl Default for HeadersModel {
     fn default() -> Self {
-        Self::new("", "", Rc::new(RefCell::new(HistoryModel::default())))
+        Self::new("", "", "", Rc::new(RefCell::new(HistoryModel::default())))
     }
 }
 
@@ -55,25 +83,38 @@ impl HeadersModel {
     pub fn new(
         default_address: &str,
         default_auth_header: &str,
+        default_timeout: &str,
         history: Rc<RefCell<HistoryModel>>,
     ) -> Self {
         let mut address = TextEditor::single();
         address.set_text_raw(default_address);
+        let mut timeout = TextEditor::single();
+        timeout.set_text_raw(default_timeout);
         let mut auth_header = AuthHeader::default();
         auth_header.set_text(default_auth_header);
         Self {
             addr: address,
+            timeout,
             auth: auth_header,
+            compression: CompressionHeader::default(),
             meta: MetaHeaders::default(),
             tab: HeadersTab::default(),
             selected_method: None,
             history,
             default_auth_header: default_auth_header.to_string(),
             default_address: default_address.to_string(),
+            default_timeout: default_timeout.to_string(),
+            default_compression: (String::new(), String::new()),
+            environments: Vec::new(),
+            environment: None,
             addr_title_area: None,
             addr_content_area: None,
+            timeout_title_area: None,
+            timeout_content_area: None,
             auth_title_area: None,
             auth_content_area: None,
+            compression_title_area: None,
+            compression_content_area: None,
             meta_title_area: None,
             meta_content_area: None,
         }
@@ -84,6 +125,62 @@ impl HeadersModel {
         self.addr.get_text_raw()
     }
 
+    /// Get the parsed deadline. Returns `None` if no timeout is set.
+    ///
+    /// # Errors
+    /// - Failed to parse the timeout.
+    pub fn timeout(&self) -> Result<Option<Duration>, ErrorKind> {
+        let raw = self.timeout.get_text_raw();
+        if raw.trim().is_empty() {
+            return Ok(None);
+        }
+        parse_duration(&raw)
+            .map(Some)
+            .ok_or_else(|| ErrorKind::format_error(format!("invalid timeout: {raw}")))
+    }
+
+    /// Sets the default send encoding and the comma separated
+    /// accepted encodings.
+    pub fn set_default_compression(&mut self, send: &str, accept: &str) {
+        self.default_compression = (send.to_string(), accept.to_string());
+        self.compression.set_text(send, accept);
+    }
+
+    /// Sets the names of the configured environments
+    pub fn set_environments(&mut self, environments: Vec<String>) {
+        self.environments = environments;
+    }
+
+    /// Selects the next environment. Cycles back to no environment
+    /// after the last one.
+    pub fn next_environment(&mut self) -> Option<String> {
+        let next = match &self.environment {
+            None => self.environments.first(),
+            Some(current) => self
+                .environments
+                .iter()
+                .position(|name| name == current)
+                .and_then(|index| self.environments.get(index + 1)),
+        };
+        self.environment = next.cloned();
+        self.environment.clone()
+    }
+
+    /// Replaces the default address and auth header, e.g. after the
+    /// environment was switched. An address or auth header that was
+    /// edited by the user is kept.
+    pub fn set_defaults(&mut self, default_address: &str, default_auth_header: &str) {
+        if self.address() == self.default_address {
+            self.addr.set_text_raw(default_address);
+        }
+        if self.auth.value() == self.default_auth_header {
+            self.auth.clear();
+            self.auth.set_text(default_auth_header);
+        }
+        self.default_address = default_address.to_string();
+        self.default_auth_header = default_auth_header.to_string();
+    }
+
     /// Sets the selected method
     pub fn set_method(&mut self, method: &MethodDescriptor) {
         self.selected_method = Some(method.clone());
@@ -93,7 +190,9 @@ impl HeadersModel {
     pub fn selected_editor<'b, 'a: 'b>(&'a self) -> Option<&'b TextEditor> {
         match self.tab {
             HeadersTab::Addr => Some(&self.addr),
+            HeadersTab::Timeout => Some(&self.timeout),
             HeadersTab::Auth => Some(self.auth.selected_editor()),
+            HeadersTab::Compression => Some(self.compression.selected_editor()),
             HeadersTab::Meta => self.meta.selected_editor(),
             HeadersTab::None => None,
         }
@@ -103,7 +202,9 @@ impl HeadersModel {
     pub fn selected_editor_mut<'b, 'a: 'b>(&'a mut self) -> Option<&'b mut TextEditor> {
         match self.tab {
             HeadersTab::Addr => Some(&mut self.addr),
+            HeadersTab::Timeout => Some(&mut self.timeout),
             HeadersTab::Auth => Some(self.auth.selected_editor_mut()),
+            HeadersTab::Compression => Some(self.compression.selected_editor_mut()),
             HeadersTab::Meta => self.meta.selected_editor_mut(),
             HeadersTab::None => None,
         }
@@ -116,10 +217,16 @@ impl HeadersModel {
 
     /// Returns the editor mode
     pub fn mode(&self) -> EditorMode {
-        [self.auth.mode(), self.addr.state.mode, self.meta.mode()]
-            .into_iter()
-            .find(|&x| x != EditorMode::Normal)
-            .unwrap_or(EditorMode::Normal)
+        [
+            self.auth.mode(),
+            self.addr.state.mode,
+            self.timeout.state.mode,
+            self.compression.mode(),
+            self.meta.mode(),
+        ]
+        .into_iter()
+        .find(|&x| x != EditorMode::Normal)
+        .unwrap_or(EditorMode::Normal)
     }
 
     /// Get the raw headers as a map
@@ -177,8 +284,10 @@ impl HeadersModel {
     pub fn next_tab(&mut self) -> HeadersTab {
         match self.tab {
             HeadersTab::None => HeadersTab::Addr,
-            HeadersTab::Addr => HeadersTab::Auth,
-            HeadersTab::Auth => {
+            HeadersTab::Addr => HeadersTab::Timeout,
+            HeadersTab::Timeout => HeadersTab::Auth,
+            HeadersTab::Auth => HeadersTab::Compression,
+            HeadersTab::Compression => {
                 if self.meta.is_hidden() {
                     return HeadersTab::Addr;
                 }
@@ -196,17 +305,19 @@ impl HeadersModel {
     /// TODO: Simplify
     pub fn prev_tab(&mut self) -> HeadersTab {
         match self.tab {
-            HeadersTab::None | HeadersTab::Auth => HeadersTab::Addr,
+            HeadersTab::None | HeadersTab::Timeout => HeadersTab::Addr,
+            HeadersTab::Auth => HeadersTab::Timeout,
+            HeadersTab::Compression => HeadersTab::Auth,
             HeadersTab::Addr => {
                 if self.meta.is_hidden() {
-                    return HeadersTab::Auth;
+                    return HeadersTab::Compression;
                 }
                 self.meta.select_last();
                 HeadersTab::Meta
             }
             HeadersTab::Meta => {
                 self.meta.unselect();
-                HeadersTab::Auth
+                HeadersTab::Compression
             }
         }
     }
@@ -231,6 +342,7 @@ impl HeadersModel {
         match self.tab {
             HeadersTab::Meta => self.meta.next_col(),
             HeadersTab::Auth => self.auth.next(),
+            HeadersTab::Compression => self.compression.selected = CompressionSelection::Accept,
             _ => (),
         }
     }
@@ -239,6 +351,7 @@ impl HeadersModel {
         match self.tab {
             HeadersTab::Meta => self.meta.prev_col(),
             HeadersTab::Auth => self.auth.next(),
+            HeadersTab::Compression => self.compression.selected = CompressionSelection::Send,
             _ => (),
         }
     }
@@ -247,6 +360,9 @@ impl HeadersModel {
     pub fn clear(&mut self) {
         self.auth.clear();
         self.auth.set_text(&self.default_auth_header);
+        self.timeout.set_text_raw(&self.default_timeout);
+        let (send, accept) = &self.default_compression;
+        self.compression.set_text(send, accept);
         self.meta.clear();
         self.meta.add();
         self.tab = HeadersTab::None;
@@ -259,11 +375,14 @@ pub enum HeadersTab {
     #[default]
     None,
     Addr,
+    Timeout,
     Auth,
+    Compression,
     Meta,
 }
 
-fn try_expand(raw: &str) -> String {
+/// Expands `$(command)` to the output of the command.
+pub(crate) fn try_expand(raw: &str) -> String {
     if raw.starts_with("$(") && raw.ends_with(')') {
         let command = &raw[2..raw.len() - 1];
         if let Some(command) = execute_command(command) {
@@ -290,3 +409,32 @@ fn execute_command(command: &str) -> Option<String> {
     };
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_defaults_keeps_edits() {
+        // given
+        let history = Rc::new(RefCell::new(HistoryModel::default()));
+        let mut headers = HeadersModel::new("http://local:50051", "Bearer local", "", history);
+        headers.auth.set_text("Bearer edited");
+
+        // when
+        headers.set_defaults("http://staging:50051", "Bearer staging");
+
+        // then
+        assert_eq!(headers.address(), "http://staging:50051");
+        assert_eq!(headers.auth.value(), "Bearer edited");
+
+        // when
+        headers.addr.set_text_raw("http://edited:50051");
+        headers.auth.set_text("Bearer staging");
+        headers.set_defaults("http://prod:50051", "Bearer prod");
+
+        // then
+        assert_eq!(headers.address(), "http://edited:50051");
+        assert_eq!(headers.auth.value(), "Bearer prod");
+    }
+}
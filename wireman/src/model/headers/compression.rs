@@ -0,0 +1,71 @@
+use crate::widgets::editor::{ErrorKind, TextEditor};
+use edtui::EditorMode;
+use wireman_core::client::compression::Compression;
+
+/// The compression settings of a request: the encoding of the sent
+/// messages and the accepted encodings of the response.
+pub struct CompressionHeader {
+    pub(crate) send: TextEditor,
+    pub(crate) accept: TextEditor,
+    pub(crate) selected: CompressionSelection,
+}
+
+impl Default for CompressionHeader {
+    fn default() -> Self {
+        Self {
+            send: TextEditor::single(),
+            accept: TextEditor::single(),
+            selected: CompressionSelection::Send,
+        }
+    }
+}
+
+/// The selection state of `CompressionHeader`.
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub enum CompressionSelection {
+    #[default]
+    Send,
+    Accept,
+}
+
+impl CompressionHeader {
+    pub fn mode(&self) -> EditorMode {
+        self.selected_editor().state.mode
+    }
+
+    /// Get the raw send encoding and accepted encodings.
+    pub fn raw(&self) -> (String, String) {
+        (self.send.get_text_raw(), self.accept.get_text_raw())
+    }
+
+    /// Sets the send encoding and the comma separated accepted encodings.
+    pub fn set_text(&mut self, send: &str, accept: &str) {
+        self.send.set_text_raw(send);
+        self.accept.set_text_raw(accept);
+    }
+
+    /// Get the parsed compression settings.
+    ///
+    /// # Errors
+    /// - Failed to parse an encoding.
+    pub fn compression(&self) -> Result<Compression, ErrorKind> {
+        let (send, accept) = self.raw();
+        Compression::parse(&send, &accept).map_err(|err| ErrorKind::format_error(err.to_string()))
+    }
+
+    /// Get the selected editor
+    pub fn selected_editor<'b, 'a: 'b>(&'a self) -> &'b TextEditor {
+        match self.selected {
+            CompressionSelection::Send => &self.send,
+            CompressionSelection::Accept => &self.accept,
+        }
+    }
+
+    /// Get the selected editor
+    pub fn selected_editor_mut<'b, 'a: 'b>(&'a mut self) -> &'b mut TextEditor {
+        match self.selected {
+            CompressionSelection::Send => &mut self.send,
+            CompressionSelection::Accept => &mut self.accept,
+        }
+    }
+}
@@ -81,7 +81,7 @@ impl HistoryModel {
             return;
         };
 
-        let Ok(message) = messages.request.editor.get_text_json() else {
+        let Some(request) = HistoryData::from_messages(messages) else {
             Logger::debug("history: failed to parse request");
             return;
         };
@@ -100,17 +100,6 @@ impl HistoryModel {
             return;
         }
 
-        let address = messages.headers.borrow().address();
-        let auth_str = messages.headers.borrow().auth.value();
-        let auth = Option::from(!auth_str.is_empty()).map(|_| auth_str);
-        let metadata = messages.headers.borrow().meta.as_btree();
-        let request = HistoryData {
-            message,
-            address,
-            authentication: auth,
-            metadata,
-        };
-
         match serde_json::to_string_pretty(&request) {
             Ok(data) => {
                 std::fs::write(path, data).unwrap_or_else(|_| {
@@ -179,12 +168,18 @@ impl HistoryModel {
     }
 }
 
-#[derive(Serialize, Deserialize, Eq, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Clone)]
 pub struct HistoryData {
     pub message: String,
     pub address: String,
     pub authentication: Option<String>,
     pub metadata: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub send_encoding: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accept_encodings: Option<String>,
 }
 
 impl HistoryData {
@@ -193,15 +188,44 @@ impl HistoryData {
         address: String,
         authentication: Option<String>,
         metadata: BTreeMap<String, String>,
+        timeout: Option<String>,
     ) -> Self {
         Self {
             message,
             address,
             authentication,
             metadata,
+            timeout,
+            send_encoding: None,
+            accept_encodings: None,
         }
     }
 
+    /// Creates the history data from the current request and headers.
+    /// Returns `None` if the request cannot be parsed.
+    pub fn from_messages(messages: &MessagesModel) -> Option<Self> {
+        let message = messages.request_json().ok()?;
+        let headers = messages.headers.borrow();
+        let address = headers.address();
+        let auth_str = headers.auth.value();
+        let auth = Option::from(!auth_str.is_empty()).map(|_| auth_str);
+        let metadata = headers.meta.as_btree();
+        let timeout_str = headers.timeout.get_text_raw();
+        let timeout = Option::from(!timeout_str.is_empty()).map(|_| timeout_str);
+        let (send, accept) = headers.compression.raw();
+        let send_encoding = Option::from(!send.is_empty()).map(|_| send);
+        let accept_encodings = Option::from(!accept.is_empty()).map(|_| accept);
+        Some(Self {
+            message,
+            address,
+            authentication: auth,
+            metadata,
+            timeout,
+            send_encoding,
+            accept_encodings,
+        })
+    }
+
     pub fn to_json(&self) -> String {
         serde_json::to_string_pretty(self).unwrap_or_else(|_| {
             Logger::debug("history: failed to parse to json");
@@ -210,7 +234,7 @@ impl HistoryData {
     }
 
     /// Applies a history.
-    fn apply(&self, messages: &mut MessagesModel) {
+    pub(crate) fn apply(&self, messages: &mut MessagesModel) {
         let mut headers = messages.headers.borrow_mut();
         headers.clear();
         headers.addr.set_text_raw(&self.address);
@@ -219,11 +243,21 @@ impl HistoryData {
         } else {
             headers.auth.set_text("");
         }
+        if let Some(timeout) = &self.timeout {
+            headers.timeout.set_text_raw(timeout);
+        }
+        if self.send_encoding.is_some() || self.accept_encodings.is_some() {
+            headers.compression.set_text(
+                self.send_encoding.as_deref().unwrap_or_default(),
+                self.accept_encodings.as_deref().unwrap_or_default(),
+            );
+        }
         headers.meta.set_btree(&self.metadata);
         if headers.meta.headers.is_empty() {
             headers.meta = MetaHeaders::default();
         }
-        messages.request.editor.set_text_raw(&self.message);
+        drop(headers);
+        messages.set_request_json(&self.message);
     }
 }
 
@@ -244,6 +278,9 @@ mod tests {
             address: "Test address".to_string(),
             authentication: Some("Bearer Test".to_string()),
             metadata,
+            timeout: None,
+            send_encoding: None,
+            accept_encodings: None,
         };
 
         // when
@@ -274,6 +311,9 @@ mod tests {
             address: "Test address".to_string(),
             authentication: Some("Bearer test".to_string()),
             metadata,
+            timeout: Some("5s".to_string()),
+            send_encoding: Some("gzip".to_string()),
+            accept_encodings: None,
         };
 
         // when
@@ -289,5 +329,17 @@ mod tests {
 
         let expected_auth = "Bearer test";
         assert_eq!(messages.headers.borrow().auth.value(), expected_auth);
+
+        let expected_timeout = Some(std::time::Duration::from_secs(5));
+        assert_eq!(
+            messages.headers.borrow().timeout().unwrap(),
+            expected_timeout
+        );
+
+        let expected_compression = (String::from("gzip"), String::new());
+        assert_eq!(
+            messages.headers.borrow().compression.raw(),
+            expected_compression
+        );
     }
 }
@@ -0,0 +1,105 @@
+#![allow(clippy::module_name_repetitions)]
+use wireman_core::descriptor::JsonOptions;
+
+/// The state of the dialog that toggles the options of the JSON
+/// serialization profile at runtime.
+#[derive(Default)]
+pub struct JsonProfileModel {
+    /// Whether the dialog is open
+    toggled: bool,
+
+    /// The index of the selected option
+    pub selected: usize,
+}
+
+/// An option of the JSON serialization profile.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum JsonOption {
+    SkipDefaultFields,
+    EnumsAsNumbers,
+    ProtoFieldNames,
+    Stringify64BitIntegers,
+    DenyUnknownFields,
+    EmitDefaults,
+}
+
+impl JsonOption {
+    /// All options in the order they are listed.
+    pub const ALL: [Self; 6] = [
+        Self::SkipDefaultFields,
+        Self::EnumsAsNumbers,
+        Self::ProtoFieldNames,
+        Self::Stringify64BitIntegers,
+        Self::DenyUnknownFields,
+        Self::EmitDefaults,
+    ];
+
+    /// The label of the option.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::SkipDefaultFields => "Skip default fields",
+            Self::EnumsAsNumbers => "Enums as numbers",
+            Self::ProtoFieldNames => "Proto field names",
+            Self::Stringify64BitIntegers => "64-bit integers as strings",
+            Self::DenyUnknownFields => "Deny unknown fields",
+            Self::EmitDefaults => "grpcurl -emit-defaults",
+        }
+    }
+
+    /// Whether the option is enabled.
+    pub fn get(self, options: &JsonOptions) -> bool {
+        match self {
+            Self::SkipDefaultFields => options.skip_default_fields,
+            Self::EnumsAsNumbers => options.enums_as_numbers,
+            Self::ProtoFieldNames => options.proto_field_names,
+            Self::Stringify64BitIntegers => options.stringify_64_bit_integers,
+            Self::DenyUnknownFields => options.deny_unknown_fields,
+            Self::EmitDefaults => options.emit_defaults,
+        }
+    }
+
+    /// Enables or disables the option.
+    pub fn toggle(self, options: &mut JsonOptions) {
+        let value = match self {
+            Self::SkipDefaultFields => &mut options.skip_default_fields,
+            Self::EnumsAsNumbers => &mut options.enums_as_numbers,
+            Self::ProtoFieldNames => &mut options.proto_field_names,
+            Self::Stringify64BitIntegers => &mut options.stringify_64_bit_integers,
+            Self::DenyUnknownFields => &mut options.deny_unknown_fields,
+            Self::EmitDefaults => &mut options.emit_defaults,
+        };
+        *value = !*value;
+    }
+}
+
+impl JsonProfileModel {
+    /// Whether the dialog is open.
+    pub fn toggled(&self) -> bool {
+        self.toggled
+    }
+
+    /// Opens the dialog.
+    pub fn open(&mut self) {
+        self.toggled = true;
+    }
+
+    /// Closes the dialog.
+    pub fn close(&mut self) {
+        self.toggled = false;
+    }
+
+    /// Selects the next option.
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1) % JsonOption::ALL.len();
+    }
+
+    /// Selects the previous option.
+    pub fn prev(&mut self) {
+        self.selected = (self.selected + JsonOption::ALL.len() - 1) % JsonOption::ALL.len();
+    }
+
+    /// Returns the selected option.
+    pub fn selected_option(&self) -> JsonOption {
+        JsonOption::ALL[self.selected]
+    }
+}
@@ -1,19 +1,128 @@
 #![allow(clippy::module_name_repetitions)]
-use super::{core_client::CoreClient, headers::HeadersModel, history::HistoryModel};
+use super::{
+    completion::CompletionModel,
+    core_client::CoreClient,
+    headers::HeadersModel,
+    history::HistoryModel,
+    json_profile::JsonProfileModel,
+    validation::ValidationModel,
+    wire::{WireModel, WireTarget},
+};
 use crate::widgets::editor::{pretty_format_json, yank_to_clipboard, ErrorKind, TextEditor};
+use edtui::Index2;
 use futures::channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
-use futures::{self, stream::once, Stream, StreamExt};
+use futures::{
+    self,
+    stream::{once, unfold},
+    Stream, StreamExt,
+};
 use ratatui::prelude::Rect;
-use std::{cell::RefCell, collections::HashMap, pin::Pin, rc::Rc};
+use std::{cell::RefCell, collections::HashMap, fmt, pin::Pin, rc::Rc};
 use tokio::task::JoinHandle;
 use wireman_core::{
-    client::tls::TlsConfig,
-    descriptor::{response::StreamingResponse, DynamicMessage, RequestMessage, ResponseMessage},
-    MethodDescriptor,
+    client::{metrics::CallMetrics, GrpcClient},
+    descriptor::{
+        metadata::Metadata,
+        response::{ResponseDetails, StreamingResponse},
+        wire::{encode_base64, encode_hex, parse_wire_bytes},
+        DynamicMessage, JsonOptions, Position, RequestMessage, ResponseMessage,
+    },
+    error::{Error, GrpcStatus},
+    DescriptorPool, MessageDescriptor, MethodDescriptor,
 };
 
 /// Map from Method to request/response message
-type MessagesCache = HashMap<String, (String, String)>;
+type MessagesCache = HashMap<String, CacheEntry>;
+
+/// The cached editor contents of a method.
+#[derive(Clone, Default)]
+struct CacheEntry {
+    request: String,
+    response: String,
+    request_syntax: Syntax,
+    response_syntax: Syntax,
+}
+
+/// The syntax of the messages in an editor.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Syntax {
+    #[default]
+    Json,
+    TextFormat,
+}
+
+impl Syntax {
+    /// Returns the other syntax.
+    #[must_use]
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::Json => Self::TextFormat,
+            Self::TextFormat => Self::Json,
+        }
+    }
+
+    /// Parses a message of the given type. Json is parsed with the options.
+    ///
+    /// # Errors
+    /// - Failed to parse the message.
+    pub fn parse(
+        self,
+        desc: MessageDescriptor,
+        text: &str,
+        options: &JsonOptions,
+    ) -> Result<DynamicMessage, ErrorKind> {
+        let mut message = DynamicMessage::new(desc);
+        let result = match self {
+            Self::Json => message.from_json_with_options(text, options),
+            Self::TextFormat => message.from_text_format(text),
+        };
+        result.map_err(|err| ErrorKind::default_error(err.to_string()))?;
+        Ok(message)
+    }
+
+    /// Pretty formats a message. Json is serialized with the options.
+    ///
+    /// # Errors
+    /// - Failed to serialize the message.
+    pub fn format(
+        self,
+        message: &DynamicMessage,
+        options: &JsonOptions,
+    ) -> Result<String, ErrorKind> {
+        match self {
+            Self::Json => Ok(try_pretty_format_json(
+                &message.to_json_with_options(options)?,
+            )),
+            Self::TextFormat => Ok(message.to_text_format()),
+        }
+    }
+
+    /// Pretty formats a request template. Json templates list the
+    /// alternatives of each oneof in a comment.
+    ///
+    /// # Errors
+    /// - Failed to serialize the message.
+    pub fn format_template(
+        self,
+        message: &DynamicMessage,
+        options: &JsonOptions,
+    ) -> Result<String, ErrorKind> {
+        match self {
+            Self::Json => Ok(message.to_json_template(options)?),
+            Self::TextFormat => Ok(message.to_text_format()),
+        }
+    }
+}
+
+impl fmt::Display for Syntax {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let display_str = match self {
+            Syntax::Json => "json",
+            Syntax::TextFormat => "textproto",
+        };
+        write!(f, "{display_str}")
+    }
+}
 
 pub struct MessagesModel {
     /// The request editor model
@@ -50,6 +159,18 @@ pub struct MessagesModel {
 
     /// Number of messages sent on the current client-side stream.
     pub stream_count: usize,
+
+    /// The dialog to decode and encode wire bytes
+    pub wire: WireModel,
+
+    /// The dialog to toggle the JSON serialization profile
+    pub json_profile: JsonProfileModel,
+
+    /// The completion popup of the request editor
+    pub completion: CompletionModel,
+
+    /// The problems of the request found by the live validation
+    pub validation: ValidationModel,
 }
 
 impl Default for MessagesModel {
@@ -84,6 +205,10 @@ impl MessagesModel {
             handler: None,
             stream_tx: None,
             stream_count: 0,
+            wire: WireModel::default(),
+            json_profile: JsonProfileModel::default(),
+            completion: CompletionModel::default(),
+            validation: ValidationModel::default(),
         }
     }
 
@@ -127,34 +252,136 @@ impl MessagesModel {
         // Save the current editor
         let current_id = self.loaded_cache_id.clone();
         if let Some(value) = self.cache.get_mut(&current_id) {
-            *value = (
-                self.request.editor.get_text_raw(),
-                self.response.editor.get_text_raw(),
-            );
+            *value = CacheEntry {
+                request: self.request.editor.get_text_raw(),
+                response: self.response.editor.get_text_raw(),
+                request_syntax: self.request.syntax,
+                response_syntax: self.response.syntax,
+            };
         }
 
-        // Create a new cache entry if there is none for the given key
+        // Create a new cache entry if there is none for the given key.
+        // New entries keep the current syntax of the editors.
         if !self.cache.contains_key(id) {
-            self.cache
-                .insert(id.to_string(), (String::new(), String::new()));
+            let entry = CacheEntry {
+                request_syntax: self.request.syntax,
+                response_syntax: self.response.syntax,
+                ..Default::default()
+            };
+            self.cache.insert(id.to_string(), entry);
         }
 
         // set the active editor
         self.loaded_cache_id = id.to_string();
-        let (req, resp) = self.cache[id].clone();
-        self.request.editor.set_text_raw(&req);
-        self.response.editor.set_text_raw(&resp);
+        let entry = self.cache[id].clone();
+        self.request.editor.set_text_raw(&entry.request);
+        self.response.editor.set_text_raw(&entry.response);
+        self.request.syntax = entry.request_syntax;
+        self.response.syntax = entry.response_syntax;
+        self.response.details.clear();
+        self.response.metrics = None;
+    }
+
+    /// Returns the JSON serialization profile.
+    pub fn json_options(&self) -> JsonOptions {
+        self.request.core_client.borrow().json_options()
+    }
+
+    /// Toggles the selected option of the JSON profile. The json editors
+    /// are converted to the new profile if they hold a valid message.
+    pub fn toggle_json_option(&mut self) {
+        let old = self.json_options();
+        let mut new = old;
+        self.json_profile.selected_option().toggle(&mut new);
+        if let Some(method) = &self.selected_method {
+            let convert = |desc: MessageDescriptor, text: &str| {
+                Syntax::Json
+                    .parse(desc, text, &old)
+                    .and_then(|message| Syntax::Json.format(&message, &new))
+            };
+            if self.request.syntax == Syntax::Json {
+                let text = self.request.editor.get_text_raw();
+                if let Ok(converted) = convert(method.input(), &text) {
+                    self.request.set_text(&converted);
+                }
+            }
+            if self.response.syntax == Syntax::Json && self.response.editor.get_error().is_none() {
+                let text = self.response.editor.get_text_raw();
+                if let Ok(converted) = convert(method.output(), &text) {
+                    self.response.set_text(&converted);
+                }
+            }
+        }
+        self.request.core_client.borrow_mut().set_json_options(new);
+    }
+
+    /// Updates the completions of the request editor for the text before
+    /// the cursor. Completion is only offered for json in insert mode.
+    pub fn update_completion(&mut self) {
+        match &self.selected_method {
+            Some(method)
+                if self.request.syntax == Syntax::Json && self.request.editor.insert_mode() =>
+            {
+                let text = self.request.editor.get_text_before_cursor();
+                self.completion.update(&text, &method.input());
+            }
+            _ => self.completion.close(),
+        }
+    }
+
+    /// Inserts the selected completion into the request editor.
+    pub fn accept_completion(&mut self) {
+        if let Some((item, prefix_len)) = self.completion.selected_item() {
+            let insert = item.insert.clone();
+            self.request
+                .editor
+                .replace_before_cursor(prefix_len, &insert);
+        }
+        self.completion.close();
+    }
+
+    /// Validates the json of the request editor against the input
+    /// message of the selected method.
+    pub fn update_validation(&mut self) {
+        match &self.selected_method {
+            Some(method) if self.request.syntax == Syntax::Json => {
+                let options = self.json_options();
+                let text = self.request.editor.get_text_raw();
+                if !self.validation.update(&text, &method.input(), &options) {
+                    return;
+                }
+            }
+            _ => self.validation.clear(),
+        }
+        let spans = self.validation.problems().iter().map(|problem| {
+            let start = Index2::new(problem.start.line, problem.start.column);
+            let end = match problem.end.column {
+                0 => start,
+                column => Index2::new(problem.end.line, column - 1),
+            };
+            (start, end)
+        });
+        self.request.editor.highlight(spans);
+    }
+
+    /// Moves the cursor of the request editor to the start of the next
+    /// problem.
+    pub fn next_problem(&mut self) {
+        let cursor = self.request.editor.state.cursor;
+        let position = Position::new(cursor.row, cursor.col);
+        if let Some(problem) = self.validation.next(position) {
+            self.request.editor.state.cursor =
+                Index2::new(problem.start.line, problem.start.column);
+        }
     }
 
     /// Returns the request as json string
     pub fn to_json(&self) -> String {
+        let options = self.json_options();
         if let Some(method) = &self.selected_method {
-            let mut req = self.request.core_client.borrow().get_request(method);
-            match req
-                .message_mut()
-                .from_json(&self.request.editor.get_text_raw())
-            {
-                Ok(()) => try_pretty_format_json(&req.to_json().unwrap()),
+            let text = self.request.editor.get_text_raw();
+            match self.request.syntax.parse(method.input(), &text, &options) {
+                Ok(message) => Syntax::Json.format(&message, &options).unwrap_or_default(),
                 Err(_) => String::new(),
             }
         } else {
@@ -162,6 +389,173 @@ impl MessagesModel {
         }
     }
 
+    /// Returns the request as pretty json string, serialized with the json
+    /// options. A request in the text format is converted to json. A json
+    /// request that does not match the input message, e.g. because it holds
+    /// a variable in place of a number, is only pretty formatted.
+    ///
+    /// # Errors
+    /// - Failed to parse the request.
+    pub fn request_json(&self) -> Result<String, ErrorKind> {
+        let options = self.json_options();
+        let text = self.request.editor.get_text_raw();
+        let Some(method) = &self.selected_method else {
+            return pretty_format_json(&text);
+        };
+        match self.request.syntax.parse(method.input(), &text, &options) {
+            Ok(message) => Syntax::Json.format(&message, &options),
+            Err(err) if self.request.syntax == Syntax::TextFormat => Err(err),
+            Err(_) => pretty_format_json(&text),
+        }
+    }
+
+    /// Loads a json request into the request editor. The request is
+    /// converted if the editor is in the text format.
+    pub fn set_request_json(&mut self, json: &str) {
+        let options = self.json_options();
+        let converted = match (self.request.syntax, &self.selected_method) {
+            (Syntax::TextFormat, Some(method)) => Syntax::Json
+                .parse(method.input(), json, &options)
+                .and_then(|message| Syntax::TextFormat.format(&message, &options))
+                .ok(),
+            _ => None,
+        };
+        self.request.set_text(converted.as_deref().unwrap_or(json));
+    }
+
+    /// Switches the syntax of the request editor and converts its content.
+    /// The syntax is kept if the content cannot be converted.
+    pub fn toggle_request_syntax(&mut self) {
+        let options = self.json_options();
+        let syntax = self.request.syntax;
+        let text = self.request.editor.get_text_raw();
+        if let (Some(method), false) = (&self.selected_method, text.trim().is_empty()) {
+            let converted = syntax
+                .parse(method.input(), &text, &options)
+                .and_then(|message| syntax.toggle().format(&message, &options));
+            match converted {
+                Ok(converted) => self.request.set_text(&converted),
+                Err(err) => {
+                    self.request.set_error(err);
+                    return;
+                }
+            }
+        }
+        self.request.syntax = syntax.toggle();
+        self.request.editor.set_error(None);
+    }
+
+    /// Switches the syntax of the response editor. The content is converted
+    /// if it holds a response message.
+    pub fn toggle_response_syntax(&mut self) {
+        let options = self.json_options();
+        let syntax = self.response.syntax;
+        if let (Some(method), None) = (&self.selected_method, self.response.editor.get_error()) {
+            let text = self.response.editor.get_text_raw();
+            let converted = syntax
+                .parse(method.output(), &text, &options)
+                .and_then(|message| syntax.toggle().format(&message, &options));
+            if let Ok(converted) = converted {
+                self.response.set_text(&converted);
+            }
+        }
+        self.response.syntax = syntax.toggle();
+    }
+
+    /// Pretty formats the request in the syntax of the editor.
+    pub fn format_request(&mut self) {
+        let (Syntax::TextFormat, Some(method)) = (self.request.syntax, &self.selected_method)
+        else {
+            self.request.editor.format_json();
+            return;
+        };
+        let options = self.json_options();
+        let text = self.request.editor.get_text_raw();
+        let formatted = Syntax::TextFormat
+            .parse(method.input(), &text, &options)
+            .and_then(|message| Syntax::TextFormat.format(&message, &options));
+        match formatted {
+            Ok(formatted) => {
+                self.request.set_text(&formatted);
+                self.request.editor.set_error(None);
+            }
+            Err(err) => self.request.set_error(err),
+        }
+    }
+
+    /// Opens the wire bytes dialog. The message type defaults to the input
+    /// or output of the selected method. If opened from the request editor,
+    /// the dialog shows the encoded request.
+    pub fn open_wire_dialog(&mut self, target: WireTarget) {
+        let Some(method) = &self.selected_method else {
+            return;
+        };
+        let message_type = match target {
+            WireTarget::Request => method.input(),
+            WireTarget::Response => method.output(),
+        };
+        self.wire.open(target, message_type.full_name());
+        if target == WireTarget::Request {
+            match self.encode_request() {
+                Ok(bytes) => self.wire.encoded = Some((encode_base64(&bytes), encode_hex(&bytes))),
+                Err(err) => self.wire.error = Some(err.string()),
+            }
+        }
+    }
+
+    /// Encodes the request to the wire format. Variables of the
+    /// selected environment are resolved.
+    fn encode_request(&self) -> Result<Vec<u8>, ErrorKind> {
+        let Some(method) = &self.selected_method else {
+            return Err(ErrorKind::default_error("No method selected"));
+        };
+        let options = self.json_options();
+        let text = self
+            .request
+            .core_client
+            .borrow()
+            .substitute(&self.request.editor.get_text_raw());
+        let message = self.request.syntax.parse(method.input(), &text, &options)?;
+        Ok(message.to_bytes())
+    }
+
+    /// Decodes the wire bytes of the dialog with the selected message type
+    /// and loads the message into the target editor.
+    pub fn decode_wire_bytes(&mut self) {
+        let Some(method) = &self.selected_method else {
+            return;
+        };
+        let options = self.json_options();
+        let name = self.wire.message_type.get_text_raw();
+        let name = name.trim();
+        let decoded = method
+            .parent_pool()
+            .get_message_by_name(name)
+            .ok_or_else(|| ErrorKind::format_error(format!("message type {name} not found")))
+            .and_then(|desc| {
+                let bytes = parse_wire_bytes(&self.wire.input.get_text_raw())?;
+                let mut message = DynamicMessage::new(desc);
+                message.from_bytes(&bytes)?;
+                Ok(message)
+            });
+        let text = match self.wire.target {
+            WireTarget::Request => decoded.and_then(|m| self.request.syntax.format(&m, &options)),
+            WireTarget::Response => decoded.and_then(|m| self.response.syntax.format(&m, &options)),
+        };
+        match text {
+            Ok(text) => {
+                let editor = match self.wire.target {
+                    WireTarget::Request => &mut self.request.editor,
+                    WireTarget::Response => &mut self.response.editor,
+                };
+                editor.set_text_raw(&text);
+                editor.set_error(None);
+                self.wire.close();
+            }
+            Err(err) => self.wire.error = Some(err.string()),
+        }
+    }
+
     /// Marks a request to be dispatched on the next frame, or sends the next
     /// message when a client-side stream is already open.
     pub fn start_request(&mut self) {
@@ -172,6 +566,8 @@ impl MessagesModel {
         self.dispatch = true;
         self.response.editor.set_text_raw("Processing...");
         self.response.editor.set_error(None);
+        self.response.details.clear();
+        self.response.metrics = None;
     }
 
     /// This method should be called to abort a grpc request.
@@ -267,23 +663,26 @@ impl MessagesModel {
             self.response.editor.set_text_raw(&err.string());
             return Err(ErrorKind::default_error("No method selected"));
         };
-        let mut req = self.request.core_client.borrow().get_request(&method);
+        let core_client = self.request.core_client.borrow();
+        let options = core_client.json_options();
+        let mut req = core_client.get_request(&method);
 
-        if let Err(err) = req
-            .message_mut()
-            .from_json(&self.request.editor.get_text_raw())
-        {
-            return Err(ErrorKind::default_error(err.to_string()));
-        }
+        // Variables of the selected environment are resolved at send time.
+        let text = core_client.substitute(&self.request.editor.get_text_raw());
+        let message = self.request.syntax.parse(method.input(), &text, &options)?;
+        req.set_message(message);
 
         let headers = self.headers.borrow();
         for (key, val) in headers.headers_expanded() {
             if !key.is_empty() {
-                let _ = req.insert_metadata(&key, &val);
+                req.insert_metadata(&key, &core_client.substitute(&val))
+                    .map_err(|err| ErrorKind::format_error(format!("header {key}: {err}")))?;
             }
         }
 
-        req.set_address(&headers.address());
+        req.set_address(&core_client.substitute(&headers.address()));
+        req.set_timeout(headers.timeout()?);
+        req.set_compression(headers.compression.compression()?);
         Ok(req)
     }
 
@@ -313,7 +712,9 @@ impl MessagesModel {
     pub fn yank_grpcurl(&mut self) {
         if let Some(method) = &self.selected_method {
             let address = self.headers.borrow().address();
-            let message = self.request.editor.get_text_raw();
+            let message = self
+                .request_json()
+                .unwrap_or_else(|_| self.request.editor.get_text_raw());
             let header = self.headers.borrow().headers();
 
             if let Ok(text) = self
@@ -335,72 +736,86 @@ impl MessagesModel {
     }
 }
 
-pub(crate) async fn unary(req: RequestMessage, tls: Option<TlsConfig>) -> RequestResult {
-    let resp: Result<ResponseMessage, ErrorKind> = CoreClient::call_unary_async(&req, tls).await;
-
-    match resp {
-        Ok(resp) => unmarshal_message(&resp.message),
-        Err(err) => RequestResult::error(err),
+pub(crate) async fn unary(
+    req: RequestMessage,
+    mut client: GrpcClient,
+    options: JsonOptions,
+) -> RequestResult {
+    match client.unary(&req).await {
+        Ok(resp) => unmarshal_response(&resp, &options),
+        Err(err) => RequestResult::from_error(err, req.method_descriptor().parent_pool()),
     }
 }
 
 pub(crate) async fn server_streaming(
     req: RequestMessage,
-    tls: Option<TlsConfig>,
+    mut client: GrpcClient,
+    options: JsonOptions,
 ) -> Pin<Box<dyn Stream<Item = RequestResult> + Send>> {
-    let resp: Result<StreamingResponse, ErrorKind> =
-        CoreClient::call_server_streaming(&req, tls).await;
-
-    if let Err(err) = resp {
-        return once(async { RequestResult::error(err) }).boxed();
+    let pool = req.method_descriptor().parent_pool().clone();
+    match client.server_streaming(&req).await {
+        Ok(resp) => unmarshal_stream(resp, pool, options),
+        Err(err) => once(async move { RequestResult::from_error(err, &pool) }).boxed(),
     }
-
-    let resp = resp.unwrap();
-
-    let mapped_stream = resp.inner.map(|message| match message {
-        Ok(message) => unmarshal_message(&message.message),
-        Err(err) => {
-            let kind = ErrorKind::streaming_error(format!("{err}"));
-            RequestResult::error(kind)
-        }
-    });
-
-    Box::pin(mapped_stream)
 }
 
 pub(crate) async fn client_streaming(
     head: RequestMessage,
     messages: impl Stream<Item = RequestMessage> + Send + 'static,
-    tls: Option<TlsConfig>,
+    mut client: GrpcClient,
+    options: JsonOptions,
 ) -> RequestResult {
-    let resp: Result<ResponseMessage, ErrorKind> =
-        CoreClient::call_client_streaming(&head, messages, tls).await;
-
-    match resp {
-        Ok(resp) => unmarshal_message(&resp.message),
-        Err(err) => RequestResult::error(err),
+    match client.client_streaming(&head, messages).await {
+        Ok(resp) => unmarshal_response(&resp, &options),
+        Err(err) => RequestResult::from_error(err, head.method_descriptor().parent_pool()),
     }
 }
 
 pub(crate) async fn bidi_streaming(
     head: RequestMessage,
     messages: impl Stream<Item = RequestMessage> + Send + 'static,
-    tls: Option<TlsConfig>,
+    mut client: GrpcClient,
+    options: JsonOptions,
 ) -> Pin<Box<dyn Stream<Item = RequestResult> + Send>> {
-    let resp: Result<StreamingResponse, ErrorKind> =
-        CoreClient::call_bidirectional_streaming(&head, messages, tls).await;
-
-    if let Err(err) = resp {
-        return once(async { RequestResult::error(err) }).boxed();
+    let pool = head.method_descriptor().parent_pool().clone();
+    match client.bidirectional_streaming(&head, messages).await {
+        Ok(resp) => unmarshal_stream(resp, pool, options),
+        Err(err) => once(async move { RequestResult::from_error(err, &pool) }).boxed(),
     }
+}
 
-    let resp = resp.unwrap();
-
-    let mapped_stream = resp.inner.map(|message| match message {
-        Ok(message) => unmarshal_message(&message.message),
-        Err(err) => {
-            let kind = ErrorKind::streaming_error(format!("{err}"));
-            RequestResult::error(kind)
+/// Maps a response stream to request results. Once the stream is exhausted,
+/// a final result carrying only the response details is emitted.
+/// The pool is used to decode the rich error details of a failed call.
+fn unmarshal_stream(
+    resp: StreamingResponse,
+    pool: DescriptorPool,
+    options: JsonOptions,
+) -> Pin<Box<dyn Stream<Item = RequestResult> + Send>> {
+    let mapped_stream = unfold(Some(resp), move |resp| {
+        let pool = pool.clone();
+        async move {
+            let mut resp = resp?;
+            match resp.message().await {
+                Ok(Some(message)) => {
+                    let result =
+                        unmarshal_message(&message.message, &options).with_metrics(resp.metrics());
+                    Some((result, Some(resp)))
+                }
+                Ok(None) => {
+                    let result = match resp.details().await {
+                        Ok(details) => RequestResult::details(format_details(&details))
+                            .with_metrics(details.metrics),
+                        Err(err) => RequestResult::from_error(err, &pool),
+                    };
+                    Some((result, None))
+                }
+                Err(err) => {
+                    let kind = ErrorKind::streaming_error(format!("{err}"));
+                    let status = format_status_details(&GrpcStatus::from(err), &pool);
+                    Some((RequestResult::error(kind).with_status_details(status), None))
+                }
+            }
         }
     });
 
@@ -410,7 +825,11 @@ pub(crate) async fn bidi_streaming(
 #[derive(Default, Clone)]
 pub struct RequestResult {
     data: Option<String>,
+    message: Option<Box<DynamicMessage>>,
     error: Option<ErrorKind>,
+    details: Option<String>,
+    status_details: Option<String>,
+    metrics: Option<CallMetrics>,
 }
 unsafe impl Send for RequestResult {}
 
@@ -418,24 +837,92 @@ impl RequestResult {
     pub fn data(data: String) -> Self {
         Self {
             data: Some(data),
-            error: None,
+            ..Default::default()
         }
     }
     pub fn error(error: ErrorKind) -> Self {
         Self {
-            data: None,
             error: Some(error),
+            ..Default::default()
+        }
+    }
+    pub fn details(details: String) -> Self {
+        Self {
+            details: Some(details),
+            ..Default::default()
         }
     }
-    pub fn set(&self, editor: &mut TextEditor) {
+    pub fn get_data(&self) -> Option<&str> {
+        self.data.as_deref()
+    }
+    pub fn get_error(&self) -> Option<&ErrorKind> {
+        self.error.as_ref()
+    }
+    /// Sets the response message, which is formatted in the syntax and
+    /// JSON profile of the response editor.
+    #[must_use]
+    pub fn with_message(mut self, message: DynamicMessage) -> Self {
+        self.message = Some(Box::new(message));
+        self
+    }
+    #[must_use]
+    pub fn with_details(mut self, details: Option<String>) -> Self {
+        self.details = details;
+        self
+    }
+    /// Sets the decoded rich error details, shown below the error.
+    #[must_use]
+    pub fn with_status_details(mut self, status_details: Option<String>) -> Self {
+        self.status_details = status_details;
+        self
+    }
+    #[must_use]
+    pub fn with_metrics(mut self, metrics: CallMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+    pub fn set(&self, response: &mut ResponseModel, options: &JsonOptions) {
         if let Some(text) = &self.data {
-            editor.set_error(None);
-            editor.set_text_raw(text);
+            response.editor.set_error(None);
+            let formatted = self
+                .message
+                .as_ref()
+                .and_then(|message| response.syntax.format(message, options).ok());
+            response
+                .editor
+                .set_text_raw(formatted.as_deref().unwrap_or(text));
         }
         if let Some(error) = &self.error {
-            editor.set_error(Some(error.clone()));
-            editor.set_text_raw(&error.string());
+            response.editor.set_error(Some(error.clone()));
+            let mut text = error.string();
+            if let Some(status_details) = &self.status_details {
+                text = format!("{text}\n\n{status_details}");
+            }
+            response.editor.set_text_raw(&text);
+        }
+        if let Some(details) = &self.details {
+            response.details.clone_from(details);
         }
+        if let Some(metrics) = &self.metrics {
+            response.metrics = Some(metrics.clone());
+        }
+    }
+}
+
+impl RequestResult {
+    /// Creates the result of a failed call. The rich error details of
+    /// the status are decoded with the pool.
+    pub fn from_error(err: Error, pool: &DescriptorPool) -> Self {
+        let (details, status_details) = match &err {
+            Error::GrpcError(status) | Error::DeadlineExceeded(status) => (
+                Some(format_status(status)),
+                format_status_details(status, pool),
+            ),
+            _ => (None, None),
+        };
+        Self::error(ErrorKind::from(err))
+            .with_details(details)
+            .with_status_details(status_details)
     }
 }
 
@@ -447,6 +934,9 @@ pub struct RequestModel {
     /// The currently active editor
     pub editor: TextEditor,
 
+    /// The syntax of the request message
+    pub syntax: Syntax,
+
     /// The metadata
     pub metadata: String,
 
@@ -464,6 +954,7 @@ impl RequestModel {
         Self {
             core_client,
             editor: TextEditor::new(),
+            syntax: Syntax::default(),
             metadata: String::new(),
             window_size: 50,
             content_area: None,
@@ -472,14 +963,13 @@ impl RequestModel {
 
     /// Loads a new request message template into the editor.
     pub fn load_template(&mut self, method: &MethodDescriptor) {
-        let req = self
-            .core_client
-            .borrow_mut()
-            .get_request(method)
-            .message()
-            .to_json()
-            .map_or("{}".to_string(), |r| try_pretty_format_json(&r));
-        self.editor.set_text_raw(&req);
+        let req = self.core_client.borrow_mut().get_request(method);
+        let options = self.core_client.borrow().json_options();
+        let text = self
+            .syntax
+            .format_template(req.message(), &options)
+            .unwrap_or_else(|_| "{}".to_string());
+        self.editor.set_text_raw(&text);
     }
 
     pub fn set_text(&mut self, text: &str) {
@@ -504,6 +994,18 @@ pub struct ResponseModel {
     // The response text field
     pub editor: TextEditor,
 
+    /// The syntax of the response messages
+    pub syntax: Syntax,
+
+    /// The status, headers, trailers and timing of the last call
+    pub details: String,
+
+    /// Whether the details section is shown below the response
+    pub show_details: bool,
+
+    /// The timing and payload sizes of the last call
+    pub metrics: Option<CallMetrics>,
+
     /// Hit-test area of the response editor
     pub content_area: Option<Rect>,
 }
@@ -513,6 +1015,10 @@ impl ResponseModel {
     pub fn new() -> Self {
         Self {
             editor: TextEditor::new(),
+            syntax: Syntax::default(),
+            details: String::new(),
+            show_details: false,
+            metrics: None,
             content_area: None,
         }
     }
@@ -520,6 +1026,13 @@ impl ResponseModel {
     /// Clears the response
     pub fn clear(&mut self) {
         self.editor = TextEditor::new();
+        self.details.clear();
+        self.metrics = None;
+    }
+
+    /// Shows or hides the details section.
+    pub fn toggle_details(&mut self) {
+        self.show_details = !self.show_details;
     }
 
     pub fn set_text(&mut self, text: &str) {
@@ -537,12 +1050,173 @@ fn try_pretty_format_json(input: &str) -> String {
     pretty_format_json(input).unwrap_or_else(|_| input.to_string())
 }
 
-fn unmarshal_message(message: &DynamicMessage) -> RequestResult {
-    if let Ok(json) = message.to_json() {
-        let formatted_json = try_pretty_format_json(&json);
-        RequestResult::data(formatted_json)
+fn unmarshal_response(response: &ResponseMessage, options: &JsonOptions) -> RequestResult {
+    let details = response.details.as_ref().map(format_details);
+    let result = unmarshal_message(&response.message, options).with_details(details);
+    match &response.details {
+        Some(details) => result.with_metrics(details.metrics.clone()),
+        None => result,
+    }
+}
+
+/// Serializes a response message with the json options, so that the
+/// request log and the response editor show the same json.
+fn unmarshal_message(message: &DynamicMessage, options: &JsonOptions) -> RequestResult {
+    if let Ok(json) = Syntax::Json.format(message, options) {
+        RequestResult::data(json).with_message(message.clone())
     } else {
         let err = ErrorKind::format_error("failed to parse json".to_string());
         RequestResult::error(err)
     }
 }
+
+/// Formats the status, timing and metadata of a successful call.
+fn format_details(details: &ResponseDetails) -> String {
+    let mut lines = vec![
+        "Status:  Ok".to_string(),
+        format!("Elapsed: {:.2?}", details.metrics.duration),
+        format!(
+            "Encoding: {}",
+            details.encoding.as_deref().unwrap_or("identity")
+        ),
+    ];
+    lines.extend(format_arrivals(&details.metrics));
+    lines.extend(format_metadata("Headers", &details.headers));
+    lines.extend(format_metadata("Trailers", &details.trailers));
+    lines.join("\n")
+}
+
+/// Formats the status and metadata of a failed call.
+fn format_status(status: &GrpcStatus) -> String {
+    let mut lines = vec![
+        format!("Status:  {:?}", status.code),
+        format!("Message: {}", status.message),
+    ];
+    lines.extend(format_metadata("Metadata", &status.metadata));
+    lines.join("\n")
+}
+
+/// Formats the rich error details of a status as pretty json. Returns
+/// `None` if the status has no details.
+fn format_status_details(status: &GrpcStatus, pool: &DescriptorPool) -> Option<String> {
+    let details = match status.decode_details(pool) {
+        Ok(details) if details.is_empty() => return None,
+        Ok(details) => serde_json::Value::from(details),
+        Err(err) => return Some(err.to_string()),
+    };
+    serde_json::to_string_pretty(&details).ok()
+}
+
+/// Formats the arrival times of the messages of a stream.
+fn format_arrivals(metrics: &CallMetrics) -> Vec<String> {
+    if metrics.response_count() < 2 {
+        return Vec::new();
+    }
+    let mut lines = vec![String::new(), "Arrivals".to_string()];
+    lines.extend(
+        metrics
+            .arrivals
+            .iter()
+            .enumerate()
+            .map(|(i, arrival)| format!("  #{}: {arrival:.2?}", i + 1)),
+    );
+    lines
+}
+
+/// Formats the timing and payload sizes of a call as a single line.
+pub(crate) fn format_metrics(metrics: &CallMetrics) -> String {
+    let mut parts = Vec::new();
+    if let Some(ttfb) = metrics.time_to_first_byte {
+        parts.push(format!("TTFB {ttfb:.2?}"));
+    }
+    parts.push(format!("Total {:.2?}", metrics.duration));
+    parts.push(format!("Req {}", format_bytes(metrics.request_bytes)));
+    parts.push(format!("Resp {}", format_bytes(metrics.response_bytes)));
+    if metrics.request_count > 1 {
+        parts.push(format!("Sent {}", metrics.request_count));
+    }
+    if metrics.response_count() > 1 {
+        parts.push(format!("Received {}", metrics.response_count()));
+    }
+    parts.join(" | ")
+}
+
+/// Formats a size in bytes in human readable units.
+#[allow(clippy::cast_precision_loss)]
+fn format_bytes(bytes: usize) -> String {
+    const KIB: usize = 1024;
+    const MIB: usize = 1024 * KIB;
+    if bytes < KIB {
+        format!("{bytes} B")
+    } else if bytes < MIB {
+        format!("{:.1} KiB", bytes as f64 / KIB as f64)
+    } else {
+        format!("{:.1} MiB", bytes as f64 / MIB as f64)
+    }
+}
+
+fn format_metadata(title: &str, metadata: &Metadata) -> Vec<String> {
+    let entries = metadata.decoded_entries();
+    if entries.is_empty() {
+        return Vec::new();
+    }
+    let mut lines = vec![String::new(), title.to_string()];
+    lines.extend(
+        entries
+            .into_iter()
+            .map(|(key, val)| format!("  {key}: {val}")),
+    );
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_format_metrics() {
+        // given
+        let metrics = CallMetrics {
+            time_to_first_byte: Some(Duration::from_millis(12)),
+            duration: Duration::from_millis(40),
+            request_count: 1,
+            request_bytes: 42,
+            response_bytes: 2048,
+            arrivals: vec![Duration::from_millis(20), Duration::from_millis(30)],
+        };
+
+        // when
+        let line = format_metrics(&metrics);
+
+        // then
+        assert_eq!(
+            line,
+            "TTFB 12.00ms | Total 40.00ms | Req 42 B | Resp 2.0 KiB | Received 2"
+        );
+        assert_eq!(format_arrivals(&metrics).len(), 4);
+    }
+
+    #[test]
+    fn test_unmarshal_message_with_options() {
+        // given
+        let desc =
+            wireman_core::ProtoDescriptor::new(["../wireman-core/test_files"], ["test.proto"])
+                .unwrap();
+        let method = desc
+            .get_method_by_name("proto.TestService", "Enum")
+            .unwrap();
+        let mut message = DynamicMessage::new(method.input());
+        message.from_json(r#"{"color": "RED"}"#).unwrap();
+        let options = JsonOptions {
+            enums_as_numbers: true,
+            ..JsonOptions::default()
+        };
+
+        // when
+        let result = unmarshal_message(&message, &options);
+
+        // then
+        assert_eq!(result.get_data(), Some("{\n  \"color\": 1\n}"));
+    }
+}
@@ -1,11 +1,18 @@
 #![allow(clippy::module_name_repetitions)]
+pub mod collections;
+pub mod completion;
 pub mod configuration;
 pub mod core_client;
 pub mod headers;
 pub mod history;
+pub mod json_profile;
 pub mod messages;
 pub mod reflection;
+pub mod reflection_cache;
+pub mod request_log;
 pub mod selection;
+pub mod validation;
+pub mod wire;
 
 pub use core_client::CoreClient;
 pub use messages::MessagesModel;
@@ -1,13 +1,17 @@
 #![allow(clippy::module_name_repetitions)]
 use super::core_client::CoreClient;
 use super::headers::HeadersModel;
+use super::reflection_cache::ReflectionCache;
 use super::SelectionModel;
 use crate::events::InternalStreamData;
+use crate::widgets::editor::ErrorKind;
+use chrono::{DateTime, Local};
 use std::cell::RefCell;
 use std::rc::Rc;
 use tokio::sync::mpsc::Sender;
 use wireman_core::descriptor::ReflectionRequest;
 use wireman_core::ProtoDescriptor;
+use wireman_logger::Logger;
 
 #[derive(Clone)]
 pub struct ReflectionModel {
@@ -21,6 +25,11 @@ pub struct ReflectionModel {
     pub dispatch_reflection: bool,
     /// An error that occurred during reflection
     pub error: Option<String>,
+    /// The cache of the reflected descriptors
+    cache: ReflectionCache,
+    /// The host whose schema cache was looked up and when its schema
+    /// was cached, if at all
+    cached_at: Option<(String, Option<DateTime<Local>>)>,
 }
 
 impl ReflectionModel {
@@ -30,6 +39,7 @@ impl ReflectionModel {
         core_client: Rc<RefCell<CoreClient>>,
         headers: Rc<RefCell<HeadersModel>>,
         selection: Rc<RefCell<SelectionModel>>,
+        cache: ReflectionCache,
     ) -> Self {
         Self {
             core_client,
@@ -37,6 +47,8 @@ impl ReflectionModel {
             selection,
             dispatch_reflection: false,
             error: None,
+            cache,
+            cached_at: None,
         }
     }
 
@@ -46,10 +58,23 @@ impl ReflectionModel {
 
     pub fn handle_reflection(&mut self, sx: Sender<InternalStreamData>) {
         let request = self.build_request();
+        let host = self.host();
+        let cache = self.cache.clone();
         self.dispatch_reflection = false;
         tokio::spawn(async move {
-            let event = match ProtoDescriptor::from_reflection(request).await {
-                Ok(desc) => InternalStreamData::Reflection(Ok(desc)),
+            let result = match request {
+                Ok(request) => ProtoDescriptor::from_reflection(request)
+                    .await
+                    .map_err(|err| err.to_string()),
+                Err(err) => Err(err.string()),
+            };
+            if let Ok(desc) = &result {
+                if let Err(err) = cache.save(&host, desc) {
+                    Logger::debug(format!("reflection cache: {err}"));
+                }
+            }
+            let event = match result {
+                Ok(desc) => InternalStreamData::Reflection(Ok((host, desc))),
                 Err(err) => {
                     InternalStreamData::Reflection(Err(format!("Server reflection failed: {err}")))
                 }
@@ -58,21 +83,110 @@ impl ReflectionModel {
         });
     }
 
-    // Builds the grpc request
-    pub fn build_request(&mut self) -> ReflectionRequest {
+    /// The address of the reflected server.
+    pub fn host(&self) -> String {
+        let address = self.headers.borrow().address();
+        self.core_client.borrow().substitute(&address)
+    }
+
+    /// Returns when the schema of the server was cached, if any, as
+    /// last looked up by [`Self::load_cached_at`].
+    pub fn cached_at(&self) -> Option<DateTime<Local>> {
+        self.cached_at
+            .as_ref()
+            .and_then(|(_, timestamp)| *timestamp)
+    }
+
+    /// Looks up when the schema of the server was cached. Called when
+    /// the dialog opens and after a schema was saved.
+    pub fn load_cached_at(&mut self) {
+        let host = self.host();
+        let timestamp = self.cache.timestamp(&host);
+        self.cached_at = Some((host, timestamp));
+    }
+
+    /// Looks up when the schema of the server was cached if the address
+    /// changed since the last lookup.
+    pub fn refresh_cached_at(&mut self) {
+        let host = self.host();
+        if self.cached_at.as_ref().map(|(cached, _)| cached) != Some(&host) {
+            self.load_cached_at();
+        }
+    }
+
+    /// Loads the cached schema of the server. Sets the error if
+    /// no schema is cached or it cannot be decoded.
+    pub fn load_cache(&mut self) -> Option<ProtoDescriptor> {
+        match self.cache.load(&self.host()) {
+            Ok(desc) => {
+                self.error = None;
+                Some(desc)
+            }
+            Err(err) => {
+                self.error = Some(format!("Failed to load the cached schema: {err}"));
+                None
+            }
+        }
+    }
+
+    // Builds the grpc request. Uses the same TLS settings as the grpc client.
+    pub fn build_request(&mut self) -> Result<ReflectionRequest, ErrorKind> {
         let headers = self.headers.borrow();
+        let core_client = self.core_client.borrow();
 
         // Address
-        let address = headers.address();
+        let address = core_client.substitute(&headers.address());
         let mut req = ReflectionRequest::new(&address);
 
         // Metadata
         for (key, val) in headers.auth_headers_expanded() {
             if !key.is_empty() {
-                let _ = req.insert_metadata(&key, &val);
+                let _ = req.insert_metadata(&key, &core_client.substitute(&val));
             }
         }
 
-        req
+        let tls = core_client.get_tls_config()?;
+        Ok(req.with_tls(tls))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::history::HistoryModel;
+    use wireman_config::{config::TlsConfig, Config};
+    use wireman_core::client::tls::TlsConfig as ClientTlsConfig;
+
+    #[test]
+    fn test_build_request_uses_client_tls() {
+        // given
+        let cfg = Config {
+            tls: TlsConfig {
+                skip_hostname_verification: Some(true),
+                ..TlsConfig::default()
+            },
+            ..Config::default()
+        };
+        let core_client = Rc::new(RefCell::new(CoreClient::new(&cfg).unwrap()));
+        let history = Rc::new(RefCell::new(HistoryModel::default()));
+        let headers = HeadersModel::new("https://localhost:50051", "", "", history);
+        let selection = SelectionModel::new(Rc::clone(&core_client));
+        let mut model = ReflectionModel::new(
+            Rc::clone(&core_client),
+            Rc::new(RefCell::new(headers)),
+            Rc::new(RefCell::new(selection)),
+            ReflectionCache::new(&cfg),
+        );
+
+        // when
+        let request = model.build_request().unwrap();
+
+        // then
+        assert_eq!(request.host, "https://localhost:50051");
+        assert_eq!(
+            request.tls,
+            Some(ClientTlsConfig::native().with_skip_hostname_verification(true))
+        );
+        assert_eq!(request.tls, core_client.borrow().get_tls_config().unwrap());
     }
 }
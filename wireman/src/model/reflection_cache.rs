@@ -0,0 +1,190 @@
+#![allow(clippy::module_name_repetitions)]
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::{error::Error, path::PathBuf};
+use wireman_config::Config;
+use wireman_core::ProtoDescriptor;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Caches the descriptors of reflected servers, so that a server's
+/// schema is available after a restart or while it is unreachable.
+///
+/// Each host is stored as `<directory>/<host>.protoset` next to a
+/// `<host>.json` file that holds the time of the reflection.
+#[derive(Default, Debug, Clone)]
+pub struct ReflectionCache {
+    /// The directory where the descriptors are cached
+    directory: PathBuf,
+}
+
+/// The metadata of a cached descriptor.
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheMeta {
+    /// The reflected host
+    host: String,
+
+    /// The time of the reflection in RFC 3339 format
+    timestamp: String,
+}
+
+impl ReflectionCache {
+    pub fn new(env: &Config) -> Self {
+        Self {
+            directory: PathBuf::from(env.reflection.directory_expanded()),
+        }
+    }
+
+    /// Caches the reflected descriptor of a host.
+    ///
+    /// # Errors
+    /// - Failed to create the cache directory or to write the files.
+    pub fn save(&self, host: &str, desc: &ProtoDescriptor) -> Result<()> {
+        std::fs::create_dir_all(&self.directory)?;
+        let (protoset, meta) = self.paths(host);
+        std::fs::write(protoset, desc.encode_file_descriptor_set())?;
+        let meta_data = CacheMeta {
+            host: host.to_string(),
+            timestamp: Local::now().to_rfc3339(),
+        };
+        std::fs::write(meta, serde_json::to_string_pretty(&meta_data)?)?;
+        Ok(())
+    }
+
+    /// Returns the time when the descriptor of a host was cached, if any.
+    pub fn timestamp(&self, host: &str) -> Option<DateTime<Local>> {
+        let (protoset, meta) = self.paths(host);
+        if !protoset.exists() {
+            return None;
+        }
+        let data = std::fs::read_to_string(meta).ok()?;
+        let meta_data: CacheMeta = serde_json::from_str(&data).ok()?;
+        if meta_data.host != host {
+            return None;
+        }
+        let timestamp = DateTime::parse_from_rfc3339(&meta_data.timestamp).ok()?;
+        Some(timestamp.with_timezone(&Local))
+    }
+
+    /// Loads the cached descriptor of a host.
+    ///
+    /// # Errors
+    /// - No descriptor is cached for the host.
+    /// - Failed to decode the cached descriptor.
+    pub fn load(&self, host: &str) -> Result<ProtoDescriptor> {
+        if self.timestamp(host).is_none() {
+            return Err(format!("no cached schema for {host}").into());
+        }
+        let (protoset, _) = self.paths(host);
+        let mut desc = ProtoDescriptor::default();
+        desc.add_protosets([protoset])?;
+        Ok(desc)
+    }
+
+    /// Returns the paths of the cached descriptor and its metadata.
+    fn paths(&self, host: &str) -> (PathBuf, PathBuf) {
+        let stem = file_stem(host);
+        (
+            self.directory.join(format!("{stem}.protoset")),
+            self.directory.join(format!("{stem}.json")),
+        )
+    }
+}
+
+/// Maps a host such as `http://localhost:50051` to a file name.
+fn file_stem(host: &str) -> String {
+    host.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Formats the time since a timestamp, e.g. `3h ago`.
+pub fn format_age(timestamp: DateTime<Local>) -> String {
+    let seconds = (Local::now() - timestamp).num_seconds().max(0);
+    match seconds {
+        0..=59 => format!("{seconds}s ago"),
+        60..=3599 => format!("{}m ago", seconds / 60),
+        3600..=86399 => format!("{}h ago", seconds / 3600),
+        _ => format!("{}d ago", seconds / 86400),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::temp_path;
+
+    fn test_cache(dir: &std::path::Path) -> ReflectionCache {
+        ReflectionCache {
+            directory: dir.to_path_buf(),
+        }
+    }
+
+    #[test]
+    fn test_reflection_cache() {
+        // given
+        let (_guard, dir) = temp_path("reflection");
+        let cache = test_cache(&dir);
+        let host = "http://localhost:50051";
+        let desc = ProtoDescriptor::new(["../wireman-core/test_files"], ["test.proto"]).unwrap();
+
+        // when
+        let before = cache.timestamp(host);
+        cache.save(host, &desc).unwrap();
+        let after = cache.timestamp(host);
+        let loaded = cache.load(host).unwrap();
+        let other = cache.load("http://localhost:50052");
+
+        // then
+        assert!(before.is_none());
+        assert_eq!(after.map(format_age), Some("0s ago".to_string()));
+        let method = loaded.get_method_by_name("proto.TestService", "Simple");
+        assert_eq!(
+            method.map(|m| m.full_name().to_string()),
+            Some("proto.TestService.Simple".to_string())
+        );
+        assert!(other.is_err());
+        assert_eq!(file_stem(host), "http___localhost_50051");
+    }
+
+    #[test]
+    fn test_reflection_cache_missing_or_corrupt() {
+        // given
+        let (_guard, dir) = temp_path("reflection");
+        let cache = test_cache(&dir);
+        let host = "http://localhost:50051";
+        let desc = ProtoDescriptor::new(["../wireman-core/test_files"], ["test.proto"]).unwrap();
+        cache.save(host, &desc).unwrap();
+        let (protoset, meta) = cache.paths(host);
+
+        // when
+        std::fs::write(&protoset, "not a protoset").unwrap();
+        let corrupt = cache.load(host);
+
+        // then
+        assert!(corrupt.is_err());
+
+        // when
+        std::fs::remove_file(&protoset).unwrap();
+        let missing = cache.load(host);
+
+        // then
+        assert!(missing.is_err());
+        assert!(cache.timestamp(host).is_none());
+
+        // when
+        cache.save(host, &desc).unwrap();
+        std::fs::write(&meta, "{").unwrap();
+        let corrupt_meta = cache.load(host);
+
+        // then
+        assert!(corrupt_meta.is_err());
+        assert!(cache.timestamp(host).is_none());
+    }
+}
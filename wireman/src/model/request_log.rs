@@ -0,0 +1,404 @@
+use super::{headers::AuthHeader, messages::RequestResult, MessagesModel};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+use tui_widget_list::ListState;
+use wireman_config::Config;
+use wireman_core::descriptor::RequestMessage;
+use wireman_logger::Logger;
+
+/// The file name of the request log inside the history directory.
+const LOG_FNAME: &str = "requests.jsonl";
+
+/// The value of redacted headers.
+const REDACTED: &str = "<redacted>";
+
+/// Headers whose values are never written to the log.
+const SECRET_HEADERS: [&str; 5] = [
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "x-api-key",
+    "api-key",
+];
+
+/// An append-only log of every dispatched call. Each call is stored
+/// as one json line in the history directory.
+#[derive(Default)]
+pub struct RequestLogModel {
+    /// The log file. `None` if the history is disabled.
+    path: Option<PathBuf>,
+
+    /// The call that is in flight
+    pending: Option<PendingCall>,
+
+    /// Whether the log browser is open
+    toggled: bool,
+
+    /// The logged calls, newest first. Loaded when the browser is opened.
+    pub entries: Vec<LogEntry>,
+
+    /// The selection state of the filtered entries
+    pub state: ListState,
+
+    /// Filters the entries
+    pub filter: Option<String>,
+
+    /// Whether the search line is edited
+    pub searching: bool,
+}
+
+struct PendingCall {
+    entry: LogEntry,
+    responses: Vec<String>,
+    started: Instant,
+}
+
+impl RequestLogModel {
+    pub fn new(env: &Config) -> Self {
+        let directory = env.history.directory_expanded();
+        let path = if env.history.disabled || directory.is_empty() {
+            None
+        } else {
+            Some(PathBuf::from(directory).join(LOG_FNAME))
+        };
+        Self {
+            path,
+            ..Self::default()
+        }
+    }
+
+    /// Starts recording a dispatched call.
+    pub fn start(&mut self, entry: LogEntry) {
+        self.finish();
+        self.pending = Some(PendingCall {
+            entry,
+            responses: Vec::new(),
+            started: Instant::now(),
+        });
+    }
+
+    /// Records a response message or the error of the call in flight.
+    pub fn record(&mut self, result: &RequestResult) {
+        let Some(pending) = &mut self.pending else {
+            return;
+        };
+        if let Some(data) = result.get_data() {
+            pending.responses.push(data.to_string());
+        }
+        if let Some(error) = result.get_error() {
+            pending.entry.status = error.string();
+        }
+    }
+
+    /// Marks the call in flight as cancelled and writes it to the log.
+    pub fn cancel(&mut self) {
+        if let Some(pending) = &mut self.pending {
+            pending.entry.status = String::from("Cancelled");
+        }
+        self.finish();
+    }
+
+    /// Writes the call in flight to the log.
+    pub fn finish(&mut self) {
+        let Some(pending) = self.pending.take() else {
+            return;
+        };
+        let mut entry = pending.entry;
+        entry.latency_ms = u64::try_from(pending.started.elapsed().as_millis()).ok();
+        entry.response = match pending.responses.len() {
+            0 => None,
+            1 => pending.responses.into_iter().next(),
+            _ => Some(format!("[\n{}\n]", pending.responses.join(",\n"))),
+        };
+        if let Some(path) = &self.path {
+            if let Err(err) = append(path, &entry) {
+                Logger::debug(format!("request log: failed to write {path:?}: {err}"));
+            }
+        }
+    }
+
+    /// Whether the log browser is open.
+    pub fn toggled(&self) -> bool {
+        self.toggled
+    }
+
+    /// Opens or closes the log browser. Reads the log when it is opened.
+    pub fn toggle(&mut self) {
+        self.toggled = !self.toggled;
+        self.searching = false;
+        if self.toggled {
+            self.entries = self.path.as_deref().map(read).unwrap_or_default();
+            self.state
+                .select(Some(0).filter(|_| !self.filtered().is_empty()));
+        }
+    }
+
+    /// Returns the entries that match the filter.
+    pub fn filtered(&self) -> Vec<&LogEntry> {
+        let filter = self.filter.as_deref().unwrap_or_default().to_lowercase();
+        self.entries
+            .iter()
+            .filter(|entry| entry.matches(&filter))
+            .collect()
+    }
+
+    /// Returns the selected entry.
+    pub fn selected(&self) -> Option<&LogEntry> {
+        let index = self.state.selected?;
+        self.filtered().get(index).copied()
+    }
+
+    /// Selects the next entry.
+    pub fn next(&mut self) {
+        let len = self.filtered().len();
+        if len == 0 {
+            return;
+        }
+        let i = match self.state.selected {
+            Some(i) if i + 1 < len => i + 1,
+            _ => 0,
+        };
+        self.state.select(Some(i));
+    }
+
+    /// Selects the previous entry.
+    pub fn prev(&mut self) {
+        let len = self.filtered().len();
+        if len == 0 {
+            return;
+        }
+        let i = match self.state.selected {
+            Some(i) if i > 0 => i - 1,
+            _ => len - 1,
+        };
+        self.state.select(Some(i));
+    }
+
+    pub fn push_char_filter(&mut self, ch: char) {
+        self.filter.get_or_insert_with(String::new).push(ch);
+        self.reset_selection();
+    }
+
+    pub fn remove_char_filter(&mut self) {
+        if let Some(filter) = &mut self.filter {
+            filter.pop();
+        }
+        self.reset_selection();
+    }
+
+    pub fn clear_filter(&mut self) {
+        self.filter = None;
+        self.reset_selection();
+    }
+
+    fn reset_selection(&mut self) {
+        let is_empty = self.filtered().is_empty();
+        self.state.select(Some(0).filter(|_| !is_empty));
+    }
+}
+
+/// Appends an entry as a single json line.
+fn append(path: &Path, entry: &LogEntry) -> std::io::Result<()> {
+    let line = serde_json::to_string(entry)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+/// Reads the log, newest first. Lines that cannot be parsed are skipped.
+fn read(path: &Path) -> Vec<LogEntry> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .rev()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// A logged call.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LogEntry {
+    /// The time the call was dispatched, in RFC 3339 format
+    pub timestamp: String,
+    /// The method, e.g. `package.Service/Method`
+    pub method: String,
+    /// The server address
+    pub address: String,
+    /// The sent headers. Secrets are redacted.
+    pub metadata: BTreeMap<String, String>,
+    /// The request as entered in the editor
+    pub request: String,
+    /// The response message. Multiple messages of a stream are
+    /// stored as a json array.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<String>,
+    /// The status of the call
+    pub status: String,
+    /// The duration of the call in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+}
+
+impl LogEntry {
+    /// Creates a new entry for a dispatched call. `request` is the json of
+    /// the request editor and `address` the address as entered by the user,
+    /// both with their variables unexpanded.
+    pub fn new(head: &RequestMessage, request: String, address: String) -> Self {
+        let method = head.method_descriptor();
+        let metadata = head
+            .metadata()
+            .as_ref()
+            .map(|metadata| metadata.entries())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(key, val)| {
+                let val = redact(&key, &val);
+                (key, val)
+            })
+            .collect();
+        Self {
+            timestamp: chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            method: format!("{}/{}", method.parent_service().full_name(), method.name()),
+            address,
+            metadata,
+            request,
+            response: None,
+            status: String::from("OK"),
+            latency_ms: None,
+        }
+    }
+
+    /// Returns the service and method name.
+    pub fn service_and_method(&self) -> Option<(&str, &str)> {
+        self.method.split_once('/')
+    }
+
+    /// Whether any field of the entry contains the lowercase filter.
+    fn matches(&self, filter: &str) -> bool {
+        if filter.is_empty() {
+            return true;
+        }
+        [
+            &self.timestamp,
+            &self.method,
+            &self.address,
+            &self.request,
+            &self.status,
+        ]
+        .into_iter()
+        .chain(self.response.as_ref())
+        .chain(self.metadata.values())
+        .any(|field| field.to_lowercase().contains(filter))
+    }
+
+    /// Loads the request into the editor, converted to the editor's syntax,
+    /// and the logged response into the response editor. Redacted headers
+    /// are not restored.
+    pub fn apply(&self, messages: &mut MessagesModel) {
+        {
+            let mut headers = messages.headers.borrow_mut();
+            headers.addr.set_text_raw(&self.address);
+            let auth_key = AuthHeader::key();
+            let metadata: BTreeMap<String, String> = self
+                .metadata
+                .iter()
+                .filter(|(key, val)| **key != auth_key && *val != REDACTED)
+                .map(|(key, val)| (key.clone(), val.clone()))
+                .collect();
+            if !metadata.is_empty() {
+                headers.meta.set_btree(&metadata);
+            }
+        }
+        messages.set_request_json(&self.request);
+        let response = self.response.as_deref().unwrap_or(&self.status);
+        messages.response.editor.set_error(None);
+        messages.response.editor.set_text_raw(response);
+    }
+}
+
+/// Redacts the value of secret headers. The scheme of an authorization
+/// header such as `Bearer` is kept.
+fn redact(key: &str, val: &str) -> String {
+    let key = key.to_lowercase();
+    let is_secret = SECRET_HEADERS.contains(&key.as_str())
+        || ["token", "secret", "password"]
+            .iter()
+            .any(|part| key.contains(part));
+    if !is_secret {
+        return val.to_string();
+    }
+    match val.split_once(' ') {
+        Some((scheme, _)) if key.ends_with("authorization") => format!("{scheme} {REDACTED}"),
+        _ => String::from(REDACTED),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::temp_path;
+
+    fn log_entry(method: &str, status: &str) -> LogEntry {
+        LogEntry {
+            timestamp: "2024-01-01T12:00:00.000Z".to_string(),
+            method: method.to_string(),
+            address: "http://localhost:50051".to_string(),
+            metadata: BTreeMap::new(),
+            request: "{}".to_string(),
+            response: None,
+            status: status.to_string(),
+            latency_ms: Some(12),
+        }
+    }
+
+    #[test]
+    fn test_redact() {
+        assert_eq!(
+            redact("authorization", "Bearer ey.123"),
+            "Bearer <redacted>"
+        );
+        assert_eq!(redact("x-api-key", "123"), "<redacted>");
+        assert_eq!(redact("x-session-token", "123"), "<redacted>");
+        assert_eq!(redact("x-request-id", "42"), "42");
+    }
+
+    #[test]
+    fn test_append_and_filter() {
+        // given
+        let (_guard, path) = temp_path("log.jsonl");
+        let mut model = RequestLogModel {
+            path: Some(path.clone()),
+            ..RequestLogModel::default()
+        };
+
+        // when
+        for entry in [
+            log_entry("proto.TestService/Simple", "OK"),
+            log_entry("proto.TestService/Streaming", "grpc: status: Unavailable"),
+        ] {
+            model.start(entry);
+            model.finish();
+        }
+        model.toggle();
+
+        // then
+        assert_eq!(model.entries.len(), 2);
+        assert_eq!(model.entries[0].method, "proto.TestService/Streaming");
+        assert_eq!(model.selected(), Some(&model.entries[0]));
+
+        // when
+        "simple".chars().for_each(|ch| model.push_char_filter(ch));
+
+        // then
+        let filtered = model.filtered();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].method, "proto.TestService/Simple");
+    }
+}
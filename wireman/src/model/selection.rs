@@ -4,7 +4,7 @@ use std::rc::Rc;
 use tui_widget_list::ListState;
 use wireman_core::{MethodDescriptor, ProtoDescriptor, ServiceDescriptor};
 
-use super::core_client::CoreClient;
+use super::core_client::{CoreClient, ServiceSource};
 
 #[derive(Clone)]
 pub struct SelectionModel {
@@ -75,24 +75,51 @@ impl SelectionModel {
         }
     }
 
-    /// Toggles the reflection mode.
+    /// Toggles the reflection dialog. Reflected services are kept
+    /// when the dialog is opened again to reflect another server.
     pub fn toggle_reflection_mode(&mut self) {
         match self.selection_mode {
-            SelectionMode::File => self.selection_mode = SelectionMode::ReflectionDialog,
-            SelectionMode::ReflectionDialog => self.selection_mode = SelectionMode::File,
-            SelectionMode::Reflection => {
-                let _ = self.core_client.borrow_mut().reset();
-                self.load_core_services_and_methods_from_files();
-                self.selection_mode = SelectionMode::File;
+            SelectionMode::File | SelectionMode::Reflection => {
+                self.selection_mode = SelectionMode::ReflectionDialog;
             }
+            SelectionMode::ReflectionDialog => self.close_reflection_dialog(),
             SelectionMode::Configuration => {}
         }
     }
 
-    /// Update the proto descriptor.
-    pub fn update_descriptor(&mut self, desc: ProtoDescriptor) {
-        self.core_client.borrow_mut().update_proto_descriptor(desc);
+    /// Closes the reflection dialog.
+    pub fn close_reflection_dialog(&mut self) {
+        self.selection_mode = if self.core_client.borrow().has_reflection() {
+            SelectionMode::Reflection
+        } else {
+            SelectionMode::File
+        };
+    }
+
+    /// Removes the services of all reflected servers and switches
+    /// back to the file mode.
+    pub fn untoggle_reflection_mode(&mut self) {
+        self.core_client.borrow_mut().clear_reflection();
         self.load_core_services_and_methods_from_files();
+        self.selection_mode = SelectionMode::File;
+    }
+
+    /// Adds the proto descriptor of a reflected server to the services
+    /// of the local protos.
+    pub fn add_reflection(&mut self, address: &str, desc: ProtoDescriptor) {
+        self.core_client.borrow_mut().add_reflection(address, desc);
+        self.load_core_services_and_methods_from_files();
+        self.selection_mode = SelectionMode::Reflection;
+    }
+
+    /// Returns where a service is defined. Only set if a server
+    /// was reflected, local services need no marker otherwise.
+    pub fn service_source(&self, name: &str) -> Option<ServiceSource> {
+        let core_client = self.core_client.borrow();
+        if !core_client.has_reflection() {
+            return None;
+        }
+        core_client.get_service_source(name)
     }
 
     /// Select the next service.
@@ -146,6 +173,22 @@ impl SelectionModel {
         true
     }
 
+    /// Selects a method and its service. Clears the filters.
+    /// Returns true if the method was found.
+    pub fn select_method(&mut self, method: &MethodDescriptor) -> bool {
+        self.services_filter = None;
+        let service = method.parent_service().full_name().to_string();
+        let Some(index) = self.services.iter().position(|s| *s == service) else {
+            return false;
+        };
+        self.select_service_by_index(index);
+        let Some(index) = self.methods.iter().position(|m| m == method.name()) else {
+            return false;
+        };
+        self.methods_state.select(Some(index));
+        true
+    }
+
     /// Select the first service.
     pub fn select_first_service(&mut self) {
         self.select_service_by_index(0);
@@ -0,0 +1,84 @@
+use wireman_core::descriptor::{validate_json, JsonOptions, JsonProblem, Position};
+use wireman_core::MessageDescriptor;
+
+/// The problems of the request editor's json, validated against the
+/// input message of the selected method.
+#[derive(Default)]
+pub struct ValidationModel {
+    /// The text, message type and options of the last validation
+    key: Option<(String, String, JsonOptions)>,
+
+    /// The problems ordered by their position
+    problems: Vec<JsonProblem>,
+}
+
+impl ValidationModel {
+    /// Validates the text unless it was already validated with the same
+    /// message type and options. Returns whether the text was validated.
+    pub fn update(&mut self, text: &str, desc: &MessageDescriptor, options: &JsonOptions) -> bool {
+        let key = (text.to_string(), desc.full_name().to_string(), *options);
+        if self.key.as_ref() == Some(&key) {
+            return false;
+        }
+        self.problems = validate_json(desc, text, options);
+        self.key = Some(key);
+        true
+    }
+
+    /// Removes all problems.
+    pub fn clear(&mut self) {
+        self.key = None;
+        self.problems.clear();
+    }
+
+    /// Returns the problems ordered by their position.
+    pub fn problems(&self) -> &[JsonProblem] {
+        &self.problems
+    }
+
+    /// Returns the problem at a position, or the first problem.
+    pub fn current(&self, position: Position) -> Option<&JsonProblem> {
+        self.problems
+            .iter()
+            .find(|problem| problem.start <= position && position < problem.end)
+            .or_else(|| self.problems.first())
+    }
+
+    /// Returns the first problem behind a position. Wraps around to
+    /// the first problem.
+    pub fn next(&self, position: Position) -> Option<&JsonProblem> {
+        self.problems
+            .iter()
+            .find(|problem| problem.start > position)
+            .or_else(|| self.problems.first())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wireman_core::ProtoDescriptor;
+
+    #[test]
+    fn test_next_problem() {
+        // given
+        let desc = ProtoDescriptor::new(["../wireman-core/test_files"], ["test.proto"]).unwrap();
+        let input = desc
+            .get_method_by_name("proto.TestService", "Multiple")
+            .unwrap()
+            .input();
+        let mut model = ValidationModel::default();
+        let text = "{\n  \"id\": 1,\n  \"number\": \"a\"\n}";
+
+        // when
+        model.update(text, &input, &JsonOptions::default());
+        let first = model.next(Position::new(0, 0)).unwrap().start;
+        let wrapped = model.next(first).unwrap().start;
+
+        // then
+        assert_eq!(model.problems().len(), 1);
+        assert_eq!(first, Position::new(1, 8));
+        assert_eq!(wrapped, first);
+        assert_eq!(model.current(Position::new(1, 8)).unwrap().start, first);
+    }
+}
@@ -0,0 +1,110 @@
+#![allow(clippy::module_name_repetitions)]
+use crate::widgets::editor::{yank_to_clipboard, TextEditor};
+
+/// The state of the wire bytes dialog. The dialog decodes base64, hex or
+/// binary file input into a message editor and shows the encoded request.
+pub struct WireModel {
+    /// Whether the dialog is open
+    toggled: bool,
+
+    /// The editor that decoded messages are loaded into
+    pub target: WireTarget,
+
+    /// The full name of the message type of the bytes
+    pub message_type: TextEditor,
+
+    /// The wire bytes as base64, hex or `@path` to a binary file
+    pub input: TextEditor,
+
+    /// The selected input field
+    pub selected: WireSelection,
+
+    /// The request encoded as base64 and hex. Only set if the dialog
+    /// is opened from the request editor.
+    pub encoded: Option<(String, String)>,
+
+    /// The error of the last action
+    pub error: Option<String>,
+}
+
+impl Default for WireModel {
+    fn default() -> Self {
+        Self {
+            toggled: false,
+            target: WireTarget::default(),
+            message_type: TextEditor::single(),
+            input: TextEditor::single(),
+            selected: WireSelection::default(),
+            encoded: None,
+            error: None,
+        }
+    }
+}
+
+/// The editor that decoded messages are loaded into.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum WireTarget {
+    #[default]
+    Request,
+    Response,
+}
+
+/// The selected input field of the wire bytes dialog.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum WireSelection {
+    MessageType,
+    #[default]
+    Input,
+}
+
+impl WireModel {
+    /// Whether the dialog is open.
+    pub fn toggled(&self) -> bool {
+        self.toggled
+    }
+
+    /// Opens the dialog for the given editor and message type.
+    pub fn open(&mut self, target: WireTarget, message_type: &str) {
+        *self = Self {
+            toggled: true,
+            target,
+            ..Self::default()
+        };
+        self.message_type.set_text_raw(message_type);
+    }
+
+    /// Closes the dialog.
+    pub fn close(&mut self) {
+        self.toggled = false;
+    }
+
+    /// Selects the other input field.
+    pub fn toggle_selection(&mut self) {
+        self.selected = match self.selected {
+            WireSelection::MessageType => WireSelection::Input,
+            WireSelection::Input => WireSelection::MessageType,
+        };
+    }
+
+    /// Yanks the base64 encoded request.
+    pub fn yank_base64(&self) {
+        if let Some((base64, _)) = &self.encoded {
+            yank_to_clipboard(base64);
+        }
+    }
+
+    /// Yanks the hex encoded request.
+    pub fn yank_hex(&self) {
+        if let Some((_, hex)) = &self.encoded {
+            yank_to_clipboard(hex);
+        }
+    }
+
+    /// Get the selected editor
+    pub fn selected_editor_mut(&mut self) -> &mut TextEditor {
+        match self.selected {
+            WireSelection::MessageType => &mut self.message_type,
+            WireSelection::Input => &mut self.input,
+        }
+    }
+}
@@ -0,0 +1,11 @@
+//! Helpers shared by the unit tests.
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+/// Returns the path of `name` inside a new temporary directory. The
+/// directory is removed when the returned guard is dropped.
+pub(crate) fn temp_path(name: &str) -> (TempDir, PathBuf) {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(name);
+    (dir, path)
+}
@@ -1,8 +1,14 @@
+pub mod collections;
+pub mod completion_popup;
 pub mod configuration;
+pub mod docs_panel;
 pub mod headers;
 pub mod history_tab;
+pub mod json_profile_dialog;
 pub mod messages;
 pub mod reflection_dialog;
+pub mod request_log;
 pub mod root;
 pub mod selection;
 pub(super) mod util;
+pub mod wire_dialog;
@@ -0,0 +1,161 @@
+use crate::model::collections::{CollectionEntry, CollectionsModel};
+use crate::widgets::list::ListItem;
+use ratatui::layout::Constraint::{Length, Min, Percentage};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Padding, Paragraph, Wrap};
+use tui_widget_list::{ListBuilder, ListView};
+use wireman_theme::Theme;
+
+/// The page with the named requests of the selected method.
+pub struct CollectionsPage<'a> {
+    pub model: &'a mut CollectionsModel,
+    pub method: Option<String>,
+    pub main_split: Direction,
+}
+
+impl CollectionsPage<'_> {
+    pub fn footer_keys(model: &CollectionsModel) -> Vec<(&'static str, &'static str)> {
+        if model.input.is_some() {
+            return vec![("⏎", "Submit"), ("Esc", "Cancel")];
+        }
+        let mut keys = vec![("a", "Save current")];
+        if model.selected().is_some() {
+            keys.push(("⏎", "Load"));
+            keys.push(("r", "Rename"));
+            keys.push(("c", "Duplicate"));
+            keys.push(("d", "Delete"));
+        }
+        keys.push(("?", "Show help"));
+        keys
+    }
+}
+
+impl Widget for CollectionsPage<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = Theme::global();
+
+        let show_input = u16::from(self.model.input.is_some() || self.model.error.is_some());
+        let [main, input_area] = Layout::vertical([Min(0), Length(show_input)]).areas(area);
+        let [list, preview] =
+            Layout::new(self.main_split, [Percentage(40), Percentage(60)]).areas(main);
+
+        // Saved requests
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .title_alignment(Alignment::Center)
+            .padding(Padding::horizontal(1));
+        let title = match &self.method {
+            Some(method) => format!(" {method} "),
+            None => String::from(" Collections "),
+        };
+        let list_block = block
+            .clone()
+            .title(title)
+            .title_style(theme.title.focused)
+            .border_style(theme.border.focused)
+            .border_type(theme.border.border_type_focused);
+
+        let entries = self.model.entries.clone();
+        let item_count = entries.len();
+        if item_count == 0 {
+            let text = if self.method.is_some() {
+                "No saved requests. Save the current request with \"a\"."
+            } else {
+                "Go back and select a method."
+            };
+            Paragraph::new(text)
+                .style(theme.base.unfocused)
+                .wrap(Wrap { trim: true })
+                .block(list_block.clone())
+                .render(list, buf);
+        }
+
+        let builder = ListBuilder::new(move |context| {
+            let entry = &entries[context.index];
+            let is_first_in_folder = entry.folder.is_some()
+                && (context.index == 0 || entries[context.index - 1].folder != entry.folder);
+
+            let mut item = EntryItem::new(entry, is_first_in_folder);
+            if context.is_selected {
+                item.item.style = theme.highlight.focused.reversed();
+                item.item.prefix = Some(">>");
+            } else {
+                item.item.style = theme.base.focused;
+            }
+            let height = 1 + u16::from(is_first_in_folder);
+
+            (item, height)
+        });
+        if item_count > 0 {
+            ListView::new(builder, item_count)
+                .block(list_block)
+                .scroll_padding(1)
+                .render(list, buf, &mut self.model.state);
+        }
+
+        // Preview of the selected request
+        let preview_block = block
+            .title(" Preview ")
+            .title_style(theme.title.unfocused)
+            .border_style(theme.border.unfocused)
+            .border_type(theme.border.border_type_unfocused);
+        let text = self
+            .model
+            .preview
+            .as_ref()
+            .map(crate::model::history::HistoryData::to_json)
+            .unwrap_or_default();
+        Paragraph::new(text)
+            .style(theme.base.unfocused)
+            .block(preview_block)
+            .render(preview, buf);
+
+        // Name input or error of the last action
+        if let Some(input) = &self.model.input {
+            Line::from(format!("{} (folder/name): {}", input.action, input.text))
+                .style(theme.base.focused)
+                .render(input_area, buf);
+        } else if let Some(error) = &self.model.error {
+            Line::from(error.as_str()).red().render(input_area, buf);
+        }
+    }
+}
+
+/// A saved request. The first request of a folder is rendered
+/// below the folder name.
+struct EntryItem {
+    folder: Option<String>,
+    item: ListItem<'static>,
+}
+
+impl EntryItem {
+    fn new(entry: &CollectionEntry, is_first_in_folder: bool) -> Self {
+        let folder = entry.folder.clone().filter(|_| is_first_in_folder);
+        let name = if entry.folder.is_some() {
+            format!("  {}", entry.name)
+        } else {
+            entry.name.clone()
+        };
+        Self {
+            folder,
+            item: ListItem::new(name),
+        }
+    }
+}
+
+impl Widget for EntryItem {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = Theme::global();
+        let area = match self.folder {
+            Some(folder) => {
+                let [header, area] = Layout::vertical([Length(1), Length(1)]).areas(area);
+                Line::from(format!("{folder}/"))
+                    .style(theme.title.unfocused)
+                    .render(header, buf);
+                area
+            }
+            None => area,
+        };
+        self.item.render(area, buf);
+    }
+}
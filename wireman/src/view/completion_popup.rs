@@ -0,0 +1,72 @@
+use crate::model::completion::CompletionModel;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear};
+use wireman_theme::Theme;
+
+/// The maximum number of completions that are shown at once.
+const MAX_VISIBLE: usize = 8;
+
+/// The popup that lists the completions of the request editor.
+pub struct CompletionPopup<'a> {
+    pub model: &'a CompletionModel,
+}
+
+impl CompletionPopup<'_> {
+    pub fn footer_keys() -> Vec<(&'static str, &'static str)> {
+        vec![("Tab", "Accept"), ("↑/↓", "Select"), ("Esc", "Close")]
+    }
+
+    /// Returns the area of the popup in the lower left corner of the editor.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn area(&self, editor: Rect) -> Rect {
+        let visible = self.model.items().len().min(MAX_VISIBLE) as u16;
+        let width = self
+            .model
+            .items()
+            .iter()
+            .map(|item| item.label.chars().count() + item.detail.chars().count() + 3)
+            .max()
+            .unwrap_or_default() as u16
+            + 2;
+        let inner = editor.inner(Margin::new(1, 1));
+        let width = width.min(inner.width);
+        let height = (visible + 2).min(inner.height);
+        Rect::new(inner.x, inner.bottom() - height, width, height)
+    }
+}
+
+impl Widget for CompletionPopup<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = Theme::global();
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .style(theme.base.focused)
+            .border_style(theme.border.focused)
+            .border_type(theme.border.border_type_focused);
+        let inner = block.inner(area);
+        Clear.render(area, buf);
+        block.render(area, buf);
+
+        let items = self.model.items();
+        let offset = self.model.selected.saturating_sub(MAX_VISIBLE - 1);
+        let label_width = items
+            .iter()
+            .map(|item| item.label.chars().count())
+            .max()
+            .unwrap_or_default();
+        let rows =
+            Layout::vertical(vec![Constraint::Length(1); inner.height as usize]).split(inner);
+        for (row, (index, item)) in rows.iter().zip(items.iter().enumerate().skip(offset)) {
+            let line = Line::from(vec![
+                Span::from(format!(" {:label_width$} ", item.label)),
+                Span::from(format!("{} ", item.detail)).style(theme.base.unfocused),
+            ]);
+            let line = if index == self.model.selected {
+                line.style(theme.highlight.focused.reversed())
+            } else {
+                line
+            };
+            line.render(*row, buf);
+        }
+    }
+}
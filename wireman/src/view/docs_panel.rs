@@ -0,0 +1,149 @@
+use crate::model::completion::field_detail;
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Widget, Wrap},
+};
+use wireman_core::{
+    descriptor::Documented, FieldDescriptor, MessageDescriptor, MethodDescriptor, ServiceDescriptor,
+};
+use wireman_theme::Theme;
+
+/// The side panel that documents the selected service and method
+/// with the comments of their proto definitions.
+pub struct DocsPanel {
+    pub service: Option<ServiceDescriptor>,
+    pub method: Option<MethodDescriptor>,
+
+    /// The field at the cursor of the request editor
+    pub field: Option<FieldDescriptor>,
+}
+
+impl DocsPanel {
+    fn lines(&self) -> Vec<Line<'static>> {
+        let theme = Theme::global();
+        let mut lines = Vec::new();
+        if let Some(service) = &self.service {
+            lines.push(heading("service", service.full_name(), service));
+            push_comments(&mut lines, service, 0);
+        }
+        let Some(method) = &self.method else {
+            if lines.is_empty() {
+                lines.push(Line::styled("No service selected.", theme.base.unfocused));
+            }
+            return lines;
+        };
+        lines.push(Line::default());
+        lines.push(heading("rpc", &method_signature(method), method));
+        push_comments(&mut lines, method, 0);
+
+        if let Some(field) = &self.field {
+            lines.push(Line::default());
+            lines.push(Line::styled("Cursor", theme.title.focused));
+            push_field(&mut lines, field);
+        }
+
+        for (title, message) in [("Request", method.input()), ("Response", method.output())] {
+            lines.push(Line::default());
+            lines.push(Line::styled(
+                format!("{title} {}", message.full_name()),
+                theme.title.focused,
+            ));
+            push_message(&mut lines, &message);
+        }
+        lines
+    }
+}
+
+impl Widget for DocsPanel {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = Theme::global();
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .style(theme.base.unfocused)
+            .title_style(theme.title.unfocused)
+            .border_style(theme.border.unfocused)
+            .border_type(theme.border.border_type_unfocused)
+            .title(" Docs ")
+            .title_alignment(Alignment::Center)
+            .padding(Padding::horizontal(1));
+        Clear.render(area, buf);
+        Paragraph::new(self.lines())
+            .wrap(Wrap { trim: false })
+            .block(block)
+            .render(area, buf);
+    }
+}
+
+/// A line such as `rpc Simple(SimpleReq) returns (EmptyResp)` followed by
+/// a deprecation marker if the element is deprecated.
+fn heading(keyword: &str, name: &str, element: &impl Documented) -> Line<'static> {
+    let theme = Theme::global();
+    let mut spans = vec![
+        Span::styled(format!("{keyword} "), theme.base.unfocused),
+        Span::styled(name.to_string(), theme.highlight.focused.bold()),
+    ];
+    push_deprecated(&mut spans, element);
+    Line::from(spans)
+}
+
+fn method_signature(method: &MethodDescriptor) -> String {
+    let stream = |streaming: bool| if streaming { "stream " } else { "" };
+    format!(
+        "{}({}{}) returns ({}{})",
+        method.name(),
+        stream(method.is_client_streaming()),
+        method.input().name(),
+        stream(method.is_server_streaming()),
+        method.output().name(),
+    )
+}
+
+/// Lists the fields of a message with their types and comments.
+fn push_message(lines: &mut Vec<Line<'static>>, message: &MessageDescriptor) {
+    let theme = Theme::global();
+    if message.is_deprecated() {
+        lines.push(Line::styled("[deprecated]", Style::new().red()));
+    }
+    push_comments(lines, message, 0);
+    if message.fields().len() == 0 {
+        lines.push(Line::styled("  (no fields)", theme.base.unfocused));
+    }
+    for field in message.fields() {
+        push_field(lines, &field);
+    }
+}
+
+/// A line such as `  number: int32 = 1` followed by the comments of the field.
+fn push_field(lines: &mut Vec<Line<'static>>, field: &FieldDescriptor) {
+    let theme = Theme::global();
+    let mut spans = vec![
+        Span::styled(format!("  {}", field.json_name()), theme.base.focused),
+        Span::styled(
+            format!(": {} = {}", field_detail(field), field.number()),
+            theme.base.unfocused,
+        ),
+    ];
+    push_deprecated(&mut spans, field);
+    lines.push(Line::from(spans));
+    push_comments(lines, field, 4);
+}
+
+fn push_deprecated(spans: &mut Vec<Span<'static>>, element: &impl Documented) {
+    if element.is_deprecated() {
+        spans.push(Span::styled(" [deprecated]", Style::new().red()));
+    }
+}
+
+fn push_comments(lines: &mut Vec<Line<'static>>, element: &impl Documented, indent: usize) {
+    let theme = Theme::global();
+    let Some(comments) = element.comments() else {
+        return;
+    };
+    let indent = " ".repeat(indent);
+    for comment in comments.lines() {
+        lines.push(Line::styled(
+            format!("{indent}{comment}"),
+            theme.base.unfocused.italic(),
+        ));
+    }
+}
@@ -1,7 +1,7 @@
 use std::rc::Rc;
 
 use super::root::layout;
-use crate::model::headers::{AuthSelection, HeadersModel, HeadersTab};
+use crate::model::headers::{AuthSelection, CompressionSelection, HeadersModel, HeadersTab};
 use crate::view::history_tab::HistoryTabs;
 use crate::widgets::editor::{view_single_selected, view_single_unselected};
 use crate::widgets::kv::KV;
@@ -11,11 +11,20 @@ use ratatui::{
     widgets::{Block, StatefulWidget, Tabs, Widget},
 };
 use tui_widget_list::{ListBuilder, ListState, ListView};
+use wireman_core::client::channel::ConnectionState;
 use wireman_theme::Theme;
 
+/// The width of the timeout input next to the address.
+const TIMEOUT_WIDTH: u16 = 16;
+
+/// The width of the send encoding input.
+const SEND_WIDTH: u16 = 20;
+
 pub struct HeadersPage<'a> {
     model: Rc<std::cell::RefCell<HeadersModel>>,
     pub history_tabs_area: Option<&'a mut Option<[Rect; 5]>>,
+    connection_state: Option<ConnectionState>,
+    address_error: Option<String>,
 }
 
 impl<'a> HeadersPage<'a> {
@@ -23,9 +32,21 @@ impl<'a> HeadersPage<'a> {
         Self {
             model,
             history_tabs_area: None,
+            connection_state: None,
+            address_error: None,
         }
     }
 
+    pub fn with_connection_state(mut self, state: ConnectionState) -> Self {
+        self.connection_state = Some(state);
+        self
+    }
+
+    pub fn with_address_error(mut self, error: Option<String>) -> Self {
+        self.address_error = error;
+        self
+    }
+
     pub fn with_history_tabs_area(mut self, area: &'a mut Option<[Rect; 5]>) -> Self {
         self.history_tabs_area = Some(area);
         self
@@ -39,6 +60,9 @@ impl<'a> HeadersPage<'a> {
             keys.push(("i", "Insert"));
             keys.push(("<C-e>", "Open in Editor"));
         }
+        if !model.environments.is_empty() {
+            keys.push(("<C-n>", "Environment"));
+        }
 
         keys
     }
@@ -49,15 +73,31 @@ impl Widget for HeadersPage<'_> {
         use ratatui::layout::Constraint::{Length, Min};
         let theme = wireman_theme::Theme::global();
         let sl = u16::from(!theme.hide_status);
-        let [addr_title, addr_content, _, auth_title, auth_content, _, meta_title, meta_content, status] =
-            layout(area, Direction::Vertical, &[1, 3, 1, 1, 4, 1, 1, 0, sl]);
+        let [addr_title, addr_content, _, auth_title, auth_content, _, comp_title, comp_content, _, meta_title, meta_content, status] =
+            layout(
+                area,
+                Direction::Vertical,
+                &[1, 3, 1, 1, 4, 1, 1, 3, 1, 1, 0, sl],
+            );
+        let [addr_title, _, timeout_title] =
+            layout(addr_title, Direction::Horizontal, &[0, 1, TIMEOUT_WIDTH]);
+        let [addr_content, _, timeout_content] =
+            layout(addr_content, Direction::Horizontal, &[0, 1, TIMEOUT_WIDTH]);
+        let [send_title, _, accept_title] =
+            layout(comp_title, Direction::Horizontal, &[SEND_WIDTH, 1, 0]);
+        let [send_content, _, accept_content] =
+            layout(comp_content, Direction::Horizontal, &[SEND_WIDTH, 1, 0]);
 
         {
             let mut model_mut = self.model.borrow_mut();
             model_mut.addr_title_area = Some(addr_title);
             model_mut.addr_content_area = Some(addr_content);
+            model_mut.timeout_title_area = Some(timeout_title);
+            model_mut.timeout_content_area = Some(timeout_content);
             model_mut.auth_title_area = Some(auth_title);
             model_mut.auth_content_area = Some(auth_content);
+            model_mut.compression_title_area = Some(comp_title);
+            model_mut.compression_content_area = Some(comp_content);
             model_mut.meta_title_area = Some(meta_title);
             model_mut.meta_content_area = Some(meta_content);
         }
@@ -71,11 +111,26 @@ impl Widget for HeadersPage<'_> {
         } else {
             theme.title.unfocused
         };
-        Block::default()
+        let mut block = Block::default()
             .title(String::from(" Address "))
             .title_alignment(Alignment::Center)
-            .title_style(style)
-            .render(addr_title, buf);
+            .title_style(style);
+        if let Some(state) = self.connection_state {
+            block = block.title(
+                Line::from(format!(" {state} "))
+                    .style(theme.base.unfocused)
+                    .left_aligned(),
+            );
+        }
+        if !model.environments.is_empty() {
+            let environment = model.environment.as_deref().unwrap_or("none");
+            block = block.title(
+                Line::from(format!(" Env: {environment} "))
+                    .style(theme.base.unfocused)
+                    .right_aligned(),
+            );
+        }
+        block.render(addr_title, buf);
 
         Address {
             state: model.addr.state.clone(),
@@ -83,6 +138,35 @@ impl Widget for HeadersPage<'_> {
             selected: model.tab == HeadersTab::Addr,
         }
         .render(addr_content, buf);
+        if let Some(error) = &self.address_error {
+            // Shown on the bottom border of the address input.
+            let area = Rect {
+                x: addr_content.x + 2,
+                y: addr_content.bottom().saturating_sub(1),
+                width: addr_content.width.saturating_sub(4),
+                height: 1,
+            };
+            Line::from(format!(" {error} ")).red().render(area, buf);
+        }
+
+        // Timeout
+        let style = if model.tab == HeadersTab::Timeout {
+            theme.title.focused
+        } else {
+            theme.title.unfocused
+        };
+        Block::default()
+            .title(String::from(" Timeout "))
+            .title_alignment(Alignment::Center)
+            .title_style(style)
+            .render(timeout_title, buf);
+
+        Address {
+            state: model.timeout.state.clone(),
+            title: String::new(),
+            selected: model.tab == HeadersTab::Timeout,
+        }
+        .render(timeout_content, buf);
 
         // Authentication
         let style = if model.tab == HeadersTab::Auth {
@@ -112,6 +196,47 @@ impl Widget for HeadersPage<'_> {
         };
         body.render(auth_content, buf);
 
+        // Compression
+        let is_compression_selected = model.tab == HeadersTab::Compression;
+        let selected = &model.compression.selected;
+        for (title, area, is_selected) in [
+            (
+                " Send encoding ",
+                send_title,
+                *selected == CompressionSelection::Send,
+            ),
+            (
+                " Accept encodings ",
+                accept_title,
+                *selected == CompressionSelection::Accept,
+            ),
+        ] {
+            let style = if is_compression_selected && is_selected {
+                theme.title.focused
+            } else {
+                theme.title.unfocused
+            };
+            Block::default()
+                .title(String::from(title))
+                .title_alignment(Alignment::Center)
+                .title_style(style)
+                .render(area, buf);
+        }
+
+        Address {
+            state: model.compression.send.state.clone(),
+            title: String::new(),
+            selected: is_compression_selected && *selected == CompressionSelection::Send,
+        }
+        .render(send_content, buf);
+
+        Address {
+            state: model.compression.accept.state.clone(),
+            title: String::new(),
+            selected: is_compression_selected && *selected == CompressionSelection::Accept,
+        }
+        .render(accept_content, buf);
+
         // Metadata
         let style = if model.tab == HeadersTab::Meta {
             theme.title.focused
@@ -258,7 +383,7 @@ impl Widget for Metadata {
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(list_height),
-                Constraint::Length(u16::from(self.focused) * 2),
+                Constraint::Length(u16::from(self.focused) * 3),
             ])
             .areas(area);
 
@@ -274,9 +399,9 @@ impl Widget for Metadata {
         );
         list.render(main, buf, &mut state);
         if self.focused {
-            let [add, del] = Layout::default()
+            let [add, del, bin] = Layout::default()
                 .direction(Direction::Vertical)
-                .constraints([Constraint::Length(1), Constraint::Length(1)])
+                .constraints([Constraint::Length(1); 3])
                 .areas(text);
             Line::from(vec![
                 Span::from("<C-a>: ").style(theme.title.unfocused),
@@ -288,6 +413,11 @@ impl Widget for Metadata {
                 Span::from("Delete header").style(theme.base.unfocused),
             ])
             .render(del, buf);
+            Line::from(vec![
+                Span::from("*-bin: ").style(theme.title.unfocused),
+                Span::from("Base64 or hex:<hex> value").style(theme.base.unfocused),
+            ])
+            .render(bin, buf);
         }
     }
 }
@@ -0,0 +1,48 @@
+use crate::model::json_profile::{JsonOption, JsonProfileModel};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Padding, Paragraph};
+use wireman_core::descriptor::JsonOptions;
+use wireman_theme::Theme;
+
+/// The dialog to toggle the options of the JSON serialization profile.
+pub struct JsonProfileDialog<'a> {
+    pub model: &'a JsonProfileModel,
+    pub options: JsonOptions,
+}
+
+impl JsonProfileDialog<'_> {
+    pub fn footer_keys() -> Vec<(&'static str, &'static str)> {
+        vec![("j/k", "Scroll"), ("⏎", "Toggle"), ("Esc", "Close")]
+    }
+}
+
+impl Widget for JsonProfileDialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = Theme::global();
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .style(theme.base.focused)
+            .title_style(theme.title.focused)
+            .border_style(theme.border.focused)
+            .border_type(theme.border.border_type_focused)
+            .title(" JSON Options ")
+            .title_alignment(Alignment::Center)
+            .padding(Padding::uniform(1));
+
+        let lines: Vec<Line> = JsonOption::ALL
+            .iter()
+            .enumerate()
+            .map(|(index, option)| {
+                let check = if option.get(&self.options) { "x" } else { " " };
+                let line = Line::from(format!("[{check}] {}", option.label()));
+                if index == self.model.selected {
+                    line.style(theme.highlight.focused.reversed())
+                } else {
+                    line.style(theme.base.focused)
+                }
+            })
+            .collect();
+
+        Paragraph::new(lines).block(block).render(area, buf);
+    }
+}
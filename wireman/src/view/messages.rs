@@ -1,11 +1,18 @@
 #![allow(clippy::module_name_repetitions, clippy::cast_possible_truncation)]
 
 use crate::context::MessagesTab;
+use crate::model::messages::format_metrics;
 use crate::model::MessagesModel;
+use crate::view::completion_popup::CompletionPopup;
 use crate::view::history_tab::HistoryTabs;
+use crate::view::json_profile_dialog::JsonProfileDialog;
+use crate::view::wire_dialog::WireDialog;
 use crate::widgets::editor::{view_selected, view_unselected};
+use crate::widgets::modal::centered_rect;
 use edtui::{EditorMode, EditorStatusLine};
 use ratatui::prelude::*;
+use ratatui::widgets::{Block, Clear, Paragraph};
+use wireman_core::descriptor::Position;
 
 /// The request and response tab
 pub struct MessagesPage<'a> {
@@ -16,16 +23,32 @@ pub struct MessagesPage<'a> {
 }
 
 impl MessagesPage<'_> {
-    pub fn footer_keys(tab: MessagesTab, insert_mode: bool) -> Vec<(&'static str, &'static str)> {
+    pub fn footer_keys(
+        tab: MessagesTab,
+        insert_mode: bool,
+        has_problems: bool,
+    ) -> Vec<(&'static str, &'static str)> {
         let mut keys = vec![];
         if tab == MessagesTab::Request {
             keys.push(("⏎", "Request"));
+            if has_problems && !insert_mode {
+                keys.push(("<C-g>", "Next Problem"));
+            }
             keys.push(("<C-s>", "Save"));
             keys.push(("<C-q>", "Reset"));
         } else {
             keys.push(("Y", "Copy"));
+            keys.push(("<C-t>", "Details"));
         }
+        keys.push(("<C-x>", "Syntax"));
+        keys.push(("<C-b>", "Bytes"));
+        keys.push(("<C-o>", "JSON"));
+        keys.push(("<C-l>", "Log"));
+        keys.push(("<C-k>", "Docs"));
         if insert_mode {
+            if tab == MessagesTab::Request {
+                keys.push(("<C-Space>", "Complete"));
+            }
             keys.push(("Esc", "Normal"));
         } else {
             keys.push(("<C-e>", "Open in Editor"));
@@ -50,7 +73,30 @@ impl Widget for MessagesPage<'_> {
             Direction::Vertical => "K",
             Direction::Horizontal => "H",
         };
-        let title = format!(" Request ({key}) ");
+        let title = format!(" Request ({key}) [{}] ", self.model.request.syntax);
+
+        let problems = self.model.validation.problems();
+        let request = if problems.is_empty() {
+            request
+        } else {
+            let cursor = self.model.request.editor.state.cursor;
+            let position = Position::new(cursor.row, cursor.col);
+            let count = match problems.len() {
+                1 => "1 problem".to_string(),
+                n => format!("{n} problems"),
+            };
+            let current = self
+                .model
+                .validation
+                .current(position)
+                .map(ToString::to_string)
+                .unwrap_or_default();
+            let [request, problems_area] = Layout::vertical([Min(0), Length(1)]).areas(request);
+            Line::from(format!(" {count} · {current} "))
+                .red()
+                .render(problems_area, buf);
+            request
+        };
 
         let editor = if self.tab == MessagesTab::Request {
             view_selected(&mut self.model.request.editor.state, title)
@@ -61,12 +107,20 @@ impl Widget for MessagesPage<'_> {
         editor.render(request, buf);
         self.model.request.content_area = Some(request);
 
+        if self.tab == MessagesTab::Request && self.model.completion.is_open() {
+            let popup = CompletionPopup {
+                model: &self.model.completion,
+            };
+            let popup_area = popup.area(request);
+            popup.render(popup_area, buf);
+        }
+
         // Request
         let key = match self.main_split {
             Direction::Vertical => "J",
             Direction::Horizontal => "L",
         };
-        let title = format!(" Response ({key}) ");
+        let title = format!(" Response ({key}) [{}] ", self.model.response.syntax);
 
         let editor = if self.tab == MessagesTab::Response {
             view_selected(&mut self.model.response.editor.state, title)
@@ -74,6 +128,27 @@ impl Widget for MessagesPage<'_> {
             view_unselected(&mut self.model.response.editor.state, title)
         };
 
+        let response = if self.model.response.show_details {
+            let details = &self.model.response.details;
+            let height = details.lines().count().max(1) as u16 + 2;
+            let [response, details_area] =
+                Layout::vertical([Min(0), Length(height.min(response.height / 2))]).areas(response);
+            ResponseDetailsView::new(details).render(details_area, buf);
+            response
+        } else {
+            response
+        };
+
+        let response = if let Some(metrics) = &self.model.response.metrics {
+            let [response, metrics_area] = Layout::vertical([Min(0), Length(1)]).areas(response);
+            Line::from(format!(" {} ", format_metrics(metrics)))
+                .style(theme.base.unfocused)
+                .render(metrics_area, buf);
+            response
+        } else {
+            response
+        };
+
         editor.render(response, buf);
         self.model.response.content_area = Some(response);
 
@@ -113,5 +188,56 @@ impl Widget for MessagesPage<'_> {
             }
             history.render(h, buf);
         }
+
+        if self.model.wire.toggled() {
+            let popup_area = centered_rect(80, 60, main);
+            Clear.render(popup_area, buf);
+            WireDialog {
+                model: &mut self.model.wire,
+            }
+            .render(popup_area, buf);
+        }
+
+        if self.model.json_profile.toggled() {
+            let popup_area = centered_rect(40, 40, main);
+            Clear.render(popup_area, buf);
+            JsonProfileDialog {
+                model: &self.model.json_profile,
+                options: self.model.json_options(),
+            }
+            .render(popup_area, buf);
+        }
+    }
+}
+
+/// The status, headers, trailers and timing of the last call.
+struct ResponseDetailsView<'a> {
+    details: &'a str,
+}
+
+impl<'a> ResponseDetailsView<'a> {
+    fn new(details: &'a str) -> Self {
+        Self { details }
+    }
+}
+
+impl Widget for ResponseDetailsView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = wireman_theme::Theme::global();
+        let block = Block::bordered()
+            .title(" Details ")
+            .title_alignment(Alignment::Center)
+            .title_style(theme.title.unfocused)
+            .border_style(theme.border.unfocused)
+            .border_type(theme.border.border_type_unfocused);
+        let text = if self.details.is_empty() {
+            "No details available."
+        } else {
+            self.details
+        };
+        Paragraph::new(text)
+            .style(theme.base.unfocused)
+            .block(block)
+            .render(area, buf);
     }
 }
@@ -2,6 +2,7 @@ use super::headers::{Address, Authentication};
 use super::root::layout;
 use crate::model::headers::{AuthSelection, HeadersTab};
 use crate::model::reflection::ReflectionModel;
+use crate::model::reflection_cache::format_age;
 use ratatui::layout::{Alignment, Layout};
 use ratatui::style::Stylize;
 use ratatui::text::Line;
@@ -96,15 +97,21 @@ impl Widget for ReflectionDialog {
         body.render(auth_content, buf);
 
         // Status line
-        let line = if let Some(err) = self.model.error {
-            Line::from(err).left_aligned().red()
-        } else {
-            Line::from("Press Enter ")
-                .style(theme.base.unfocused)
-                .right_aligned()
+        let cached = self.model.cached_at().map(format_age);
+        let mut lines = Vec::new();
+        if let Some(err) = self.model.error {
+            lines.push(Line::from(err).left_aligned().red());
+        }
+        let hint = match cached {
+            Some(age) => format!("Cached {age}. <C-o>: Use cache, Enter: Refresh "),
+            None if lines.is_empty() => String::from("Press Enter "),
+            None => String::new(),
         };
+        if !hint.is_empty() {
+            lines.push(Line::from(hint).style(theme.base.unfocused).right_aligned());
+        }
 
-        let paragraph = Paragraph::new(line).wrap(Wrap { trim: true });
+        let paragraph = Paragraph::new(lines).wrap(Wrap { trim: true });
         let line_count = paragraph.line_count(f.width) as u16;
         f.y += max_height_footer.saturating_sub(line_count);
         paragraph.render(f, buf);
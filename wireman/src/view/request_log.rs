@@ -0,0 +1,146 @@
+use crate::{
+    model::request_log::{LogEntry, RequestLogModel},
+    view::util::spans_from_keys,
+    widgets::list::ListItem,
+};
+use ratatui::{
+    layout::Constraint::{Length, Min, Percentage},
+    prelude::*,
+    widgets::{Block, Borders, Padding, Paragraph, Widget},
+};
+use tui_widget_list::{ListBuilder, ListView};
+use wireman_theme::Theme;
+
+/// The browser of the logged calls.
+pub struct RequestLogDialog<'a> {
+    pub model: &'a mut RequestLogModel,
+}
+
+impl RequestLogDialog<'_> {
+    pub fn footer_keys(searching: bool) -> Vec<(&'static str, &'static str)> {
+        if searching {
+            return vec![("⏎", "Finish search")];
+        }
+        vec![
+            ("Esc", "Quit"),
+            ("j/k", "Scroll"),
+            ("⏎", "Open"),
+            ("/", "Search"),
+        ]
+    }
+}
+
+impl Widget for RequestLogDialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = Theme::global();
+
+        let [area, footer] = Layout::vertical([Min(0), Length(1)]).areas(area);
+
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .style(theme.base.focused)
+            .title_style(theme.title.focused)
+            .border_style(theme.border.focused)
+            .border_type(theme.border.border_type_focused)
+            .title(" Request Log ")
+            .title_alignment(Alignment::Center)
+            .padding(Padding::horizontal(1));
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+
+        let [search, main] = Layout::vertical([Length(1), Min(0)]).areas(inner_area);
+        let [list, details] = Layout::horizontal([Percentage(45), Percentage(55)]).areas(main);
+
+        // Search line
+        let style = if self.model.searching {
+            theme.base.focused
+        } else {
+            theme.base.unfocused
+        };
+        Line::from(format!(
+            "Search (/): {}",
+            self.model.filter.as_deref().unwrap_or_default()
+        ))
+        .style(style)
+        .render(search, buf);
+
+        // Logged calls
+        let entries: Vec<String> = self.model.filtered().into_iter().map(summary).collect();
+        let item_count = entries.len();
+        let builder = ListBuilder::new(move |context| {
+            let mut item = ListItem::new(entries[context.index].clone());
+            if context.is_selected {
+                item.style = theme.highlight.focused.reversed();
+            } else {
+                item.style = theme.base.focused;
+            }
+            (item, 1)
+        });
+        if item_count == 0 {
+            Line::from("No calls logged.")
+                .style(theme.base.unfocused)
+                .render(list, buf);
+        } else {
+            ListView::new(builder, item_count).scroll_padding(1).render(
+                list,
+                buf,
+                &mut self.model.state,
+            );
+        }
+
+        // Details of the selected call
+        let detail_block = Block::new()
+            .borders(Borders::LEFT)
+            .border_style(theme.border.unfocused)
+            .padding(Padding::horizontal(1));
+        let text = self.model.selected().map(details_text).unwrap_or_default();
+        Paragraph::new(text)
+            .style(theme.base.focused)
+            .block(detail_block)
+            .render(details, buf);
+
+        let keys = Self::footer_keys(self.model.searching);
+        Paragraph::new(Line::from(spans_from_keys(&keys)))
+            .style(theme.base.focused)
+            .alignment(Alignment::Center)
+            .render(footer, buf);
+    }
+}
+
+/// A single line summary of a logged call.
+fn summary(entry: &LogEntry) -> String {
+    let time = entry.timestamp.get(..19).unwrap_or(&entry.timestamp);
+    let status = if entry.status == "OK" { "OK" } else { "ERR" };
+    format!("{} {status:<3} {}", time.replace('T', " "), entry.method)
+}
+
+/// The details of a logged call.
+fn details_text(entry: &LogEntry) -> Text<'static> {
+    let theme = Theme::global();
+    let title = |title: &str| Line::from(title.to_string()).style(theme.title.unfocused);
+    let mut lines = vec![
+        Line::from(format!("Time:    {}", entry.timestamp)),
+        Line::from(format!("Method:  {}", entry.method)),
+        Line::from(format!("Address: {}", entry.address)),
+        Line::from(format!("Status:  {}", entry.status)),
+    ];
+    if let Some(latency) = entry.latency_ms {
+        lines.push(Line::from(format!("Latency: {latency} ms")));
+    }
+    if !entry.metadata.is_empty() {
+        lines.push(title("Headers"));
+        lines.extend(
+            entry
+                .metadata
+                .iter()
+                .map(|(key, val)| Line::from(format!("  {key}: {val}"))),
+        );
+    }
+    lines.push(title("Request"));
+    lines.extend(entry.request.lines().map(|l| Line::from(l.to_string())));
+    if let Some(response) = &entry.response {
+        lines.push(title("Response"));
+        lines.extend(response.lines().map(|l| Line::from(l.to_string())));
+    }
+    Text::from(lines)
+}
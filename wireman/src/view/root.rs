@@ -1,9 +1,12 @@
 use super::{
-    configuration::ConfigurationDialog, headers::HeadersPage, messages::MessagesPage,
-    selection::SelectionPage, util::spans_from_keys,
+    collections::CollectionsPage, completion_popup::CompletionPopup,
+    configuration::ConfigurationDialog, docs_panel::DocsPanel, headers::HeadersPage,
+    json_profile_dialog::JsonProfileDialog, messages::MessagesPage, request_log::RequestLogDialog,
+    selection::SelectionPage, util::spans_from_keys, wire_dialog::WireDialog,
 };
 use crate::{
     context::{AppContext, Tab},
+    model::{completion::field_at_cursor, messages::Syntax},
     widgets::{help::HelpDialog, modal::centered_rect},
 };
 use ratatui::{
@@ -11,6 +14,7 @@ use ratatui::{
     widgets::{Block, Clear, Paragraph, Tabs, Widget},
 };
 use std::rc::Rc;
+use wireman_core::client::address::Address;
 use wireman_theme::Theme;
 
 pub struct Root<'a> {
@@ -25,14 +29,14 @@ impl<'a> Root<'a> {
 impl Root<'_> {
     fn render_navbar(&self, area: Rect, buf: &mut Buffer) {
         let theme = Theme::global();
-        let [title, tabs] = layout(area, Direction::Horizontal, &[0, 31]);
+        let [title, tabs] = layout(area, Direction::Horizontal, &[0, 45]);
         Block::new().style(theme.base.focused).render(area, buf);
 
         let style = theme.base.unfocused.bold();
         let highlight_style = theme.title.focused;
 
         Paragraph::new(Span::styled("WireMan", theme.highlight.focused).bold()).render(title, buf);
-        let titles = vec!["Endpoints", "Headers", "Request"];
+        let titles = vec!["Endpoints", "Headers", "Request", "Collections"];
         Tabs::new(titles)
             .style(style)
             .highlight_style(highlight_style)
@@ -40,27 +44,18 @@ impl Root<'_> {
             .render(tabs, buf);
 
         // Capture tab areas for hit-testing
-        let third = tabs.width / 3;
-        let rem = tabs.width % 3;
-        let r0 = Rect {
-            x: tabs.x,
-            y: tabs.y,
-            width: third,
-            height: tabs.height,
-        };
-        let r1 = Rect {
-            x: tabs.x + third,
-            y: tabs.y,
-            width: third,
-            height: tabs.height,
-        };
-        let r2 = Rect {
-            x: tabs.x + third * 2,
-            y: tabs.y,
-            width: third + rem,
-            height: tabs.height,
-        };
-        self.ctx.ui.borrow_mut().navbar_tabs = Some([r0, r1, r2]);
+        let quarter = tabs.width / 4;
+        let rem = tabs.width % 4;
+        let mut areas = [Rect::default(); 4];
+        for (i, area) in areas.iter_mut().enumerate() {
+            *area = Rect {
+                x: tabs.x + quarter * i as u16,
+                y: tabs.y,
+                width: if i == 3 { quarter + rem } else { quarter },
+                height: tabs.height,
+            };
+        }
+        self.ctx.ui.borrow_mut().navbar_tabs = Some(areas);
     }
 
     fn render_info(&self, area: Rect, buf: &mut Buffer) {
@@ -78,7 +73,52 @@ impl Root<'_> {
         Paragraph::new(Span::styled(info, theme.base.unfocused)).render(area, buf);
     }
 
+    /// Returns the docs panel of the selection and messages tabs if it is shown.
+    fn docs_panel(&self) -> Option<DocsPanel> {
+        if !self.ctx.ui.borrow().show_docs {
+            return None;
+        }
+        match self.ctx.tab {
+            Tab::Selection => {
+                let selection = self.ctx.selection.borrow();
+                Some(DocsPanel {
+                    service: selection.selected_service(),
+                    method: selection.selected_method(),
+                    field: None,
+                })
+            }
+            Tab::Messages => {
+                let messages = self.ctx.messages.borrow();
+                let method = messages.selected_method.clone();
+                let field = method.as_ref().and_then(|method| {
+                    if messages.request.syntax != Syntax::Json {
+                        return None;
+                    }
+                    let text = messages.request.editor.get_text_before_cursor();
+                    field_at_cursor(&text, &method.input())
+                });
+                Some(DocsPanel {
+                    service: method
+                        .as_ref()
+                        .map(|method| method.parent_service().clone()),
+                    method,
+                    field,
+                })
+            }
+            Tab::Headers | Tab::Collections => None,
+        }
+    }
+
     fn render_content(&self, area: Rect, buf: &mut Buffer) {
+        let area = match self.docs_panel() {
+            Some(docs) => {
+                use ratatui::layout::Constraint::{Min, Percentage};
+                let [area, docs_area] = Layout::horizontal([Min(0), Percentage(35)]).areas(area);
+                docs.render(docs_area, buf);
+                area
+            }
+            None => area,
+        };
         match self.ctx.tab {
             Tab::Selection => SelectionPage {
                 model: &mut self.ctx.selection.borrow_mut(),
@@ -101,22 +141,53 @@ impl Root<'_> {
             Tab::Headers => {
                 let headers_rc = Rc::clone(&self.ctx.headers);
                 let mut history_tabs_area = self.ctx.ui.borrow().history_tabs;
+                let address = self.ctx.headers.borrow().address();
+                let core_client = self.ctx.messages.borrow().request.core_client.clone();
+                let state = core_client.borrow().connection_state(&address);
+                let address_error = Address::parse(&core_client.borrow().substitute(&address))
+                    .err()
+                    .map(|err| err.to_string());
                 HeadersPage::new(headers_rc)
                     .with_history_tabs_area(&mut history_tabs_area)
+                    .with_connection_state(state)
+                    .with_address_error(address_error)
                     .render(area, buf);
                 self.ctx.ui.borrow_mut().history_tabs = history_tabs_area;
             }
+            Tab::Collections => CollectionsPage {
+                model: &mut self.ctx.collections.borrow_mut(),
+                method: self
+                    .ctx
+                    .messages
+                    .borrow()
+                    .selected_method
+                    .as_ref()
+                    .map(|method| method.full_name().to_string()),
+                main_split: self.ctx.ui.borrow().main_split,
+            }
+            .render(area, buf),
         };
     }
 
     fn render_footer(&self, area: Rect, buf: &mut Buffer) {
         let keys = match self.ctx.tab {
             Tab::Selection => SelectionPage::footer_keys(self.ctx.selection_tab),
+            Tab::Messages if self.ctx.messages.borrow().json_profile.toggled() => {
+                JsonProfileDialog::footer_keys()
+            }
+            Tab::Messages if self.ctx.messages.borrow().completion.is_open() => {
+                CompletionPopup::footer_keys()
+            }
+            Tab::Messages if self.ctx.messages.borrow().wire.toggled() => {
+                WireDialog::footer_keys(self.ctx.messages.borrow().wire.encoded.is_some())
+            }
             Tab::Messages => MessagesPage::footer_keys(
                 self.ctx.messages_tab,
                 self.ctx.messages.borrow().request.editor.insert_mode(),
+                !self.ctx.messages.borrow().validation.problems().is_empty(),
             ),
             Tab::Headers => HeadersPage::footer_keys(&self.ctx.headers.borrow()),
+            Tab::Collections => CollectionsPage::footer_keys(&self.ctx.collections.borrow()),
         };
         let spans = spans_from_keys(&keys);
         Paragraph::new(Line::from(spans))
@@ -157,6 +228,15 @@ impl Widget for Root<'_> {
             }
             .render(popup_area, buf);
         }
+
+        if self.ctx.request_log.borrow().toggled() {
+            let popup_area = centered_rect(90, 80, area);
+            Clear.render(popup_area, buf);
+            RequestLogDialog {
+                model: &mut self.ctx.request_log.borrow_mut(),
+            }
+            .render(popup_area, buf);
+        }
     }
 }
 
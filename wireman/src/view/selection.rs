@@ -33,6 +33,7 @@ impl SelectionPage<'_> {
             keys.push(("Esc", "Unselect"));
         }
         keys.push(("/", "Search"));
+        keys.push(("<C-k>", "Docs"));
         keys.push(("?", "Show help"));
         keys
     }
@@ -68,7 +69,16 @@ impl Widget for SelectionPage<'_> {
         // Services
         let is_selected = [SelectionTab::Services].contains(&self.tab);
 
-        let services = self.model.services();
+        // Mark the source of each service if a server was reflected
+        let services: Vec<String> = self
+            .model
+            .services()
+            .into_iter()
+            .map(|service| match self.model.service_source(&service) {
+                Some(source) => format!("{service} [{source}]"),
+                None => service,
+            })
+            .collect();
         let services_state = &mut self.model.services_state;
 
         let (border_style, title_style) = if is_selected {
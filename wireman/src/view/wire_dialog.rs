@@ -0,0 +1,77 @@
+use super::root::layout;
+use crate::model::wire::{WireModel, WireSelection};
+use crate::widgets::editor::{view_single_selected, view_single_unselected};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Padding, Paragraph, Wrap};
+use wireman_theme::Theme;
+
+/// The dialog to decode wire bytes into a message editor and to
+/// encode the request.
+pub struct WireDialog<'a> {
+    pub model: &'a mut WireModel,
+}
+
+impl WireDialog<'_> {
+    pub fn footer_keys(encoded: bool) -> Vec<(&'static str, &'static str)> {
+        let mut keys = vec![("⏎", "Decode"), ("j/k", "Field")];
+        if encoded {
+            keys.push(("<C-y>", "Copy Base64"));
+            keys.push(("<C-x>", "Copy Hex"));
+        }
+        keys.push(("Esc", "Close"));
+        keys
+    }
+}
+
+impl Widget for WireDialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = Theme::global();
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .style(theme.base.focused)
+            .title_style(theme.title.focused)
+            .border_style(theme.border.focused)
+            .border_type(theme.border.border_type_focused)
+            .title(" Wire Bytes ")
+            .title_alignment(Alignment::Center)
+            .padding(Padding::horizontal(1));
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+
+        let [message_type, input, _, encoded] =
+            layout(inner_area, Direction::Vertical, &[3, 3, 1, 0]);
+
+        let title = " Message type ";
+        if self.model.selected == WireSelection::MessageType {
+            view_single_selected(&mut self.model.message_type.state, title)
+                .render(message_type, buf);
+        } else {
+            view_single_unselected(&mut self.model.message_type.state, title)
+                .render(message_type, buf);
+        }
+
+        let title = " Bytes (base64, hex or @file) ";
+        if self.model.selected == WireSelection::Input {
+            view_single_selected(&mut self.model.input.state, title).render(input, buf);
+        } else {
+            view_single_unselected(&mut self.model.input.state, title).render(input, buf);
+        }
+
+        // The encoded request and the error of the last action
+        let mut lines = Vec::new();
+        if let Some((base64, hex)) = &self.model.encoded {
+            let title = |title: &str| Line::from(title.to_string()).style(theme.title.unfocused);
+            lines.push(title("Request as base64"));
+            lines.push(Line::from(base64.clone()));
+            lines.push(title("Request as hex"));
+            lines.push(Line::from(hex.clone()));
+        }
+        if let Some(err) = &self.model.error {
+            lines.push(Line::from(err.clone()).red());
+        }
+        Paragraph::new(lines)
+            .style(theme.base.focused)
+            .wrap(Wrap { trim: false })
+            .render(encoded, buf);
+    }
+}
@@ -3,15 +3,16 @@ use crossterm::event::MouseEvent;
 use crossterm::event::{KeyCode, KeyEvent};
 use edtui::EditorEventHandler;
 use edtui::{
-    actions::{Execute, SwitchMode},
+    actions::{DeleteChar, Execute, InsertChar, SwitchMode},
     clipboard::ClipboardTrait,
-    EditorMode, EditorState, EditorTheme, EditorView, Index2, Lines, RowIndex,
+    EditorMode, EditorState, EditorTheme, EditorView, Highlight, Index2, Lines, RowIndex,
 };
 use ratatui::{
     prelude::*,
     widgets::{Block, Borders},
 };
 use std::sync::{Mutex, OnceLock};
+use wireman_core::descriptor::strip_json_comments;
 use wireman_logger::Logger;
 use wireman_theme::Theme;
 
@@ -107,6 +108,43 @@ impl TextEditor {
         }
     }
 
+    /// Gets the editors content up to the cursor
+    pub fn get_text_before_cursor(&self) -> String {
+        let cursor = self.state.cursor;
+        let mut text = String::new();
+        for (row, line) in self.state.lines.iter_row().enumerate().take(cursor.row + 1) {
+            if row == cursor.row {
+                text.extend(line.iter().take(cursor.col));
+            } else {
+                text.extend(line.iter());
+                text.push('\n');
+            }
+        }
+        text
+    }
+
+    /// Replaces the `len` characters before the cursor with a text and
+    /// moves the cursor behind it. The text must not contain line breaks.
+    /// The replacement is applied with the editor's actions, so it can be
+    /// undone.
+    pub fn replace_before_cursor(&mut self, len: usize, text: &str) {
+        let len = len.min(self.state.cursor.col);
+        DeleteChar(len).execute(&mut self.state);
+        for c in text.chars() {
+            InsertChar(c).execute(&mut self.state);
+        }
+    }
+
+    /// Highlights spans of the text, replacing the previous highlights.
+    /// The end of each span is inclusive.
+    pub fn highlight(&mut self, spans: impl IntoIterator<Item = (Index2, Index2)>) {
+        let style = Style::new().red().underlined();
+        self.state.clear_highlights();
+        for (start, end) in spans {
+            self.state.add_highlight(Highlight::new(start, end, style));
+        }
+    }
+
     /// Return the error
     pub fn get_error(&self) -> Option<ErrorKind> {
         self.error.clone()
@@ -250,6 +288,20 @@ impl ErrorKind {
         }
     }
 
+    pub fn config_error(msg: String) -> Self {
+        Self {
+            kind: "Config Error".to_owned(),
+            msg,
+        }
+    }
+
+    pub fn deadline_exceeded(msg: String) -> Self {
+        Self {
+            kind: "Deadline Exceeded".to_owned(),
+            msg,
+        }
+    }
+
     pub fn string(&self) -> String {
         format!("{}: {}", self.kind, self.msg)
     }
@@ -263,14 +315,19 @@ impl From<serde_json::Error> for ErrorKind {
 
 impl From<wireman_core::error::Error> for ErrorKind {
     fn from(err: wireman_core::error::Error) -> Self {
-        Self::default_error(err.to_string())
+        match err {
+            wireman_core::error::Error::DeadlineExceeded(status) => {
+                Self::deadline_exceeded(status.message)
+            }
+            err => Self::default_error(err.to_string()),
+        }
     }
 }
 
-/// Pretty formats a string assuming it is in json format.
-/// Returns an error if formatting fails.
+/// Pretty formats a string assuming it is in json format. Comments
+/// are dropped. Returns an error if formatting fails.
 pub fn pretty_format_json(input: &str) -> Result<String, ErrorKind> {
-    let parsed = serde_json::from_str::<serde_json::Value>(input)?;
+    let parsed = serde_json::from_str::<serde_json::Value>(&strip_json_comments(input))?;
     let pretty = serde_json::to_string_pretty(&parsed)?;
     Ok(pretty)
 }